@@ -45,6 +45,8 @@ pub mod pallet {
   use super::*;
   use frame_support::pallet_prelude::*;
   use pallet_network::AdminInterface as NetworkAdminInterface;
+  use pallet_network::ValidatorSelectionMode;
+  use pallet_network::SubnetNodeClass;
   use pallet_subnet_democracy::AdminInterface as SubnetDemocracyAdminInterface;
 
   #[pallet::config]
@@ -250,5 +252,132 @@ pub mod pallet {
       ensure_root(origin)?;
       T::SubnetDemocracyAdminInterface::set_quorum(value)
     }
+
+    #[pallet::call_index(23)]
+    #[pallet::weight(0)]
+    pub fn set_subnet_removal_bounty(origin: OriginFor<T>, value: u128) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_subnet_removal_bounty(value)
+    }
+
+    #[pallet::call_index(24)]
+    #[pallet::weight(0)]
+    pub fn set_stake_unbonding_epochs(origin: OriginFor<T>, value: u64) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_stake_unbonding_epochs(value)
+    }
+
+    #[pallet::call_index(25)]
+    #[pallet::weight(0)]
+    pub fn set_validator_selection(origin: OriginFor<T>, value: ValidatorSelectionMode) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_validator_selection(value)
+    }
+
+    #[pallet::call_index(26)]
+    #[pallet::weight(0)]
+    pub fn set_subnet_validator_selection(
+      origin: OriginFor<T>, 
+      subnet_id: u32, 
+      value: Option<ValidatorSelectionMode>
+    ) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_subnet_validator_selection(subnet_id, value)
+    }
+
+    #[pallet::call_index(27)]
+    #[pallet::weight(0)]
+    pub fn set_validator_stake_weight_cap(origin: OriginFor<T>, value: u128) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_validator_stake_weight_cap(value)
+    }
+
+    #[pallet::call_index(28)]
+    #[pallet::weight(0)]
+    pub fn set_validators_per_epoch(origin: OriginFor<T>, value: u32) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_validators_per_epoch(value)
+    }
+
+    #[pallet::call_index(29)]
+    #[pallet::weight(0)]
+    pub fn set_node_commission_change_delay(origin: OriginFor<T>, value: u64) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_node_commission_change_delay(value)
+    }
+
+    #[pallet::call_index(30)]
+    #[pallet::weight(0)]
+    pub fn set_stake_move_cooldown_epochs(origin: OriginFor<T>, value: u64) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_stake_move_cooldown_epochs(value)
+    }
+
+    #[pallet::call_index(31)]
+    #[pallet::weight(0)]
+    pub fn set_epoch_rewards_retention_epochs(origin: OriginFor<T>, value: u32) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_epoch_rewards_retention_epochs(value)
+    }
+
+    #[pallet::call_index(32)]
+    #[pallet::weight(0)]
+    pub fn set_subnet_node_class_epochs(origin: OriginFor<T>, class_id: SubnetNodeClass, value: u64) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_subnet_node_class_epochs(class_id, value)
+    }
+
+    #[pallet::call_index(33)]
+    #[pallet::weight(0)]
+    pub fn set_subnet_node_class_epochs_override(
+      origin: OriginFor<T>, 
+      subnet_id: u32, 
+      class_epochs: Vec<(SubnetNodeClass, u64)>
+    ) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_subnet_node_class_epochs_override(subnet_id, class_epochs)
+    }
+
+    #[pallet::call_index(34)]
+    #[pallet::weight(0)]
+    pub fn set_node_class_demotion_absent_threshold(origin: OriginFor<T>, value: u32) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_node_class_demotion_absent_threshold(value)
+    }
+
+    #[pallet::call_index(35)]
+    #[pallet::weight(0)]
+    pub fn set_node_class_demotion_penalty_threshold(origin: OriginFor<T>, value: u32) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_node_class_demotion_penalty_threshold(value)
+    }
+
+    #[pallet::call_index(36)]
+    #[pallet::weight(0)]
+    pub fn cancel_proposal_enactment(origin: OriginFor<T>, proposal_index: u32) -> DispatchResult {
+      ensure_root(origin)?;
+      T::SubnetDemocracyAdminInterface::cancel_enactment(proposal_index)
+    }
+
+    #[pallet::call_index(37)]
+    #[pallet::weight(0)]
+    pub fn set_base_subnet_reward(origin: OriginFor<T>, value: u128) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_base_subnet_reward(value)
+    }
+
+    #[pallet::call_index(38)]
+    #[pallet::weight(0)]
+    pub fn set_slash_percentage(origin: OriginFor<T>, value: u128) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_slash_percentage(value)
+    }
+
+    #[pallet::call_index(39)]
+    #[pallet::weight(0)]
+    pub fn set_max_node_delegate_stake_ratio(origin: OriginFor<T>, value: u128) -> DispatchResult {
+      ensure_root(origin)?;
+      T::NetworkAdminInterface::set_max_node_delegate_stake_ratio(value)
+    }
   }
 }
\ No newline at end of file
@@ -148,6 +148,8 @@ impl pallet_network::Config for Test {
   type OffchainSignature = Signature;
 	type OffchainPublic = AccountPublic;
   type Randomness = InsecureRandomnessCollectiveFlip;
+  type ElectionRandomness = pallet_network::BlockRandomness<Test>;
+  type StakeVaultSubsidy = ();
 	type PalletId = NetworkPalletId;
   type SubnetInitializationCost = SubnetInitializationCost;
 }
@@ -155,6 +157,7 @@ impl pallet_network::Config for Test {
 parameter_types! {
 	pub const VotingPeriod: BlockNumber = DAYS * 21;
 	pub const EnactmentPeriod: BlockNumber = DAYS * 7;
+	pub const VoteLockingPeriod: BlockNumber = DAYS * 7;
   pub const MinProposalStake: u128 = 100_000_000_000_000_000_000; // 100 * 1e18
 }
 
@@ -162,12 +165,14 @@ impl pallet_subnet_democracy::Config for Test {
 	type WeightInfo = ();
 	type RuntimeEvent = RuntimeEvent;
 	type SubnetVote = Network;
+	type NetworkAdminInterface = Network;
 	type Currency = Balances;
 	type MaxActivateProposals = ConstU32<32>;
 	type MaxDeactivateProposals = ConstU32<32>;
 	type MaxProposals = ConstU32<32>;
 	type VotingPeriod = VotingPeriod;
 	type EnactmentPeriod = EnactmentPeriod;
+	type VoteLockingPeriod = VoteLockingPeriod;
   type MinProposalStake = MinProposalStake;
 }
 
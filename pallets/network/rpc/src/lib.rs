@@ -1,14 +1,27 @@
 pub use network_custom_rpc_runtime_api::NetworkRuntimeApi;
+use codec::{Codec, Decode};
+use futures::{future, FutureExt, StreamExt};
 use jsonrpsee::{
 	core::{Error as JsonRpseeError, RpcResult},
 	proc_macros::rpc,
-	types::error::{CallError, ErrorObject},
+	types::{
+		error::{CallError, ErrorObject},
+		SubscriptionResult,
+	},
+	SubscriptionSink,
 };
-use sp_api::ProvideRuntimeApi;
+use sc_client_api::BlockchainEvents;
+use sc_rpc::SubscriptionTaskExecutor;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
+use sp_core::crypto::Ss58Codec;
+use sp_rpc::number::NumberOrHex;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::sync::Arc;
 
+mod types;
+pub use types::*;
+
 #[derive(serde::Deserialize, serde::Serialize)]
 pub struct Custom {
 	code: u32,
@@ -18,35 +31,59 @@ pub struct Custom {
 #[rpc(client, server)]
 pub trait NetworkCustomApi<BlockHash> {
 	#[method(name = "network_getSubnetNodes")]
-	fn get_subnet_nodes(&self, model_id: u32, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
+	fn get_subnet_nodes(&self, subnet_id: u32, at: Option<BlockHash>) -> RpcResult<Vec<SubnetNodeJson>>;
 	#[method(name = "network_getSubnetNodesIncluded")]
-	fn get_subnet_nodes_included(&self, model_id: u32, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
+	fn get_subnet_nodes_included(&self, subnet_id: u32, at: Option<BlockHash>) -> RpcResult<Vec<SubnetNodeJson>>;
 	#[method(name = "network_getSubnetNodesSubmittable")]
-	fn get_subnet_nodes_submittable(&self, model_id: u32, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
+	fn get_subnet_nodes_submittable(&self, subnet_id: u32, at: Option<BlockHash>) -> RpcResult<Vec<SubnetNodeJson>>;
 	#[method(name = "network_getSubnetNodesUnconfirmedCount")]
 	fn get_subnet_nodes_model_unconfirmed_count(&self, model_id: u32, at: Option<BlockHash>) -> RpcResult<u32>;
 	#[method(name = "network_getConsensusData")]
-	fn get_consensus_data(&self, model_id: u32, epoch: u32, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
+	fn get_consensus_data(&self, subnet_id: u32, epoch: u32, at: Option<BlockHash>) -> RpcResult<Option<RewardsDataJson>>;
 	#[method(name = "network_getAccountantData")]
-	fn get_accountant_data(&self, model_id: u32, id: u32, at: Option<BlockHash>) -> RpcResult<Vec<u8>>;
+	fn get_accountant_data(&self, subnet_id: u32, id: u32, at: Option<BlockHash>) -> RpcResult<Option<AccountantDataJson>>;
 	#[method(name = "network_getMinimumSubnetNodes")]
 	fn get_minimum_subnet_nodes(&self, subnet_id: u32, memory_mb: u128, at: Option<BlockHash>) -> RpcResult<u32>;
+	#[method(name = "network_getAccountInfo")]
+	fn get_account_info(&self, account_id: String, at: Option<BlockHash>) -> RpcResult<AccountNetworkInfoJson>;
+	#[method(name = "network_getSubnetEpochInfo")]
+	fn get_subnet_epoch_info(&self, subnet_id: u32, account_id: String, at: Option<BlockHash>) -> RpcResult<SubnetEpochInfoJson>;
+	#[method(name = "network_getRewardProjection")]
+	fn get_reward_projection(
+		&self,
+		subnet_id: u32,
+		account_id: String,
+		stake: NumberOrHex,
+		delegate_stake: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> RpcResult<RewardProjectionJson>;
+	#[method(name = "network_getEpochRewards")]
+	fn get_epoch_rewards(&self, subnet_id: u32, epoch: u32, at: Option<BlockHash>) -> RpcResult<Option<EpochRewardsJson>>;
 
+	/// Notifies once per epoch when the validators and accountants of the epoch are chosen
+	#[subscription(
+		name = "network_subscribeEpochRoles" => "network_epochRoles",
+		unsubscribe = "network_unsubscribeEpochRoles",
+		item = SubnetEpochInfoJson
+	)]
+	fn subscribe_epoch_roles(&self, subnet_id: u32, account_id: String);
 }
 
 /// A struct that implements the `NetworkCustomApi`.
-pub struct NetworkCustom<C, Block> {
+pub struct NetworkCustom<C, Block, AccountId> {
 	// If you have more generics, no need to NetworkCustom<C, M, N, P, ...>
 	// just use a tuple like NetworkCustom<C, (M, N, P, ...)>
 	client: Arc<C>,
-	_marker: std::marker::PhantomData<Block>,
+	executor: SubscriptionTaskExecutor,
+	_marker: std::marker::PhantomData<(Block, AccountId)>,
 }
 
-impl<C, Block> NetworkCustom<C, Block> {
+impl<C, Block, AccountId> NetworkCustom<C, Block, AccountId> {
 	/// Create new `NetworkCustom` instance with the given reference to the client.
-	pub fn new(client: Arc<C>) -> Self {
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
 		Self { 
       client, 
+      executor,
       _marker: Default::default() 
     }
 	}
@@ -56,61 +93,255 @@ impl<C, Block> NetworkCustom<C, Block> {
 pub enum Error {
   /// The call to runtime failed.
   RuntimeError,
+  /// The account could not be decoded.
+  InvalidAccount,
+  /// The balance doesn't fit in a `u128`.
+  InvalidBalance,
+  /// The runtime API at the block doesn't support the call.
+  UnsupportedApiVersion,
 }
 
 impl From<Error> for i32 {
   fn from(e: Error) -> i32 {
       match e {
           Error::RuntimeError => 1,
+          Error::InvalidAccount => 2,
+          Error::InvalidBalance => 3,
+          Error::UnsupportedApiVersion => 4,
       }
   }
 }
 
-impl<C, Block> NetworkCustomApiServer<<Block as BlockT>::Hash> for NetworkCustom<C, Block>
+impl<C, Block, AccountId> NetworkCustom<C, Block, AccountId>
 where
 	Block: BlockT,
+	AccountId: Codec,
 	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: NetworkRuntimeApi<Block>,
+	C::Api: NetworkRuntimeApi<Block, AccountId>,
 {
-	fn get_subnet_nodes(&self, model_id: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<u8>> {
+	/// Version of the runtime API at `at`
+	// Runtimes before version 2 return SCALE-encoded bytes that are decoded here
+	fn api_version(&self, at: <Block as BlockT>::Hash) -> RpcResult<u32> {
+		self.client
+			.runtime_api()
+			.api_version::<dyn NetworkRuntimeApi<Block, AccountId>>(at)
+			.map_err(runtime_error_into_rpc_err)?
+			.ok_or_else(|| runtime_error_into_rpc_err("NetworkRuntimeApi not found"))
+	}
+
+	/// Ensure the runtime API at `at` is at least `version`
+	fn ensure_api_version(&self, at: <Block as BlockT>::Hash, version: u32) -> RpcResult<()> {
+		let api_version = self.api_version(at)?;
+		if api_version < version {
+			return Err(unsupported_api_version_into_rpc_err(api_version, version))
+		}
+		Ok(())
+	}
+}
+
+/// Decode a SCALE-encoded value returned by runtime API versions before 2
+fn decode_legacy<T: Decode>(encoded: Vec<u8>) -> RpcResult<T> {
+	T::decode(&mut &encoded[..]).map_err(runtime_error_into_rpc_err)
+}
+
+impl<C, Block, AccountId> NetworkCustomApiServer<<Block as BlockT>::Hash> for NetworkCustom<C, Block, AccountId>
+where
+	Block: BlockT,
+	AccountId: Codec + Ss58Codec + Ord + Clone + Send + Sync + 'static,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockchainEvents<Block>,
+	C::Api: NetworkRuntimeApi<Block, AccountId>,
+{
+	fn get_subnet_nodes(&self, subnet_id: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<SubnetNodeJson>> {
 		let api = self.client.runtime_api();
 		let at = at.unwrap_or_else(|| self.client.info().best_hash);
-		api.get_subnet_nodes(at, model_id).map_err(runtime_error_into_rpc_err)
+		let subnet_nodes = if self.api_version(at)? < 2 {
+			#[allow(deprecated)]
+			let encoded = api.get_subnet_nodes_before_version_2(at, subnet_id).map_err(runtime_error_into_rpc_err)?;
+			decode_legacy::<Vec<pallet_network::SubnetNode<AccountId>>>(encoded)?
+		} else {
+			api.get_subnet_nodes(at, subnet_id).map_err(runtime_error_into_rpc_err)?
+		};
+		Ok(subnet_nodes.into_iter().map(Into::into).collect())
 	}
-	fn get_subnet_nodes_included(&self, model_id: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<u8>> {
+	fn get_subnet_nodes_included(&self, subnet_id: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<SubnetNodeJson>> {
 		let api = self.client.runtime_api();
 		let at = at.unwrap_or_else(|| self.client.info().best_hash);
-		api.get_subnet_nodes_included(at, model_id).map_err(runtime_error_into_rpc_err)
+		let subnet_nodes = if self.api_version(at)? < 2 {
+			#[allow(deprecated)]
+			let encoded = api.get_subnet_nodes_included_before_version_2(at, subnet_id).map_err(runtime_error_into_rpc_err)?;
+			decode_legacy::<Vec<pallet_network::SubnetNode<AccountId>>>(encoded)?
+		} else {
+			api.get_subnet_nodes_included(at, subnet_id).map_err(runtime_error_into_rpc_err)?
+		};
+		Ok(subnet_nodes.into_iter().map(Into::into).collect())
 	}
-	fn get_subnet_nodes_submittable(&self, model_id: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<u8>> {
+	fn get_subnet_nodes_submittable(&self, subnet_id: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<SubnetNodeJson>> {
 		let api = self.client.runtime_api();
 		let at = at.unwrap_or_else(|| self.client.info().best_hash);
-		api.get_subnet_nodes_submittable(at, model_id).map_err(runtime_error_into_rpc_err)
+		let subnet_nodes = if self.api_version(at)? < 2 {
+			#[allow(deprecated)]
+			let encoded = api.get_subnet_nodes_submittable_before_version_2(at, subnet_id).map_err(runtime_error_into_rpc_err)?;
+			decode_legacy::<Vec<pallet_network::SubnetNode<AccountId>>>(encoded)?
+		} else {
+			api.get_subnet_nodes_submittable(at, subnet_id).map_err(runtime_error_into_rpc_err)?
+		};
+		Ok(subnet_nodes.into_iter().map(Into::into).collect())
 	}
 	fn get_subnet_nodes_model_unconfirmed_count(&self, model_id: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u32> {
 		let api = self.client.runtime_api();
 		let at = at.unwrap_or_else(|| self.client.info().best_hash);
 		api.get_subnet_nodes_model_unconfirmed_count(at, model_id).map_err(runtime_error_into_rpc_err)
 	}
-	fn get_consensus_data(&self, model_id: u32, epoch: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<u8>> {
+	fn get_consensus_data(&self, subnet_id: u32, epoch: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Option<RewardsDataJson>> {
 		let api = self.client.runtime_api();
 		let at = at.unwrap_or_else(|| self.client.info().best_hash);
-		api.get_consensus_data(at, model_id, epoch).map_err(runtime_error_into_rpc_err)
+		if self.api_version(at)? < 2 {
+			#[allow(deprecated)]
+			let encoded = api.get_consensus_data_before_version_2(at, subnet_id, epoch).map_err(runtime_error_into_rpc_err)?;
+			let rewards_data = decode_legacy::<Option<LegacyRewardsData<AccountId>>>(encoded)?;
+			return Ok(rewards_data.map(Into::into))
+		}
+		let rewards_data = api.get_consensus_data(at, subnet_id, epoch).map_err(runtime_error_into_rpc_err)?;
+		Ok(rewards_data.map(Into::into))
 	}
-	fn get_accountant_data(&self, model_id: u32, id: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<u8>> {
+	fn get_accountant_data(&self, subnet_id: u32, id: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Option<AccountantDataJson>> {
 		let api = self.client.runtime_api();
 		let at = at.unwrap_or_else(|| self.client.info().best_hash);
-		api.get_accountant_data(at, model_id, id).map_err(runtime_error_into_rpc_err)
+		let accountant_data = if self.api_version(at)? < 2 {
+			#[allow(deprecated)]
+			let encoded = api.get_accountant_data_before_version_2(at, subnet_id, id).map_err(runtime_error_into_rpc_err)?;
+			decode_legacy::<Option<pallet_network::AccountantDataParams<AccountId>>>(encoded)?
+		} else {
+			api.get_accountant_data(at, subnet_id, id).map_err(runtime_error_into_rpc_err)?
+		};
+		Ok(accountant_data.map(Into::into))
 	}
 	fn get_minimum_subnet_nodes(&self, subnet_id: u32, memory_mb: u128, at: Option<<Block as BlockT>::Hash>) -> RpcResult<u32> {
 		let api = self.client.runtime_api();
 		let at = at.unwrap_or_else(|| self.client.info().best_hash);
 		api.get_minimum_subnet_nodes(at, subnet_id, memory_mb).map_err(runtime_error_into_rpc_err)
 	}
+	fn get_account_info(&self, account_id: String, at: Option<<Block as BlockT>::Hash>) -> RpcResult<AccountNetworkInfoJson> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_api_version(at, 3)?;
+		let account_id = account_from_string::<AccountId>(&account_id)?;
+		api.get_account_info(at, account_id).map(Into::into).map_err(runtime_error_into_rpc_err)
+	}
+	fn get_subnet_epoch_info(&self, subnet_id: u32, account_id: String, at: Option<<Block as BlockT>::Hash>) -> RpcResult<SubnetEpochInfoJson> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_api_version(at, 3)?;
+		let account_id = account_from_string::<AccountId>(&account_id)?;
+		api.get_subnet_epoch_info(at, subnet_id, account_id).map(Into::into).map_err(runtime_error_into_rpc_err)
+	}
+	fn get_reward_projection(
+		&self,
+		subnet_id: u32,
+		account_id: String,
+		stake: NumberOrHex,
+		delegate_stake: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<RewardProjectionJson> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_api_version(at, 3)?;
+		let account_id = account_from_string::<AccountId>(&account_id)?;
+		let stake = balance_from_number(stake)?;
+		let delegate_stake = balance_from_number(delegate_stake)?;
+		api.get_reward_projection(at, subnet_id, account_id, stake, delegate_stake)
+			.map(Into::into)
+			.map_err(runtime_error_into_rpc_err)
+	}
+	fn get_epoch_rewards(&self, subnet_id: u32, epoch: u32, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Option<EpochRewardsJson>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_api_version(at, 3)?;
+		api.get_epoch_rewards(at, subnet_id, epoch)
+			.map(|epoch_rewards| epoch_rewards.map(Into::into))
+			.map_err(runtime_error_into_rpc_err)
+	}
+	fn subscribe_epoch_roles(&self, mut sink: SubscriptionSink, subnet_id: u32, account_id: String) -> SubscriptionResult {
+		let account_id = match account_from_string::<AccountId>(&account_id) {
+			Ok(account_id) => account_id,
+			Err(err) => {
+				let _ = sink.reject(err);
+				return Ok(())
+			},
+		};
+
+		// Roles are chosen on the second block of each epoch so only the first new best block
+		// of each epoch with assigned roles is sent
+		let client = self.client.clone();
+		let mut last_epoch: Option<u32> = None;
+		let stream = self.client
+			.import_notification_stream()
+			.filter(|notification| future::ready(notification.is_new_best))
+			.filter_map(move |notification| {
+				let api = client.runtime_api();
+				// Blocks whose runtime predates version 3 have no epoch roles to send
+				let supported = api
+					.api_version::<dyn NetworkRuntimeApi<Block, AccountId>>(notification.hash)
+					.ok()
+					.flatten()
+					.map_or(false, |version| version >= 3);
+				let epoch_info = supported
+					.then(|| api.get_subnet_epoch_info(notification.hash, subnet_id, account_id.clone()).ok())
+					.flatten()
+					.filter(|info| info.current_roles.assigned && last_epoch != Some(info.current_roles.epoch));
+
+				if let Some(info) = &epoch_info {
+					last_epoch = Some(info.current_roles.epoch);
+				}
+
+				future::ready(epoch_info.map(SubnetEpochInfoJson::from))
+			});
+
+		let fut = async move {
+			sink.pipe_from_stream(stream).await;
+		};
+
+		self.executor.spawn("network-rpc-subscription", Some("rpc"), fut.boxed());
+		Ok(())
+	}
 }
 
 const RUNTIME_ERROR: i32 = 1;
 
+/// Parse an SS58 encoded account passed to the RPC
+fn account_from_string<AccountId: Ss58Codec>(account_id: &str) -> RpcResult<AccountId> {
+  AccountId::from_ss58check(account_id).map_err(|err| {
+    CallError::Custom(ErrorObject::owned(
+      Error::InvalidAccount.into(),
+      "Invalid SS58 account",
+      Some(format!("{:?}", err)),
+    ))
+    .into()
+  })
+}
+
+/// Parse a balance passed to the RPC as a number or hex string
+fn balance_from_number(balance: NumberOrHex) -> RpcResult<u128> {
+  u128::try_from(balance).map_err(|err| {
+    CallError::Custom(ErrorObject::owned(
+      Error::InvalidBalance.into(),
+      "Balance doesn't fit in u128",
+      Some(format!("{:?}", err)),
+    ))
+    .into()
+  })
+}
+
+/// Converts a call to a runtime API version without the method into an RPC error.
+fn unsupported_api_version_into_rpc_err(api_version: u32, required: u32) -> JsonRpseeError {
+  CallError::Custom(ErrorObject::owned(
+    Error::UnsupportedApiVersion.into(),
+    "Unsupported runtime API version",
+    Some(format!("NetworkRuntimeApi version {} is required, the runtime at this block has version {}", required, api_version)),
+  ))
+  .into()
+}
+
 /// Converts a runtime trap into an RPC error.
 fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> JsonRpseeError {
   CallError::Custom(ErrorObject::owned(
@@ -0,0 +1,415 @@
+//! JSON representations of the network runtime API values
+//!
+//! Accounts are SS58 encoded, peer IDs are base58 encoded and `u128` values are
+//! decimal strings so they don't lose precision in JSON clients.
+
+use pallet_network::{
+	AccountNetworkInfo, AccountSubnetInfo, AccountantDataNodeParams, AccountantDataParams,
+	AttestationData, EpochRewardsData, EpochRoles, EpochSchedule, NodeDelegateStakeInfo, NodeEpochReward,
+	RewardProjection, RewardsData, SubnetEpochInfo, SubnetNode, SubnetNodeData,
+};
+use codec::Decode;
+use serde::{Deserialize, Serialize};
+use sp_core::{crypto::Ss58Codec, hexdisplay::HexDisplay, OpaquePeerId as PeerId};
+use std::collections::BTreeSet;
+
+pub fn account_to_string<AccountId: Ss58Codec>(account_id: &AccountId) -> String {
+	account_id.to_ss58check()
+}
+
+pub fn peer_id_to_string(peer_id: &PeerId) -> String {
+	bs58::encode(&peer_id.0).into_string()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubnetNodeJson {
+	pub account_id: String,
+	pub peer_id: String,
+	pub initialized: u64,
+}
+
+impl<AccountId: Ss58Codec> From<SubnetNode<AccountId>> for SubnetNodeJson {
+	fn from(subnet_node: SubnetNode<AccountId>) -> Self {
+		Self {
+			account_id: account_to_string(&subnet_node.account_id),
+			peer_id: peer_id_to_string(&subnet_node.peer_id),
+			initialized: subnet_node.initialized,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubnetNodeDataJson {
+	pub peer_id: String,
+	pub score: String,
+}
+
+impl From<SubnetNodeData> for SubnetNodeDataJson {
+	fn from(data: SubnetNodeData) -> Self {
+		Self {
+			peer_id: peer_id_to_string(&data.peer_id),
+			score: data.score.to_string(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationJson {
+	pub account_id: String,
+	pub agreement: String,
+	pub disagreements: Vec<String>,
+}
+
+impl AttestationJson {
+	pub fn new<AccountId: Ss58Codec>(account_id: &AccountId, attestation: AttestationData) -> Self {
+		Self {
+			account_id: account_to_string(account_id),
+			agreement: attestation.agreement.to_string(),
+			disagreements: attestation.disagreements.iter().map(peer_id_to_string).collect(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubmissionJson {
+	pub validator: String,
+	pub data: Vec<SubnetNodeDataJson>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardsDataJson {
+	pub validator: String,
+	pub nodes_count: u32,
+	pub sum: String,
+	pub attests: Vec<AttestationJson>,
+	pub data: Vec<SubnetNodeDataJson>,
+	pub submissions: Vec<SubmissionJson>,
+}
+
+impl<AccountId: Ss58Codec> From<RewardsData<AccountId>> for RewardsDataJson {
+	fn from(rewards_data: RewardsData<AccountId>) -> Self {
+		Self {
+			validator: account_to_string(&rewards_data.validator),
+			nodes_count: rewards_data.nodes_count,
+			sum: rewards_data.sum.to_string(),
+			attests: rewards_data.attests
+				.into_iter()
+				.map(|(account_id, attestation)| AttestationJson::new(&account_id, attestation))
+				.collect(),
+			data: rewards_data.data.into_iter().map(Into::into).collect(),
+			submissions: rewards_data.submissions
+				.into_iter()
+				.map(|(account_id, data)| SubmissionJson {
+					validator: account_to_string(&account_id),
+					data: data.into_iter().map(Into::into).collect(),
+				})
+				.collect(),
+		}
+	}
+}
+
+/// `RewardsData` as returned by runtime API versions before 2
+// Frozen here so changes to `RewardsData` in the pallet don't break decoding old runtimes
+#[derive(Decode, Clone, Debug, PartialEq, Eq)]
+pub struct LegacyRewardsData<AccountId: Ord> {
+	pub validator: AccountId,
+	pub nodes_count: u32,
+	pub sum: u128,
+	pub attests: BTreeSet<AccountId>,
+	pub data: Vec<SubnetNodeData>,
+}
+
+impl<AccountId: Ss58Codec + Ord> From<LegacyRewardsData<AccountId>> for RewardsDataJson {
+	fn from(rewards_data: LegacyRewardsData<AccountId>) -> Self {
+		let data: Vec<SubnetNodeDataJson> = rewards_data.data.into_iter().map(Into::into).collect();
+		Self {
+			validator: account_to_string(&rewards_data.validator),
+			nodes_count: rewards_data.nodes_count,
+			sum: rewards_data.sum.to_string(),
+			// Legacy attestations always agreed with all of the data
+			attests: rewards_data.attests
+				.iter()
+				.map(|account_id| AttestationJson::new(account_id, AttestationData { agreement: 10000, disagreements: Vec::new() }))
+				.collect(),
+			// The chosen validator was the only submitter
+			submissions: vec![SubmissionJson {
+				validator: account_to_string(&rewards_data.validator),
+				data: data.clone(),
+			}],
+			data: data,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountantDataNodeJson {
+	pub peer_id: String,
+	pub data: String,
+}
+
+impl From<AccountantDataNodeParams> for AccountantDataNodeJson {
+	fn from(params: AccountantDataNodeParams) -> Self {
+		Self {
+			peer_id: peer_id_to_string(&params.peer_id),
+			data: format!("0x{}", HexDisplay::from(&params.data.into_inner())),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountantDataJson {
+	pub accountant: String,
+	pub block: u64,
+	pub epoch: u32,
+	pub data: Vec<AccountantDataNodeJson>,
+}
+
+impl<AccountId: Ss58Codec> From<AccountantDataParams<AccountId>> for AccountantDataJson {
+	fn from(params: AccountantDataParams<AccountId>) -> Self {
+		Self {
+			accountant: account_to_string(&params.accountant),
+			block: params.block,
+			epoch: params.epoch,
+			data: params.data.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeDelegateStakeJson {
+	pub node_account_id: String,
+	pub shares: String,
+	pub balance: String,
+}
+
+impl<AccountId: Ss58Codec> From<NodeDelegateStakeInfo<AccountId>> for NodeDelegateStakeJson {
+	fn from(info: NodeDelegateStakeInfo<AccountId>) -> Self {
+		Self {
+			node_account_id: account_to_string(&info.node_account_id),
+			shares: info.shares.to_string(),
+			balance: info.balance.to_string(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountSubnetJson {
+	pub subnet_id: u32,
+	pub stake: String,
+	pub delegate_stake_shares: String,
+	pub delegate_stake_balance: String,
+	pub node_delegate_stakes: Vec<NodeDelegateStakeJson>,
+	pub unbonding: String,
+	pub sequential_absent_count: u32,
+	pub class: Option<String>,
+	pub next_class: Option<String>,
+	pub next_class_block: Option<u64>,
+}
+
+impl<AccountId: Ss58Codec> From<AccountSubnetInfo<AccountId>> for AccountSubnetJson {
+	fn from(info: AccountSubnetInfo<AccountId>) -> Self {
+		Self {
+			subnet_id: info.subnet_id,
+			stake: info.stake.to_string(),
+			delegate_stake_shares: info.delegate_stake_shares.to_string(),
+			delegate_stake_balance: info.delegate_stake_balance.to_string(),
+			node_delegate_stakes: info.node_delegate_stakes.into_iter().map(Into::into).collect(),
+			unbonding: info.unbonding.to_string(),
+			sequential_absent_count: info.sequential_absent_count,
+			class: info.class.map(|class| format!("{:?}", class)),
+			next_class: info.next_class.map(|class| format!("{:?}", class)),
+			next_class_block: info.next_class_block,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNetworkInfoJson {
+	pub account_subnets: Vec<u32>,
+	pub total_stake: String,
+	pub penalty_count: u32,
+	pub subnets: Vec<AccountSubnetJson>,
+}
+
+impl<AccountId: Ss58Codec> From<AccountNetworkInfo<AccountId>> for AccountNetworkInfoJson {
+	fn from(info: AccountNetworkInfo<AccountId>) -> Self {
+		Self {
+			account_subnets: info.account_subnets,
+			total_stake: info.total_stake.to_string(),
+			penalty_count: info.penalty_count,
+			subnets: info.subnets.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochScheduleJson {
+	pub epoch: u32,
+	pub start_block: u64,
+	pub end_block: u64,
+	pub roles_assigned_block: u64,
+	pub consensus_steps_end_block: u64,
+	pub remove_or_update_start_block: u64,
+	pub remove_or_update_end_block: u64,
+	pub submit_consensus_start_block: u64,
+	pub consensus_deadline_block: u64,
+}
+
+impl From<EpochSchedule> for EpochScheduleJson {
+	fn from(schedule: EpochSchedule) -> Self {
+		Self {
+			epoch: schedule.epoch,
+			start_block: schedule.start_block,
+			end_block: schedule.end_block,
+			roles_assigned_block: schedule.roles_assigned_block,
+			consensus_steps_end_block: schedule.consensus_steps_end_block,
+			remove_or_update_start_block: schedule.remove_or_update_start_block,
+			remove_or_update_end_block: schedule.remove_or_update_end_block,
+			submit_consensus_start_block: schedule.submit_consensus_start_block,
+			consensus_deadline_block: schedule.consensus_deadline_block,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochRolesJson {
+	pub epoch: u32,
+	pub assigned: bool,
+	pub is_validator: bool,
+	pub is_accountant: bool,
+}
+
+impl From<EpochRoles> for EpochRolesJson {
+	fn from(roles: EpochRoles) -> Self {
+		Self {
+			epoch: roles.epoch,
+			assigned: roles.assigned,
+			is_validator: roles.is_validator,
+			is_accountant: roles.is_accountant,
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubnetEpochInfoJson {
+	pub block: u64,
+	pub epoch_length: u64,
+	pub in_consensus_steps: bool,
+	pub can_remove_or_update_subnet_node: bool,
+	pub can_submit_consensus: bool,
+	pub current_epoch: EpochScheduleJson,
+	pub next_epoch: EpochScheduleJson,
+	pub current_roles: EpochRolesJson,
+	pub next_roles: EpochRolesJson,
+}
+
+impl From<SubnetEpochInfo> for SubnetEpochInfoJson {
+	fn from(info: SubnetEpochInfo) -> Self {
+		Self {
+			block: info.block,
+			epoch_length: info.epoch_length,
+			in_consensus_steps: info.in_consensus_steps,
+			can_remove_or_update_subnet_node: info.can_remove_or_update_subnet_node,
+			can_submit_consensus: info.can_submit_consensus,
+			current_epoch: info.current_epoch.into(),
+			next_epoch: info.next_epoch.into(),
+			current_roles: info.current_roles.into(),
+			next_roles: info.next_roles.into(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardProjectionJson {
+	pub subnet_id: u32,
+	pub epoch: u32,
+	pub epoch_secs: u64,
+	pub epochs_per_year: u64,
+	pub stake: String,
+	pub delegate_stake_balance: String,
+	pub node_delegate_stake_balance: String,
+	pub stake_reward: String,
+	pub validator_reward: String,
+	pub delegate_stake_reward: String,
+	pub node_delegate_stake_reward: String,
+	pub epoch_reward: String,
+	pub year_reward: String,
+	pub apy: String,
+	pub stake_vault_subsidy: String,
+}
+
+impl From<RewardProjection> for RewardProjectionJson {
+	fn from(projection: RewardProjection) -> Self {
+		Self {
+			subnet_id: projection.subnet_id,
+			epoch: projection.epoch,
+			epoch_secs: projection.epoch_secs,
+			epochs_per_year: projection.epochs_per_year,
+			stake: projection.stake.to_string(),
+			delegate_stake_balance: projection.delegate_stake_balance.to_string(),
+			node_delegate_stake_balance: projection.node_delegate_stake_balance.to_string(),
+			stake_reward: projection.stake_reward.to_string(),
+			validator_reward: projection.validator_reward.to_string(),
+			delegate_stake_reward: projection.delegate_stake_reward.to_string(),
+			node_delegate_stake_reward: projection.node_delegate_stake_reward.to_string(),
+			epoch_reward: projection.epoch_reward.to_string(),
+			year_reward: projection.year_reward.to_string(),
+			apy: projection.apy.to_string(),
+			stake_vault_subsidy: projection.stake_vault_subsidy.to_string(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeEpochRewardJson {
+	pub account_id: String,
+	pub reward: String,
+	pub validator_reward: String,
+	pub node_delegate_stake_reward: String,
+}
+
+impl<AccountId: Ss58Codec> From<NodeEpochReward<AccountId>> for NodeEpochRewardJson {
+	fn from(node_reward: NodeEpochReward<AccountId>) -> Self {
+		Self {
+			account_id: account_to_string(&node_reward.account_id),
+			reward: node_reward.reward.to_string(),
+			validator_reward: node_reward.validator_reward.to_string(),
+			node_delegate_stake_reward: node_reward.node_delegate_stake_reward.to_string(),
+		}
+	}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochRewardsJson {
+	pub total: String,
+	pub validator_rewards: String,
+	pub delegate_stake_reward: String,
+	pub nodes: Vec<NodeEpochRewardJson>,
+}
+
+impl<AccountId: Ss58Codec> From<EpochRewardsData<AccountId>> for EpochRewardsJson {
+	fn from(epoch_rewards: EpochRewardsData<AccountId>) -> Self {
+		Self {
+			total: epoch_rewards.total.to_string(),
+			validator_rewards: epoch_rewards.validator_rewards.to_string(),
+			delegate_stake_reward: epoch_rewards.delegate_stake_reward.to_string(),
+			nodes: epoch_rewards.nodes.into_iter().map(Into::into).collect(),
+		}
+	}
+}
@@ -1,15 +1,38 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+use codec::Codec;
 use frame_support::dispatch::Vec;
-use pallet_network::{AccountantDataParams, RewardsData};
+use pallet_network::{
+  AccountantDataParams, AccountNetworkInfo, EpochRewardsData, RewardProjection, RewardsData, SubnetEpochInfo,
+  SubnetNode,
+};
 
 sp_api::decl_runtime_apis! {
-  pub trait NetworkRuntimeApi {
+  /// Version 2 returns typed values in place of SCALE-encoded bytes
+  /// Version 3 adds the account, epoch, reward projection and epoch rewards queries
+  #[api_version(3)]
+  pub trait NetworkRuntimeApi<AccountId> where
+    AccountId: Codec,
+  {
+    #[changed_in(2)]
     fn get_subnet_nodes(model_id: u32) -> Vec<u8>;
+    fn get_subnet_nodes(subnet_id: u32) -> Vec<SubnetNode<AccountId>>;
+    #[changed_in(2)]
     fn get_subnet_nodes_included(model_id: u32) -> Vec<u8>;
+    fn get_subnet_nodes_included(subnet_id: u32) -> Vec<SubnetNode<AccountId>>;
+    #[changed_in(2)]
     fn get_subnet_nodes_submittable(model_id: u32) -> Vec<u8>;
+    fn get_subnet_nodes_submittable(subnet_id: u32) -> Vec<SubnetNode<AccountId>>;
     fn get_subnet_nodes_model_unconfirmed_count(model_id: u32) -> u32;
+    #[changed_in(2)]
     fn get_consensus_data(model_id: u32, epoch: u32) -> Vec<u8>;
+    fn get_consensus_data(subnet_id: u32, epoch: u32) -> Option<RewardsData<AccountId>>;
+    #[changed_in(2)]
     fn get_accountant_data(model_id: u32, id: u32) -> Vec<u8>;
+    fn get_accountant_data(subnet_id: u32, id: u32) -> Option<AccountantDataParams<AccountId>>;
     fn get_minimum_subnet_nodes(subnet_id: u32, memory_mb: u128) -> u32;
+    fn get_account_info(account_id: AccountId) -> AccountNetworkInfo<AccountId>;
+    fn get_subnet_epoch_info(subnet_id: u32, account_id: AccountId) -> SubnetEpochInfo;
+    fn get_reward_projection(subnet_id: u32, account_id: AccountId, stake: u128, delegate_stake: u128) -> RewardProjection;
+    fn get_epoch_rewards(subnet_id: u32, epoch: u32) -> Option<EpochRewardsData<AccountId>>;
   }
 }
\ No newline at end of file
@@ -190,14 +190,17 @@ impl<T: Config> Pallet<T> {
   //   }
   // }
 
+  /// Choose the accountants from the shuffled submittable subnet nodes
+  // Accountants are taken in shuffled order after the validators so no node holds both roles
   pub fn choose_accountants(
-    block: u64,
+    shuffled_account_ids: &Vec<T::AccountId>,
+    validators: &Vec<T::AccountId>,
     epoch: u32,
     subnet_id: u32,
     min_subnet_nodes: u32,
     target_accountants_len: u32,
   ) {
-    let node_sets: BTreeMap<T::AccountId, u64> = SubnetNodesClasses::<T>::get(subnet_id, SubnetNodeClass::Accountant);
+    let node_sets: BTreeMap<T::AccountId, u64> = Self::get_classified_subnet_nodes(subnet_id, SubnetNodeClass::Accountant);
     let node_sets_len: u32 = node_sets.len() as u32;
     // --- Ensure min subnet peers that are submittable are at least the minimum required
     // --- Consensus cannot begin until this minimum is reached
@@ -206,39 +209,12 @@ impl<T: Config> Pallet<T> {
       return
     }
 
-    let account_ids: Vec<T::AccountId> = node_sets.iter()
-      .map(|x| x.0.clone())
+    let current_accountants: BTreeMap<T::AccountId, bool> = shuffled_account_ids.iter()
+      .filter(|account_id| node_sets.contains_key(*account_id) && !validators.contains(*account_id))
+      .take(target_accountants_len as usize)
+      .map(|account_id| (account_id.clone(), false))
       .collect();
 
-    // --- Ensure we don't attempt to choose more accountants than are available
-    let mut max_accountants: u32 = target_accountants_len;
-    if node_sets_len < max_accountants {
-      max_accountants = node_sets_len;
-    }
-
-    // `-1` is for overflow
-    let account_ids_len = account_ids.len() - 1;
-
-    // --- Ensure no duplicates
-    // let mut unique_accountants: Vec<T::AccountId> = Vec::new();
-    let mut chosen_accountants_complete: bool = false;
-
-    let mut current_accountants: BTreeMap<T::AccountId, bool> = BTreeMap::new();
-
-    // --- Get random number 0 - MAX
-    // Because true randomization isn't as important here, we only get one random number
-    // and choose the other accountants as `n+1 % MAX` to limit computation
-    // We use block + 1 in order to differentiate between validators to prevent the chosen
-    // validator being one of the accountants. 
-    let rand_index = Self::get_random_number(account_ids_len as u32, (block + 1) as u32);
-
-    for n in 0..max_accountants {
-      let rand = rand_index + n % account_ids_len as u32;
-      let random_accountant: &T::AccountId = &account_ids[rand as usize];
-
-      current_accountants.insert(random_accountant.clone(), false);
-    }
-
     CurrentAccountants::<T>::insert(subnet_id, epoch, current_accountants);
   }
 
@@ -249,7 +225,7 @@ impl<T: Config> Pallet<T> {
     min_subnet_nodes: u32,
     target_accountants_len: u32,
   ) {
-    let node_sets: BTreeMap<T::AccountId, u64> = SubnetNodesClasses::<T>::get(subnet_id, SubnetNodeClass::Accountant);
+    let node_sets: BTreeMap<T::AccountId, u64> = Self::get_classified_subnet_nodes(subnet_id, SubnetNodeClass::Accountant);
     let node_sets_len: u32 = node_sets.len() as u32;
     // --- Ensure min subnet peers that are submittable are at least the minimum required
     // --- Consensus cannot begin until this minimum is reached
@@ -363,4 +363,232 @@ impl<T: Config> Pallet<T> {
 
     Ok(())
   }
+
+  pub fn set_subnet_removal_bounty(value: u128) -> DispatchResult {
+    // Ensure bounty doesn't exceed the cost to initialize a subnet
+    ensure!(
+      value <= T::SubnetInitializationCost::get(),
+      Error::<T>::InvalidSubnetRemovalBounty
+    );
+
+    SubnetRemovalBounty::<T>::set(value);
+
+    Self::deposit_event(Event::SetSubnetRemovalBounty(value));
+
+    Ok(())
+  }
+
+  pub fn set_stake_unbonding_epochs(value: u64) -> DispatchResult {
+    // Ensure > 0 and <= 100
+    ensure!(
+      value > 0 && value <= 100,
+      Error::<T>::InvalidStakeUnbondingEpochs
+    );
+
+    StakeUnbondingEpochs::<T>::set(value);
+
+    Self::deposit_event(Event::SetStakeUnbondingEpochs(value));
+
+    Ok(())
+  }
+
+  pub fn set_validator_selection(value: ValidatorSelectionMode) -> DispatchResult {
+    ValidatorSelection::<T>::set(value);
+
+    Self::deposit_event(Event::SetValidatorSelection(value));
+
+    Ok(())
+  }
+
+  pub fn set_subnet_validator_selection(subnet_id: u32, value: Option<ValidatorSelectionMode>) -> DispatchResult {
+    ensure!(
+      SubnetsData::<T>::contains_key(subnet_id),
+      Error::<T>::SubnetNotExist
+    );
+
+    // --- `None` falls back to the network validator selection mode
+    SubnetValidatorSelection::<T>::set(subnet_id, value);
+
+    Self::deposit_event(Event::SetSubnetValidatorSelection(subnet_id, value));
+
+    Ok(())
+  }
+
+  pub fn set_validator_stake_weight_cap(value: u128) -> DispatchResult {
+    ensure!(
+      value > 0,
+      Error::<T>::InvalidValidatorStakeWeightCap
+    );
+
+    ValidatorStakeWeightCap::<T>::set(value);
+
+    Self::deposit_event(Event::SetValidatorStakeWeightCap(value));
+
+    Ok(())
+  }
+
+  pub fn set_validators_per_epoch(value: u32) -> DispatchResult {
+    // Ensure > 0 and <= 32
+    ensure!(
+      value > 0 && value <= 32,
+      Error::<T>::InvalidValidatorsPerEpoch
+    );
+
+    ValidatorsPerEpoch::<T>::set(value);
+
+    Self::deposit_event(Event::SetValidatorsPerEpoch(value));
+
+    Ok(())
+  }
+
+  pub fn set_node_commission_change_delay(value: u64) -> DispatchResult {
+    // Ensure <= 100
+    ensure!(
+      value <= 100,
+      Error::<T>::InvalidNodeCommissionChangeDelay
+    );
+
+    NodeCommissionChangeDelay::<T>::set(value);
+
+    Self::deposit_event(Event::SetNodeCommissionChangeDelay(value));
+
+    Ok(())
+  }
+
+  pub fn set_max_node_delegate_stake_ratio(value: u128) -> DispatchResult {
+    // Ensure > 0 and <= 10x the subnet nodes stake
+    ensure!(
+      value > 0 && value <= Self::PERCENTAGE_FACTOR * 10,
+      Error::<T>::InvalidMaxNodeDelegateStakeRatio
+    );
+
+    MaxNodeDelegateStakeRatio::<T>::set(value);
+
+    Self::deposit_event(Event::SetMaxNodeDelegateStakeRatio(value));
+
+    Ok(())
+  }
+
+  pub fn set_stake_move_cooldown_epochs(value: u64) -> DispatchResult {
+    // Ensure > 0 and <= 100
+    ensure!(
+      value > 0 && value <= 100,
+      Error::<T>::InvalidStakeMoveCooldownEpochs
+    );
+
+    StakeMoveCooldownEpochs::<T>::set(value);
+
+    Self::deposit_event(Event::SetStakeMoveCooldownEpochs(value));
+
+    Ok(())
+  }
+
+  pub fn set_epoch_rewards_retention_epochs(value: u32) -> DispatchResult {
+    // Ensure > 0 and <= 100000
+    ensure!(
+      value > 0 && value <= 100000,
+      Error::<T>::InvalidEpochRewardsRetentionEpochs
+    );
+
+    EpochRewardsRetentionEpochs::<T>::set(value);
+
+    Self::deposit_event(Event::SetEpochRewardsRetentionEpochs(value));
+
+    Ok(())
+  }
+
+  pub fn set_subnet_node_class_epochs(class_id: SubnetNodeClass, value: u64) -> DispatchResult {
+    // Ensure the global classes stay ordered Idle < Included < Submittable < Accountant
+    let class_epochs: Vec<(SubnetNodeClass, u64)> = SubnetNodeClass::iter()
+      .map(|id| if id == class_id { (id, value) } else { (id, SubnetNodeClassEpochs::<T>::get(id)) })
+      .collect();
+
+    ensure!(
+      Self::is_valid_subnet_node_class_epochs(&class_epochs),
+      Error::<T>::InvalidSubnetNodeClassEpochs
+    );
+
+    SubnetNodeClassEpochs::<T>::insert(class_id, value);
+
+    Self::deposit_event(Event::SetSubnetNodeClassEpochs(class_id, value));
+
+    Ok(())
+  }
+
+  pub fn set_subnet_node_class_epochs_override(subnet_id: u32, class_epochs: Vec<(SubnetNodeClass, u64)>) -> DispatchResult {
+    ensure!(
+      SubnetsData::<T>::contains_key(subnet_id),
+      Error::<T>::SubnetNotExist
+    );
+
+    // Ensure empty to remove the override or ordered Idle < Included < Submittable < Accountant
+    ensure!(
+      class_epochs.is_empty() || Self::is_valid_subnet_node_class_epochs(&class_epochs),
+      Error::<T>::InvalidSubnetNodeClassEpochs
+    );
+
+    let _ = SubnetNodeClassEpochsOverride::<T>::clear_prefix(subnet_id, u32::MAX, None);
+    for (class_id, epochs) in class_epochs.iter() {
+      SubnetNodeClassEpochsOverride::<T>::insert(subnet_id, class_id, epochs);
+    }
+
+    Self::deposit_event(Event::SetSubnetNodeClassEpochsOverride(subnet_id, class_epochs));
+
+    Ok(())
+  }
+
+  pub fn set_node_class_demotion_absent_threshold(value: u32) -> DispatchResult {
+    // Ensure > 0 and <= MaxSequentialAbsentSubnetNode
+    ensure!(
+      value > 0 && value <= MaxSequentialAbsentSubnetNode::<T>::get(),
+      Error::<T>::InvalidNodeClassDemotionAbsentThreshold
+    );
+
+    NodeClassDemotionAbsentThreshold::<T>::set(value);
+
+    Self::deposit_event(Event::SetNodeClassDemotionAbsentThreshold(value));
+
+    Ok(())
+  }
+
+  pub fn set_node_class_demotion_penalty_threshold(value: u32) -> DispatchResult {
+    // Ensure > 0 and <= MaxAccountPenaltyCount
+    ensure!(
+      value > 0 && value <= MaxAccountPenaltyCount::<T>::get(),
+      Error::<T>::InvalidNodeClassDemotionPenaltyThreshold
+    );
+
+    NodeClassDemotionPenaltyThreshold::<T>::set(value);
+
+    Self::deposit_event(Event::SetNodeClassDemotionPenaltyThreshold(value));
+
+    Ok(())
+  }
+
+  pub fn set_base_subnet_reward(value: u128) -> DispatchResult {
+    ensure!(
+      value > 0,
+      Error::<T>::InvalidBaseSubnetReward
+    );
+
+    BaseSubnetReward::<T>::set(value);
+
+    Self::deposit_event(Event::SetBaseSubnetReward(value));
+
+    Ok(())
+  }
+
+  pub fn set_slash_percentage(value: u128) -> DispatchResult {
+    // Ensure divisible by .01% and <= 100%
+    ensure!(
+      value <= Self::PERCENTAGE_FACTOR,
+      Error::<T>::InvalidSlashPercentage
+    );
+
+    SlashPercentage::<T>::set(value);
+
+    Self::deposit_event(Event::SetSlashPercentage(value));
+
+    Ok(())
+  }
 }
\ No newline at end of file
@@ -162,6 +162,49 @@ pub fn get_current_block_as_u64<T: frame_system::Config>() -> u64 {
 		.expect("blockchain will not exceed 2^64 blocks; QED.")
 }
 
+// activate subnets each with submittable subnet nodes and move past the subnets consensus initialization
+fn build_epoch_subnets<T: Config>(subnets: u32, subnet_nodes: u32) -> Vec<u32> {
+	let block = get_current_block_as_u64::<T>();
+	let mut subnet_ids: Vec<u32> = Vec::new();
+	for s in 0..subnets {
+		let subnet_path: Vec<u8> = format!("petals-team-{s}/StableBeluga2").into();
+		let funded_initializer = funded_initializer::<T>("funded_initializer", s);
+
+		let add_subnet_data = PreSubnetData {
+			path: subnet_path.clone(),
+			memory_mb: 50000,
+			class_epochs: Vec::new(),
+		};
+		assert_ok!(
+			Network::<T>::activate_subnet(
+				funded_initializer.clone(),
+				funded_initializer.clone(),
+				add_subnet_data,
+			)
+		);
+
+		let subnet_id = SubnetPaths::<T>::get(subnet_path.clone()).unwrap();
+
+		for n in 0..subnet_nodes {
+			let peer_account = funded_account::<T>("peer", subnet_id * 1000 + n);
+			assert_ok!(
+				Network::<T>::add_subnet_node(
+					RawOrigin::Signed(peer_account.clone()).into(), 
+					subnet_id, 
+					peer(n as u8), 
+					get_min_stake_balance::<T>()
+				)
+			);
+			Network::<T>::classify_subnet_node(subnet_id, &peer_account, SubnetNodeClass::Submittable, block);
+		}
+		subnet_ids.push(subnet_id);
+	}
+
+	make_subnet_initialized::<T>();
+
+	subnet_ids
+}
+
 benchmarks! {
 	add_subnet_node {
 		// add subnet
@@ -174,6 +217,7 @@ benchmarks! {
 		let add_subnet_data = PreSubnetData {
 			path: subnet_path.clone().into(),
 			memory_mb: 50000,
+			class_epochs: Vec::new(),
 		};
 		assert_ok!(
 			Network::<T>::activate_subnet(
@@ -220,6 +264,7 @@ benchmarks! {
 		let add_subnet_data = PreSubnetData {
 			path: subnet_path.clone().into(),
 			memory_mb: 50000,
+			class_epochs: Vec::new(),
 		};
 		assert_ok!(
 			Network::<T>::activate_subnet(
@@ -280,6 +325,7 @@ benchmarks! {
 		let add_subnet_data = PreSubnetData {
 			path: subnet_path.clone().into(),
 			memory_mb: 50000,
+			class_epochs: Vec::new(),
 		};
 		assert_ok!(
 			Network::<T>::activate_subnet(
@@ -332,6 +378,7 @@ benchmarks! {
 		let add_subnet_data = PreSubnetData {
 			path: subnet_path.clone().into(),
 			memory_mb: 50000,
+			class_epochs: Vec::new(),
 		};
 		assert_ok!(
 			Network::<T>::activate_subnet(
@@ -401,6 +448,7 @@ benchmarks! {
 		let add_subnet_data = PreSubnetData {
 			path: subnet_path.clone().into(),
 			memory_mb: 50000,
+			class_epochs: Vec::new(),
 		};
 		assert_ok!(
 			Network::<T>::activate_subnet(
@@ -465,6 +513,192 @@ benchmarks! {
 		assert_eq!(Network::<T>::total_subnet_nodes(total_models.clone()), 1, "TotalSubnetNodes incorrect.");
 	}
 
+	reward_subnets {
+		// subnets
+		let s in 1 .. 32;
+		// subnet nodes in each subnet, at least the minimum subnet nodes
+		let n in 3 .. 96;
+		// absent subnet nodes removed from the first subnet
+		let r in 0 .. 96;
+		// subnets removed for surpassing the maximum penalties
+		let d in 0 .. 32;
+
+		let subnet_ids: Vec<u32> = build_epoch_subnets::<T>(s, n);
+
+		let epoch_length = T::EpochLength::get();
+		let block = get_current_block_as_u64::<T>();
+		let epoch: u32 = (block / epoch_length) as u32;
+
+		// absent subnet nodes at the maximum sequential absences are removed, they aren't submittable
+		// so the attestation percentage is unchanged
+		let max_absent = MaxSequentialAbsentSubnetNode::<T>::get();
+		for i in n..n + r {
+			let peer_account = funded_account::<T>("peer", subnet_ids[0] * 1000 + i);
+			assert_ok!(
+				Network::<T>::add_subnet_node(
+					RawOrigin::Signed(peer_account.clone()).into(), 
+					subnet_ids[0], 
+					peer(i as u8), 
+					get_min_stake_balance::<T>()
+				)
+			);
+			SequentialAbsentSubnetNode::<T>::insert(subnet_ids[0], peer_account, max_absent);
+		}
+
+		// the successful epoch decrements the penalty count once
+		let removed_subnets: u32 = d.min(s);
+		let max_subnet_penalties = MaxSubnetPenalties::<T>::get();
+		for subnet_id in subnet_ids.iter().take(removed_subnets as usize) {
+			SubnetPenaltyCount::<T>::insert(subnet_id, max_subnet_penalties + 2);
+		}
+
+		// every subnet node is submitted and attests the epoch
+		for subnet_id in subnet_ids.iter() {
+			let data: Vec<SubnetNodeData> = subnet_node_data::<T>(0, n as u8);
+			let validator: T::AccountId = account("peer", subnet_id * 1000, SEED);
+			let attests: BTreeMap<T::AccountId, AttestationData> = (0..n)
+				.map(|i| (account("peer", subnet_id * 1000 + i, SEED), Network::<T>::full_attestation()))
+				.collect();
+			let mut submissions: BTreeMap<T::AccountId, Vec<SubnetNodeData>> = BTreeMap::new();
+			submissions.insert(validator.clone(), data.clone());
+
+			SubnetRewardsValidators::<T>::insert(subnet_id, epoch, vec![validator.clone()]);
+			SubnetRewardsSubmission::<T>::insert(
+				subnet_id, 
+				epoch, 
+				RewardsData {
+					validator: validator,
+					nodes_count: n,
+					sum: DEFAULT_SCORE * n as u128,
+					attests: attests,
+					data: data,
+					submissions: submissions,
+				}
+			);
+		}
+
+		let reward_block = (block / epoch_length + 1) * epoch_length;
+	}: {
+		Network::<T>::reward_subnets(reward_block, epoch, epoch_length)
+	}
+	verify {
+		for subnet_id in subnet_ids.iter() {
+			assert!(EpochRewards::<T>::get(subnet_id, epoch).is_some(), "EpochRewards incorrect.");
+		}
+		assert_eq!(Network::<T>::total_subnet_nodes(subnet_ids[0]), n, "TotalSubnetNodes incorrect.");
+		for (i, subnet_id) in subnet_ids.iter().enumerate() {
+			assert_eq!(SubnetsData::<T>::contains_key(subnet_id), i as u32 >= removed_subnets, "SubnetsData incorrect.");
+		}
+	}
+
+	reward_subnets_penalized {
+		// subnets
+		let s in 1 .. 32;
+		// subnet nodes in each subnet, at least the minimum subnet nodes
+		let n in 3 .. 96;
+
+		let subnet_ids: Vec<u32> = build_epoch_subnets::<T>(s, n);
+
+		let epoch_length = T::EpochLength::get();
+		let block = get_current_block_as_u64::<T>();
+		let epoch: u32 = (block / epoch_length) as u32;
+
+		// every subnet node is a validator submitting no data without attestations, so every
+		// validator is penalized and demoted at the penalty threshold
+		let demotion_penalty_threshold = NodeClassDemotionPenaltyThreshold::<T>::get();
+		for subnet_id in subnet_ids.iter() {
+			let validators: Vec<T::AccountId> = (0..n)
+				.map(|i| account("peer", subnet_id * 1000 + i, SEED))
+				.collect();
+			let submissions: BTreeMap<T::AccountId, Vec<SubnetNodeData>> = validators.iter()
+				.map(|validator| (validator.clone(), Vec::new()))
+				.collect();
+
+			for validator in validators.iter() {
+				AccountPenaltyCount::<T>::insert(validator, demotion_penalty_threshold.saturating_sub(1));
+			}
+
+			SubnetRewardsValidators::<T>::insert(subnet_id, epoch, validators.clone());
+			SubnetRewardsSubmission::<T>::insert(
+				subnet_id, 
+				epoch, 
+				RewardsData {
+					validator: validators[0].clone(),
+					nodes_count: n,
+					sum: 0,
+					attests: BTreeMap::new(),
+					data: Vec::new(),
+					submissions: submissions,
+				}
+			);
+		}
+
+		let reward_block = (block / epoch_length + 1) * epoch_length;
+	}: {
+		Network::<T>::reward_subnets(reward_block, epoch, epoch_length)
+	}
+	verify {
+		for subnet_id in subnet_ids.iter() {
+			assert_eq!(SubnetPenaltyCount::<T>::get(subnet_id), 1, "SubnetPenaltyCount incorrect.");
+		}
+	}
+
+	shift_node_classes {
+		// subnets
+		let s in 1 .. 32;
+		// subnet nodes due to be shifted into their next class in each subnet
+		let p in 1 .. 96;
+
+		let subnet_ids: Vec<u32> = build_epoch_subnets::<T>(s, 0);
+
+		let epoch_length = T::EpochLength::get();
+		let block = get_current_block_as_u64::<T>();
+		let idle_epochs = SubnetNodeClassEpochs::<T>::get(SubnetNodeClass::Idle).max(1);
+
+		// shift only the epoch the subnet nodes are due
+		let shift_block = block - (block % epoch_length) + idle_epochs * epoch_length;
+
+		for subnet_id in subnet_ids.iter() {
+			for i in 0..p {
+				Network::<T>::classify_subnet_node(
+					*subnet_id, 
+					&account("promoted", subnet_id * 1000 + i, SEED), 
+					SubnetNodeClass::Idle, 
+					shift_block - idle_epochs * epoch_length
+				);
+			}
+			SubnetNodeClassShiftEpoch::<T>::insert(subnet_id, shift_block / epoch_length - 1);
+		}
+	}: {
+		Network::<T>::shift_node_classes(shift_block, epoch_length)
+	}
+	verify {
+		for subnet_id in subnet_ids.iter() {
+			assert_eq!(Network::<T>::get_classified_subnet_nodes_count(*subnet_id, SubnetNodeClass::Included), p, "Included count incorrect.");
+			assert_eq!(Network::<T>::get_classified_subnet_nodes_count(*subnet_id, SubnetNodeClass::Idle), p, "Idle count incorrect.");
+		}
+	}
+
+	do_choose_validator_and_accountants {
+		// subnets
+		let s in 1 .. 32;
+		// subnet nodes in each subnet, at least the minimum subnet nodes
+		let n in 3 .. 96;
+
+		let subnet_ids: Vec<u32> = build_epoch_subnets::<T>(s, n);
+
+		let epoch_length = T::EpochLength::get();
+		let block = get_current_block_as_u64::<T>();
+		let epoch: u32 = (block / epoch_length) as u32;
+	}: {
+		Network::<T>::do_choose_validator_and_accountants(block, epoch, epoch_length)
+	}
+	verify {
+		for subnet_id in subnet_ids.iter() {
+			assert!(SubnetRewardsValidators::<T>::get(subnet_id, epoch).is_some(), "SubnetRewardsValidators incorrect.");
+		}
+	}
+
 	impl_benchmark_test_suite!(
 		Network,
 		crate::mock::new_test_ext(),
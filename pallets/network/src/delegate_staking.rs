@@ -134,9 +134,9 @@ impl<T: Config> Pallet<T> {
     let total_model_delegated_stake_shares = TotalSubnetDelegateStakeShares::<T>::get(subnet_id.clone());
     let total_model_delegated_stake_balance = TotalSubnetDelegateStakeBalance::<T>::get(subnet_id.clone());
 
-    // --- Get accounts current balance
+    // --- Get balance of the shares to be removed
     let delegate_stake_to_be_removed = Self::convert_to_balance(
-      account_delegate_stake_shares,
+      delegate_stake_shares_to_be_removed,
       total_model_delegated_stake_shares,
       total_model_delegated_stake_balance
     );
@@ -159,8 +159,8 @@ impl<T: Config> Pallet<T> {
 
     let remaining_account_delegate_stake_shares: u128 = AccountSubnetDelegateStakeShares::<T>::get(&account_id, subnet_id);
     
-    // --- 9. We add the balancer to the account_id.  If the above fails we will not credit this account_id.
-    Self::add_balance_to_coldkey_account(&account_id, delegate_stake_to_be_added_as_currency.unwrap());
+    // --- 9. Add the balance to the accounts unbonding ledger to be claimed after `StakeUnbondingEpochs`
+    Self::do_add_unbonding(&account_id, subnet_id, delegate_stake_to_be_removed, UnbondingKind::DelegateStake)?;
     
     // Set last block for rate limiting
     Self::set_last_tx_block(&account_id, block);
@@ -203,8 +203,8 @@ impl<T: Config> Pallet<T> {
       AccountSubnetDelegateStakeShares::<T>::get(account_id, subnet_id).saturating_sub(shares),
     );
 
-    // -- increase total subnet delegate stake balance
-    TotalSubnetDelegateStakeBalance::<T>::mutate(subnet_id.clone(), |mut n| *n += amount);
+    // -- decrease total subnet delegate stake balance
+    TotalSubnetDelegateStakeBalance::<T>::mutate(subnet_id.clone(), |mut n| *n = n.saturating_sub(amount));
 
     // -- decrease total subnet delegate stake shares
     TotalSubnetDelegateStakeShares::<T>::mutate(subnet_id.clone(), |mut n| *n -= shares);
@@ -316,4 +316,96 @@ impl<T: Config> Pallet<T> {
     // balance.saturating_mul(total_shares).saturating_div(total_balance)
     balance.saturating_mul(total_shares.saturating_div(total_balance))
   }
+
+  /// Move delegate stake shares between subnets without unbonding
+  pub fn do_move_delegate_stake(
+    account_id: T::AccountId,
+    from_subnet_id: u32,
+    to_subnet_id: u32,
+    delegate_stake_shares_to_be_moved: u128,
+  ) -> DispatchResult {
+    let epoch_length: u64 = T::EpochLength::get();
+    let block: u64 = Self::get_current_block_as_u64();
+
+    Self::ensure_can_move_stake(&account_id, from_subnet_id, to_subnet_id, block, epoch_length)?;
+
+    ensure!(
+      delegate_stake_shares_to_be_moved > 0,
+      Error::<T>::NotEnoughStakeToWithdraw
+    );
+
+    let account_delegate_stake_shares: u128 = AccountSubnetDelegateStakeShares::<T>::get(&account_id, from_subnet_id);
+
+    ensure!(
+      account_delegate_stake_shares >= delegate_stake_shares_to_be_moved,
+      Error::<T>::NotEnoughStakeToWithdraw
+    );
+
+    // --- Get balance of the shares to be moved
+    let delegate_stake_to_be_moved: u128 = Self::convert_to_balance(
+      delegate_stake_shares_to_be_moved,
+      TotalSubnetDelegateStakeShares::<T>::get(from_subnet_id),
+      TotalSubnetDelegateStakeBalance::<T>::get(from_subnet_id)
+    );
+
+    let to_total_delegated_stake_shares: u128 = TotalSubnetDelegateStakeShares::<T>::get(to_subnet_id);
+    let to_total_delegated_stake_balance: u128 = TotalSubnetDelegateStakeBalance::<T>::get(to_subnet_id);
+
+    // --- Get the accounts current balance on the subnet the delegate stake is moved to
+    let to_account_delegate_stake_balance: u128 = Self::convert_to_balance(
+      AccountSubnetDelegateStakeShares::<T>::get(&account_id, to_subnet_id),
+      to_total_delegated_stake_shares,
+      to_total_delegated_stake_balance
+    );
+
+    ensure!(
+      to_account_delegate_stake_balance.saturating_add(delegate_stake_to_be_moved) <= MaxDelegateStakeBalance::<T>::get(),
+      Error::<T>::MaxDelegatedStakeReached
+    );
+
+    // --- Get amount to be added as shares on the subnet the delegate stake is moved to
+    let mut delegate_stake_to_be_added_as_shares: u128 = Self::convert_to_shares(
+      delegate_stake_to_be_moved,
+      to_total_delegated_stake_shares,
+      to_total_delegated_stake_balance
+    );
+
+    // --- Mitigate inflation attack
+    if to_total_delegated_stake_shares == 0 {
+      TotalSubnetDelegateStakeShares::<T>::mutate(to_subnet_id, |n: &mut u128| *n += 1000);
+      delegate_stake_to_be_added_as_shares = delegate_stake_to_be_added_as_shares.saturating_sub(1000);
+    }
+
+    // --- Check rounding errors
+    ensure!(
+      delegate_stake_to_be_added_as_shares != 0,
+      Error::<T>::CouldNotConvertToShares
+    );
+
+    Self::decrease_account_delegate_stake_shares(&account_id, from_subnet_id, delegate_stake_to_be_moved, delegate_stake_shares_to_be_moved);
+    Self::increase_account_delegate_stake_shares(&account_id, to_subnet_id, delegate_stake_to_be_moved, delegate_stake_to_be_added_as_shares);
+
+    // --- Update the accounts subnet delegate stake blocks
+    if AccountSubnetDelegateStakeShares::<T>::get(&account_id, from_subnet_id) == 0 {
+      SubnetAccountDelegateStake::<T>::mutate(from_subnet_id, |accounts| {
+        accounts.remove(&account_id);
+      });
+    }
+    SubnetAccountDelegateStake::<T>::mutate(to_subnet_id, |accounts| {
+      accounts.insert(account_id.clone(), block);
+    });
+
+    LastStakeMoveBlock::<T>::insert(&account_id, block);
+
+    Self::deposit_event(
+      Event::DelegateStakeMoved {
+        from_subnet_id: from_subnet_id,
+        to_subnet_id: to_subnet_id,
+        account_id: account_id,
+        amount: delegate_stake_to_be_moved,
+      }
+    );
+
+    Ok(())
+  }
 }
\ No newline at end of file
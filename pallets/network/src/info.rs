@@ -1,5 +1,7 @@
 use super::*;
 use frame_support::dispatch::Vec;
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use sp_runtime::DispatchError;
 
 impl<T: Config> Pallet<T> {
   pub fn get_subnet_nodes(
@@ -26,7 +28,7 @@ impl<T: Config> Pallet<T> {
 
     let mut subnet_nodes: Vec<SubnetNode<T::AccountId>> = Vec::new();
 
-    let node_sets: BTreeMap<T::AccountId, u64> = SubnetNodesClasses::<T>::get(subnet_id.clone(), SubnetNodeClass::Included);
+    let node_sets: BTreeMap<T::AccountId, u64> = Self::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Included);
 
     for subnet_node in SubnetNodesData::<T>::iter_prefix_values(subnet_id.clone()) {
       let account_id: T::AccountId = subnet_node.clone().account_id;
@@ -73,7 +75,7 @@ impl<T: Config> Pallet<T> {
 
     let mut subnet_nodes: Vec<SubnetNode<T::AccountId>> = Vec::new();
 
-    let node_sets: BTreeMap<T::AccountId, u64> = SubnetNodesClasses::<T>::get(subnet_id.clone(), SubnetNodeClass::Submittable);
+    let node_sets: BTreeMap<T::AccountId, u64> = Self::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Submittable);
 
     for subnet_node in SubnetNodesData::<T>::iter_prefix_values(subnet_id.clone()) {
       let account_id: T::AccountId = subnet_node.clone().account_id;
@@ -126,4 +128,375 @@ impl<T: Config> Pallet<T> {
   pub fn get_minimum_subnet_nodes(subnet_id: u32, memory_mb: u128) -> u32 {
     Self::get_min_subnet_nodes(BaseSubnetNodeMemoryMB::<T>::get(), memory_mb)
   }
+
+  /// Stake, delegate stake, penalties and subnet node classes of an account on every subnet it has a balance on
+  pub fn get_account_info(account_id: T::AccountId) -> AccountNetworkInfo<T::AccountId> {
+    let block: u64 = Self::get_current_block_as_u64();
+    let epoch_length: u64 = T::EpochLength::get();
+
+    // --- Get every subnet the account is a subnet node on or has a balance on
+    let account_subnets: Vec<u32> = AccountSubnets::<T>::get(&account_id);
+    let mut subnet_ids: BTreeSet<u32> = account_subnets.iter().copied().collect();
+    subnet_ids.extend(AccountSubnetStake::<T>::iter_prefix(&account_id).map(|(subnet_id, _)| subnet_id));
+    subnet_ids.extend(AccountSubnetDelegateStakeShares::<T>::iter_prefix(&account_id).map(|(subnet_id, _)| subnet_id));
+    subnet_ids.extend(Unbonding::<T>::iter_prefix(&account_id).map(|(subnet_id, _)| subnet_id));
+
+    let mut node_delegate_stakes: BTreeMap<u32, Vec<NodeDelegateStakeInfo<T::AccountId>>> = BTreeMap::new();
+    for ((subnet_id, node_account_id), shares) in AccountNodeDelegateStakeShares::<T>::iter_prefix(&account_id) {
+      subnet_ids.insert(subnet_id);
+      let balance: u128 = Self::get_node_delegate_stake_balance(&account_id, subnet_id, &node_account_id);
+      node_delegate_stakes
+        .entry(subnet_id)
+        .or_insert_with(Vec::new)
+        .push(NodeDelegateStakeInfo {
+          node_account_id: node_account_id,
+          shares: shares,
+          balance: balance,
+        });
+    }
+
+    let subnets: Vec<AccountSubnetInfo<T::AccountId>> = subnet_ids
+      .into_iter()
+      .map(|subnet_id| {
+        let delegate_stake_shares: u128 = AccountSubnetDelegateStakeShares::<T>::get(&account_id, subnet_id);
+        let (class, next_class, next_class_block) = Self::get_subnet_node_class_info(
+          subnet_id,
+          &account_id,
+          block,
+          epoch_length
+        );
+
+        AccountSubnetInfo {
+          subnet_id: subnet_id,
+          stake: AccountSubnetStake::<T>::get(&account_id, subnet_id),
+          delegate_stake_shares: delegate_stake_shares,
+          delegate_stake_balance: Self::convert_to_balance(
+            delegate_stake_shares,
+            TotalSubnetDelegateStakeShares::<T>::get(subnet_id),
+            TotalSubnetDelegateStakeBalance::<T>::get(subnet_id)
+          ),
+          node_delegate_stakes: node_delegate_stakes.remove(&subnet_id).unwrap_or_default(),
+          unbonding: Self::get_unbonding_balance(&account_id, subnet_id),
+          sequential_absent_count: SequentialAbsentSubnetNode::<T>::get(subnet_id, &account_id),
+          class: class,
+          next_class: next_class,
+          next_class_block: next_class_block,
+        }
+      })
+      .collect();
+
+    AccountNetworkInfo {
+      account_subnets: account_subnets,
+      total_stake: TotalAccountStake::<T>::get(&account_id),
+      penalty_count: AccountPenaltyCount::<T>::get(&account_id),
+      subnets: subnets,
+    }
+  }
+
+  /// Current class of a subnet node, its next class and the block it is shifted into the next class
+  // Classes are shifted in `shift_node_classes` on the first block of the epoch the
+  // subnet node is scheduled to be shifted into its next class
+  pub fn get_subnet_node_class_info(
+    subnet_id: u32,
+    account_id: &T::AccountId,
+    block: u64,
+    epoch_length: u64,
+  ) -> (Option<SubnetNodeClass>, Option<SubnetNodeClass>, Option<u64>) {
+    if !SubnetNodesData::<T>::contains_key(subnet_id, account_id) {
+      return (None, None, None)
+    }
+
+    let classification: SubnetNodeClassification = match SubnetNodesClassification::<T>::get(subnet_id, account_id) {
+      Some(classification) => classification,
+      None => return (None, None, None),
+    };
+
+    let class: Option<SubnetNodeClass> = Some(classification.class);
+    let next_class: Option<SubnetNodeClass> = SubnetNodeClass::from_repr(classification.class.index() + 1);
+    let promotion_epoch: u64 = match classification.promotion_epoch {
+      Some(promotion_epoch) => promotion_epoch,
+      None => return (class, None, None),
+    };
+
+    // --- If the scheduled epoch has already started the subnet node is shifted on the next epoch
+    let promotion_block: u64 = promotion_epoch * epoch_length;
+    let next_epoch_block: u64 = block - (block % epoch_length) + epoch_length;
+    let next_class_block: u64 = if promotion_block > block {
+      promotion_block
+    } else {
+      next_epoch_block
+    };
+
+    (class, next_class, Some(next_class_block))
+  }
+
+  /// Epoch schedule and an accounts validator and accountant roles on a subnet for the current and next epoch
+  pub fn get_subnet_epoch_info(subnet_id: u32, account_id: T::AccountId) -> SubnetEpochInfo {
+    let block: u64 = Self::get_current_block_as_u64();
+    let epoch_length: u64 = T::EpochLength::get();
+    let epoch: u32 = (block / epoch_length) as u32;
+
+    SubnetEpochInfo {
+      block: block,
+      epoch_length: epoch_length,
+      in_consensus_steps: Self::is_in_consensus_steps(block, epoch_length),
+      can_remove_or_update_subnet_node: Self::can_remove_or_update_subnet_node(block, epoch_length),
+      can_submit_consensus: Self::can_submit_consensus(block, epoch_length),
+      current_epoch: Self::get_epoch_schedule(epoch, epoch_length),
+      next_epoch: Self::get_epoch_schedule(epoch + 1, epoch_length),
+      current_roles: Self::get_epoch_roles(subnet_id, &account_id, epoch, block, epoch_length),
+      next_roles: Self::get_epoch_roles(subnet_id, &account_id, epoch + 1, block, epoch_length),
+    }
+  }
+
+  /// Blocks of an epoch that consensus steps, subnet node removals and consensus submissions happen on
+  // Mirrors `is_in_consensus_steps`, `can_remove_or_update_subnet_node` and `on_initialize`
+  pub fn get_epoch_schedule(epoch: u32, epoch_length: u64) -> EpochSchedule {
+    let start_block: u64 = epoch as u64 * epoch_length;
+    let end_block: u64 = start_block + epoch_length - 1;
+
+    let remove_or_update_end_block: u64 = start_block + Self::percent_mul(
+      epoch_length as u128,
+      RemoveSubnetNodeEpochPercentage::<T>::get()
+    ) as u64;
+
+    EpochSchedule {
+      epoch: epoch,
+      start_block: start_block,
+      end_block: end_block,
+      roles_assigned_block: start_block + 1,
+      consensus_steps_end_block: start_block + Self::CONSENSUS_STEPS - 1,
+      remove_or_update_start_block: start_block + Self::CONSENSUS_STEPS,
+      remove_or_update_end_block: remove_or_update_end_block,
+      submit_consensus_start_block: remove_or_update_end_block.max(start_block + Self::CONSENSUS_STEPS - 1) + 1,
+      // Submissions are rewarded on the first block of the following epoch
+      consensus_deadline_block: end_block,
+    }
+  }
+
+  /// Whether an account is a chosen validator or accountant on a subnet for an epoch
+  // Roles are chosen on the block after the epochs first block so they are never assigned ahead of time
+  pub fn get_epoch_roles(
+    subnet_id: u32,
+    account_id: &T::AccountId,
+    epoch: u32,
+    block: u64,
+    epoch_length: u64,
+  ) -> EpochRoles {
+    let assigned: bool = epoch > 0 && block > epoch as u64 * epoch_length;
+
+    EpochRoles {
+      epoch: epoch,
+      assigned: assigned,
+      is_validator: SubnetRewardsValidators::<T>::get(subnet_id, epoch)
+        .map_or(false, |validators| validators.contains(account_id)),
+      is_accountant: CurrentAccountants::<T>::get(subnet_id, epoch)
+        .map_or(false, |accountants| accountants.contains_key(account_id)),
+    }
+  }
+
+  /// Rewards emitted to a subnet for an epoch, `None` if the epoch wasn't rewarded or was pruned
+  pub fn get_epoch_rewards(subnet_id: u32, epoch: u32) -> Option<EpochRewardsData<T::AccountId>> {
+    EpochRewards::<T>::get(subnet_id, epoch)
+  }
+
+  /// Projected rewards of an account on a subnet with hypothetical stake and delegate stake added
+  // The hypothetical balances are added in a transaction that is rolled back so they weigh in the attestation
+  // percentage and the stake shares. The rewards follow `reward_subnets` on the previous epochs consensus data
+  // of the subnet, including the accounts share of the validator reward.
+  // `year_reward` annualizes the epoch rewards over `T::Year`, `apy` compounds them every epoch
+  pub fn get_reward_projection(
+    subnet_id: u32,
+    account_id: T::AccountId,
+    stake: u128,
+    delegate_stake: u128,
+  ) -> RewardProjection {
+    let projection: Result<RewardProjection, DispatchError> = with_transaction(|| {
+      // --- Add the hypothetical balances
+      if stake > 0 {
+        Self::increase_account_stake(&account_id, subnet_id, stake);
+      }
+
+      if delegate_stake > 0 {
+        let delegate_stake_as_shares: u128 = Self::convert_to_shares(
+          delegate_stake,
+          TotalSubnetDelegateStakeShares::<T>::get(subnet_id),
+          TotalSubnetDelegateStakeBalance::<T>::get(subnet_id)
+        );
+        Self::increase_account_delegate_stake_shares(
+          &account_id,
+          subnet_id,
+          delegate_stake,
+          delegate_stake_as_shares,
+        );
+      }
+
+      TransactionOutcome::Rollback(Ok(Self::project_epoch_rewards(subnet_id, &account_id)))
+    });
+
+    projection.unwrap_or_default()
+  }
+
+  /// Rewards of an account on a subnet for the previous epochs consensus data from its current balances
+  fn project_epoch_rewards(subnet_id: u32, account_id: &T::AccountId) -> RewardProjection {
+    let block: u64 = Self::get_current_block_as_u64();
+    let epoch_length: u64 = T::EpochLength::get();
+    let epoch: u32 = (block / epoch_length).saturating_sub(1) as u32;
+    let epochs_per_year: u64 = T::Year::get() / epoch_length;
+
+    let base_subnet_reward: u128 = BaseSubnetReward::<T>::get();
+    let subnet_reward: u128 = Self::percent_mul(base_subnet_reward, DelegateStakeRewardsPercentage::<T>::get());
+    let subnet_delegate_stake_reward: u128 = base_subnet_reward.saturating_sub(subnet_reward);
+
+    let [stake_balance, delegate_stake_balance, node_delegate_stake_balance] = Self::get_account_reward_balances(subnet_id, account_id);
+
+    let mut stake_reward: u128 = 0;
+    let mut validator_reward: u128 = 0;
+    let mut delegate_stake_reward: u128 = 0;
+    let mut node_delegate_stake_reward: u128 = 0;
+
+    if let Some((submission, honest_validators, validator_reward_share)) = Self::get_rewardable_submission(subnet_id, epoch) {
+      // --- Subnet nodes reward and its validator reward before sharing it with its delegators
+      let node_reward = |node_account_id: &T::AccountId| -> (u128, u128) {
+        if !submission.attests.contains_key(node_account_id) {
+          return (0, 0)
+        }
+        let peer_id: PeerId = SubnetNodesData::<T>::get(subnet_id, node_account_id).peer_id;
+        let score_reward: u128 = match submission.data.iter().find(|data| data.peer_id == peer_id) {
+          Some(data) => Self::percent_mul(Self::percent_div(data.score, submission.sum), subnet_reward),
+          None => return (0, 0),
+        };
+        let validator_reward: u128 = if honest_validators.contains(node_account_id) {
+          validator_reward_share
+        } else {
+          0
+        };
+        (score_reward.saturating_add(validator_reward), validator_reward)
+      };
+
+      // --- Stake reward if the account is a subnet node
+      if SubnetNodesData::<T>::contains_key(subnet_id, account_id) {
+        let (reward, node_validator_reward) = node_reward(account_id);
+        let delegators_reward: u128 = Self::get_node_delegators_reward(
+          subnet_id,
+          account_id,
+          stake_balance,
+          Self::get_node_commission(subnet_id, account_id, epoch as u64),
+          reward,
+        );
+        stake_reward = reward.saturating_sub(delegators_reward);
+        validator_reward = node_validator_reward;
+      }
+
+      // --- Delegate stake reward by the accounts share of the subnets delegate stake
+      delegate_stake_reward = Self::percent_mul2(
+        subnet_delegate_stake_reward,
+        Self::percent_div2(delegate_stake_balance, TotalSubnetDelegateStakeBalance::<T>::get(subnet_id))
+      );
+
+      // --- Node delegate stake reward by the accounts share of each subnet nodes delegate stake
+      for ((node_subnet_id, node_account_id), _) in AccountNodeDelegateStakeShares::<T>::iter_prefix(account_id) {
+        if node_subnet_id != subnet_id {
+          continue
+        }
+        let delegators_reward: u128 = Self::get_node_delegators_reward(
+          subnet_id,
+          &node_account_id,
+          AccountSubnetStake::<T>::get(&node_account_id, subnet_id),
+          Self::get_node_commission(subnet_id, &node_account_id, epoch as u64),
+          node_reward(&node_account_id).0,
+        );
+        let account_balance: u128 = Self::get_node_delegate_stake_balance(account_id, subnet_id, &node_account_id);
+        node_delegate_stake_reward = node_delegate_stake_reward.saturating_add(
+          Self::percent_mul2(
+            delegators_reward,
+            Self::percent_div2(account_balance, TotalNodeDelegateStakeBalance::<T>::get(subnet_id, &node_account_id))
+          )
+        );
+      }
+    }
+
+    let epoch_reward: u128 = stake_reward
+      .saturating_add(delegate_stake_reward)
+      .saturating_add(node_delegate_stake_reward);
+    let year_reward: u128 = epoch_reward.saturating_mul(epochs_per_year as u128);
+
+    let balance: u128 = stake_balance
+      .saturating_add(delegate_stake_balance)
+      .saturating_add(node_delegate_stake_balance);
+
+    RewardProjection {
+      subnet_id: subnet_id,
+      epoch: epoch,
+      epoch_secs: epoch_length.saturating_mul(T::SecsPerBlock::get()),
+      epochs_per_year: epochs_per_year,
+      stake: stake_balance,
+      delegate_stake_balance: delegate_stake_balance,
+      node_delegate_stake_balance: node_delegate_stake_balance,
+      stake_reward: stake_reward,
+      validator_reward: validator_reward,
+      delegate_stake_reward: delegate_stake_reward,
+      node_delegate_stake_reward: node_delegate_stake_reward,
+      epoch_reward: epoch_reward,
+      year_reward: year_reward,
+      apy: Self::compound_percentage(Self::percent_div2(epoch_reward, balance), epochs_per_year),
+      stake_vault_subsidy: Self::get_year_stake_vault_subsidy(block),
+    }
+  }
+
+  /// Aggregated consensus data of a subnets epoch if the subnet would be rewarded for it
+  // Returns the data with the validators responsible for it and each of their share of the validator reward
+  fn get_rewardable_submission(subnet_id: u32, epoch: u32) -> Option<(RewardsData<T::AccountId>, Vec<T::AccountId>, u128)> {
+    let min_nodes: u32 = SubnetsData::<T>::get(subnet_id)?.min_nodes;
+    let mut submission: RewardsData<T::AccountId> = SubnetRewardsSubmission::<T>::get(subnet_id, epoch)?;
+
+    let (aggregated_data, deviating_validators) = Self::aggregate_submissions(&submission.submissions);
+    submission.sum = aggregated_data.iter().fold(0, |acc, d| acc.saturating_add(d.score));
+    submission.data = aggregated_data;
+
+    let attestation_percentage: u128 = Self::get_attestation_percentage(subnet_id, &submission);
+    if (submission.data.len() as u32) < min_nodes || attestation_percentage < MinAttestationPercentage::<T>::get() {
+      return None
+    }
+
+    let honest_validators: Vec<T::AccountId> = submission.submissions.keys()
+      .filter(|account_id| !deviating_validators.contains(account_id))
+      .cloned()
+      .collect();
+    let validator_reward_share: u128 = Self::get_validator_reward(attestation_percentage)
+      .checked_div(honest_validators.len() as u128)
+      .unwrap_or(0);
+
+    Some((submission, honest_validators, validator_reward_share))
+  }
+
+  /// Stake, delegate stake and node delegate stake balances of an account on a subnet
+  fn get_account_reward_balances(subnet_id: u32, account_id: &T::AccountId) -> [u128; 3] {
+    let node_delegate_stake_balance: u128 = AccountNodeDelegateStakeShares::<T>::iter_prefix(account_id)
+      .filter(|((node_subnet_id, _), _)| *node_subnet_id == subnet_id)
+      .fold(0, |acc, ((_, node_account_id), _)| {
+        acc.saturating_add(Self::get_node_delegate_stake_balance(account_id, subnet_id, &node_account_id))
+      });
+
+    [
+      AccountSubnetStake::<T>::get(account_id, subnet_id),
+      Self::convert_to_balance(
+        AccountSubnetDelegateStakeShares::<T>::get(account_id, subnet_id),
+        TotalSubnetDelegateStakeShares::<T>::get(subnet_id),
+        TotalSubnetDelegateStakeBalance::<T>::get(subnet_id)
+      ),
+      node_delegate_stake_balance,
+    ]
+  }
+
+  /// Stake vault subsidy over the year following `block`
+  // The subsidy is sampled once a day so the sum follows the halving schedule
+  pub fn get_year_stake_vault_subsidy(block: u64) -> u128 {
+    let blocks_per_day: u64 = T::Year::get() / 365;
+
+    (0..365).fold(0, |acc: u128, day: u64| {
+      let subsidy: u128 = T::StakeVaultSubsidy::stake_vault_subsidy(block + day * blocks_per_day);
+      acc.saturating_add(subsidy.saturating_mul(blocks_per_day as u128))
+    })
+  }
 }
\ No newline at end of file
@@ -54,12 +54,19 @@ mod math;
 mod admin;
 mod staking;
 mod delegate_staking;
+mod node_delegate_staking;
 mod info;
 mod accountant;
 mod subnet_validator;
 mod rewards;
 mod proposal;
 mod randomness;
+mod subnet_cleanup;
+mod unbonding;
+pub mod migrations;
+
+pub use randomness::{BlockRandomness, CommitRevealRandomness};
+pub use math::PERCENTAGE_FACTOR;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -103,6 +110,12 @@ pub mod pallet {
 		/// Something that provides randomness in the runtime.
 		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
 
+		/// Seeds the election of subnet validators and accountants each epoch.
+		type ElectionRandomness: ElectionRandomness;
+
+		/// Block subsidy paid into the stake vault, used to project emissions.
+		type StakeVaultSubsidy: StakeVaultSubsidy;
+
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
@@ -116,12 +129,20 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		// Subnets
 		SubnetAdded { proposer: T::AccountId, activator: T::AccountId, subnet_id: u32, subnet_path: Vec<u8>, block: u64 },
-		SubnetRemoved { account: T::AccountId, subnet_id: u32, subnet_path: Vec<u8>, reason: Vec<u8>, block: u64 },
+		SubnetRemoved { account: Option<T::AccountId>, subnet_id: u32, subnet_path: Vec<u8>, reason: SubnetRemovalReason, block: u64 },
 
 		// Subnet Nodes
 		SubnetNodeAdded { subnet_id: u32, account_id: T::AccountId, peer_id: PeerId, block: u64 },
 		SubnetNodeUpdated { subnet_id: u32, account_id: T::AccountId, peer_id: PeerId, block: u64 },
-		SubnetNodeRemoved { subnet_id: u32, account_id: T::AccountId, peer_id: PeerId, block: u64 },
+		SubnetNodeRemoved { subnet_id: u32, account_id: T::AccountId, peer_id: PeerId, reason: SubnetNodeRemovalReason, block: u64 },
+		SubnetNodeAbsent { subnet_id: u32, account_id: T::AccountId, count: u32, max: u32 },
+		SubnetNodeDemoted { subnet_id: u32, account_id: T::AccountId, class: SubnetNodeClass, reason: SubnetNodeDemotionReason, block: u64 },
+		BootstrapSubnetNodeSkipped { subnet_id: u32, account_id: T::AccountId, peer_id: PeerId, error: DispatchError },
+
+		// Penalties
+		ValidatorSlashed { subnet_id: u32, account_id: T::AccountId, amount: u128, attestation_percentage: u128, reason: ValidatorSlashReason, penalty_count: u32 },
+		ValidatorPenalized { subnet_id: u32, account_id: T::AccountId, attestation_percentage: u128, penalty_count: u32 },
+		SubnetPenalized { subnet_id: u32, reason: SubnetPenaltyReason, penalty_count: u32 },
 
 		// Stake
 		StakeAdded(u32, T::AccountId, u128),
@@ -129,6 +150,28 @@ pub mod pallet {
 
 		DelegateStakeAdded(u32, T::AccountId, u128),
 		DelegateStakeRemoved(u32, T::AccountId, u128),
+
+		StakeMoved { from_subnet_id: u32, to_subnet_id: u32, account_id: T::AccountId, amount: u128 },
+		DelegateStakeMoved { from_subnet_id: u32, to_subnet_id: u32, account_id: T::AccountId, amount: u128 },
+
+		// Subnet node delegate stake
+		NodeDelegateStakeAdded { subnet_id: u32, node_account_id: T::AccountId, account_id: T::AccountId, amount: u128 },
+		NodeDelegateStakeRemoved { subnet_id: u32, node_account_id: T::AccountId, account_id: T::AccountId, amount: u128 },
+		NodeCommissionScheduled { subnet_id: u32, account_id: T::AccountId, commission: u128, epoch: u64 },
+		NodeCommissionUpdated { subnet_id: u32, account_id: T::AccountId, commission: u128 },
+
+		UnbondedClaimed(u32, T::AccountId, u128),
+
+		// Rewards
+		EpochRewarded { subnet_id: u32, epoch: u32, total: u128, validator_rewards: u128, delegate_stake_reward: u128 },
+
+		// Election randomness
+		EntropyRevealed { subnet_id: u32, account_id: T::AccountId, epoch: u64 },
+		EntropyUnrevealed { subnet_id: u32, account_id: T::AccountId, commit_epoch: u64, penalty_count: u32 },
+
+		// Removed subnets
+		SubnetCleaned { subnet_id: u32, block: u64 },
+		BalanceClaimed(T::AccountId, u128),
 		
 		// Admin 
 		SetVoteSubnetIn(Vec<u8>),
@@ -152,6 +195,22 @@ pub mod pallet {
 		SetSubnetPerNodeInitCost(u128),
 		SetSubnetConsensusUnconfirmedThreshold(u128),
 		SetRemoveSubnetNodeEpochPercentage(u128),
+		SetSubnetRemovalBounty(u128),
+		SetStakeUnbondingEpochs(u64),
+		SetValidatorSelection(ValidatorSelectionMode),
+		SetSubnetValidatorSelection(u32, Option<ValidatorSelectionMode>),
+		SetValidatorStakeWeightCap(u128),
+		SetValidatorsPerEpoch(u32),
+		SetNodeCommissionChangeDelay(u64),
+		SetMaxNodeDelegateStakeRatio(u128),
+		SetStakeMoveCooldownEpochs(u64),
+		SetEpochRewardsRetentionEpochs(u32),
+		SetSubnetNodeClassEpochs(SubnetNodeClass, u64),
+		SetSubnetNodeClassEpochsOverride(u32, Vec<(SubnetNodeClass, u64)>),
+		SetNodeClassDemotionAbsentThreshold(u32),
+		SetNodeClassDemotionPenaltyThreshold(u32),
+		SetBaseSubnetReward(u128),
+		SetSlashPercentage(u128),
 
 		// Dishonesty Proposals
 		DishonestSubnetNodeProposed { subnet_id: u32, account_id: T::AccountId, block: u64},
@@ -242,6 +301,32 @@ pub mod pallet {
 		InvalidSubnetConsensusUnconfirmedThreshold,
 		/// Invalid remove subnet peer epoch percentage, must be in 1e4 format and greater than 20.00
 		InvalidRemoveSubnetNodeEpochPercentage,
+		/// Invalid subnet removal bounty, must not exceed the subnet initialization cost
+		InvalidSubnetRemovalBounty,
+		/// Invalid stake unbonding epochs, must be greater than 0 and not exceed maximum allowable
+		InvalidStakeUnbondingEpochs,
+		/// Invalid validator stake weight cap, must be greater than 0
+		InvalidValidatorStakeWeightCap,
+		/// Invalid validators per epoch, must be greater than 0 and not exceed maximum allowable
+		InvalidValidatorsPerEpoch,
+		/// Invalid node commission change delay, must not exceed maximum allowable
+		InvalidNodeCommissionChangeDelay,
+		/// Invalid max node delegate stake ratio, must be greater than 0 and not exceed maximum allowable
+		InvalidMaxNodeDelegateStakeRatio,
+		/// Invalid stake move cooldown epochs, must be greater than 0 and not exceed maximum allowable
+		InvalidStakeMoveCooldownEpochs,
+		/// Invalid epoch rewards retention epochs, must be greater than 0 and not exceed maximum allowable
+		InvalidEpochRewardsRetentionEpochs,
+		/// Invalid subnet node class epochs, each class must be set once and increase from Idle to Accountant
+		InvalidSubnetNodeClassEpochs,
+		/// Invalid node class demotion absent threshold, must be greater than zero and less than or equal to `MaxSequentialAbsentSubnetNode`
+		InvalidNodeClassDemotionAbsentThreshold,
+		/// Invalid node class demotion penalty threshold, must be greater than zero and less than or equal to `MaxAccountPenaltyCount`
+		InvalidNodeClassDemotionPenaltyThreshold,
+		/// Invalid base subnet reward, must be greater than zero
+		InvalidBaseSubnetReward,
+		/// Invalid slash percentage, must not exceed 100.00%
+		InvalidSlashPercentage,
 		// staking
 		/// u128 -> BalanceOf conversion error
 		CouldNotConvertToBalance,
@@ -260,8 +345,18 @@ pub mod pallet {
 		CouldNotConvertToShares,
 		// 
 		MaxDelegatedStakeReached,
+		/// Delegate stake would exceed `MaxNodeDelegateStakeRatio` of the subnet nodes stake
+		MaxNodeDelegateStakeRatioReached,
 		//
 		RequiredDelegateUnstakeEpochsNotMet,
+		/// Commission must be in 1e4 format and not exceed 100.00
+		InvalidNodeCommission,
+		/// Stake can only be moved between different subnets
+		InvalidStakeMove,
+		/// Stake was moved within the last `StakeMoveCooldownEpochs`
+		StakeMoveCooldownNotMet,
+		/// Chosen validators of the epoch cannot move stake out of the subnet
+		ValidatorStakeMoveNotAllowed,
 		// Conversion to balance was zero
 		InsufficientBalanceToSharesConversion,
 		// consensus
@@ -326,6 +421,8 @@ pub mod pallet {
 		InvalidValidator,
 		/// Already attested validator data
 		AlreadyAttested,
+		/// Attestation agreement must not exceed 100.00 and disagreements must be peers of the aggregated data
+		InvalidAttestation,
 		/// Invalid rewards data length
 		InvalidRewardsDataLength,
 		/// Invalid block for submitting data
@@ -338,6 +435,20 @@ pub mod pallet {
 		ProposalUnchallenged,
 		/// Subnet node as defendant has proposal activated already
 		NodeHasActiveProposal,
+		/// Account has no balance refunded from removed subnets to claim
+		NoClaimableBalance,
+		/// Maximum unbonding entries reached for the subnet, claim unbonded balances first
+		MaxUnbondingsReached,
+		/// Account has no unbonded balance that has matured on the subnet
+		NoUnbondedBalance,
+		/// Account has not committed entropy on the subnet
+		NoEntropyCommitment,
+		/// Entropy must be revealed in the epoch following the commitment
+		InvalidEntropyRevealEpoch,
+		/// Revealed entropy doesn't match the commitment
+		InvalidEntropyReveal,
+		/// Entropy commitment exists, reveal it or wait for it to expire before committing again
+		EntropyCommitmentExists,
 	}
 	
 	// Used for decoding API data - not in use in v1.0
@@ -371,6 +482,7 @@ pub mod pallet {
 	pub struct PreSubnetData {
 		pub path: Vec<u8>,
 		pub memory_mb: u128,
+		pub class_epochs: Vec<(SubnetNodeClass, u64)>, // Overrides `SubnetNodeClassEpochs` for the subnet if not empty
 	}
 	
 	/// Data for subnet held to be compared when adding a subnet to the network
@@ -381,6 +493,15 @@ pub mod pallet {
 		pub active: bool,
 	}
 
+	/// A subnet node from an activation proposal registered once the subnet is activated
+	// The stake is escrowed by the democracy pallet during the vote
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct BootstrapSubnetNode<AccountId> {
+		pub account_id: AccountId,
+		pub peer_id: PeerId,
+		pub stake: u128,
+	}
+
 	// The submit consensus data format
 	// Scoring is calculated off-chain between subnet peers hosting AI subnets together
 	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
@@ -389,6 +510,20 @@ pub mod pallet {
 		pub score: u128,
 	}
 
+	// An attestors agreement with the epochs rewards data
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct AttestationData {
+		pub agreement: u128, // Percentage of the data the attestor agrees with
+		pub disagreements: Vec<PeerId>, // Peers the attestor disputes the scores of
+	}
+
+	// Balances unbonding on the same epoch, only the subnet node stake balance is slashable
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct UnbondingBalance {
+		pub stake: u128, // Removed subnet node stake
+		pub delegate_stake: u128, // Removed subnet and subnet node delegate stake
+	}
+
 	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
 	pub struct SubnetNode<AccountId> {
 		pub account_id: AccountId,
@@ -396,13 +531,121 @@ pub mod pallet {
 		pub initialized: u64,
 	}
 
+	// An accounts delegate stake on a subnet node
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct NodeDelegateStakeInfo<AccountId> {
+		pub node_account_id: AccountId,
+		pub shares: u128,
+		pub balance: u128,
+	}
+
+	// An accounts stake, delegate stake and subnet node status on a subnet
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct AccountSubnetInfo<AccountId> {
+		pub subnet_id: u32,
+		pub stake: u128,
+		pub delegate_stake_shares: u128,
+		pub delegate_stake_balance: u128, // Balance value of the delegate stake shares
+		pub node_delegate_stakes: Vec<NodeDelegateStakeInfo<AccountId>>,
+		pub unbonding: u128,
+		pub sequential_absent_count: u32,
+		pub class: Option<SubnetNodeClass>, // None if the account isn't a subnet node on the subnet
+		pub next_class: Option<SubnetNodeClass>,
+		pub next_class_block: Option<u64>, // Block the subnet node is shifted into `next_class`
+	}
+
+	// An accounts stake, delegate stake and penalties across all subnets
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct AccountNetworkInfo<AccountId> {
+		pub account_subnets: Vec<u32>, // Subnets the account is a subnet node on
+		pub total_stake: u128,
+		pub penalty_count: u32,
+		pub subnets: Vec<AccountSubnetInfo<AccountId>>,
+	}
+
+	// Block schedule of an epoch
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct EpochSchedule {
+		pub epoch: u32,
+		pub start_block: u64,
+		pub end_block: u64, // Last block of the epoch
+		pub roles_assigned_block: u64, // Block validators and accountants are chosen on
+		pub consensus_steps_end_block: u64, // Last block of forming consensus and choosing validators
+		pub remove_or_update_start_block: u64, // First block subnet nodes can be removed or updated
+		pub remove_or_update_end_block: u64, // Last block subnet nodes can be removed or updated
+		pub submit_consensus_start_block: u64,
+		pub consensus_deadline_block: u64, // Last block `validate` and `attest` are accepted for the epoch
+	}
+
+	// An accounts roles on a subnet for an epoch
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct EpochRoles {
+		pub epoch: u32,
+		pub assigned: bool, // False until validators and accountants are chosen for the epoch
+		pub is_validator: bool,
+		pub is_accountant: bool,
+	}
+
+	// Epoch schedule and an accounts roles on a subnet for the current and next epoch
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct SubnetEpochInfo {
+		pub block: u64,
+		pub epoch_length: u64,
+		pub in_consensus_steps: bool,
+		pub can_remove_or_update_subnet_node: bool,
+		pub can_submit_consensus: bool,
+		pub current_epoch: EpochSchedule,
+		pub next_epoch: EpochSchedule,
+		pub current_roles: EpochRoles,
+		pub next_roles: EpochRoles,
+	}
+
+	// Rewards of a subnet node for an epoch
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct NodeEpochReward<AccountId> {
+		pub account_id: AccountId,
+		pub reward: u128, // Reward added to the subnet nodes stake
+		pub validator_reward: u128, // Portion of the reward from validating the epoch
+		pub node_delegate_stake_reward: u128, // Reward added to the subnet nodes delegators
+	}
+
+	// Rewards of a subnet for an epoch
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct EpochRewardsData<AccountId> {
+		pub total: u128, // Sum of every reward emitted for the epoch
+		pub validator_rewards: u128,
+		pub delegate_stake_reward: u128, // Reward added to the subnets delegate stake pool
+		pub nodes: Vec<NodeEpochReward<AccountId>>,
+	}
+
+	// Projected rewards of an account on a subnet from the previous epochs consensus data
+	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct RewardProjection {
+		pub subnet_id: u32,
+		pub epoch: u32, // Epoch whose consensus data is projected
+		pub epoch_secs: u64,
+		pub epochs_per_year: u64,
+		pub stake: u128, // Stake balance including the hypothetical stake
+		pub delegate_stake_balance: u128, // Delegate stake balance including the hypothetical delegate stake
+		pub node_delegate_stake_balance: u128,
+		pub stake_reward: u128, // Rewards of a single epoch
+		pub validator_reward: u128, // Portion of the stake reward from validating the epoch before sharing it with delegators
+		pub delegate_stake_reward: u128,
+		pub node_delegate_stake_reward: u128,
+		pub epoch_reward: u128,
+		pub year_reward: u128, // Epoch rewards annualized without compounding
+		pub apy: u128, // Epoch rewards compounded over a year as a percentage of the balances
+		pub stake_vault_subsidy: u128, // Subsidy paid into the stake vault over the next year
+	}
+
 	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
 	pub struct RewardsData<AccountId> {
-		pub validator: AccountId, // Chosen validator of the epoch
+		pub validator: AccountId, // First chosen validator of the epoch to submit
 		pub nodes_count: u32, // Number of nodes expected to submit attestations
 		pub sum: u128, // Sum of the data scores
-		pub attests: BTreeSet<AccountId>, // Count of attestations of the submitted data
-		pub data: Vec<SubnetNodeData>, // Data submitted by chosen validator
+		pub attests: BTreeMap<AccountId, AttestationData>, // Attestations of the submitted data
+		pub data: Vec<SubnetNodeData>, // Data submitted by the first validator, aggregated from all submissions at the end of the epoch
+		pub submissions: BTreeMap<AccountId, Vec<SubnetNodeData>>, // Data submitted by each chosen validator
 	}
 
 	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
@@ -537,6 +780,63 @@ pub mod pallet {
     }
   }
 
+	// Reasons a subnet can be removed from the network
+	#[derive(Copy, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+  pub enum SubnetRemovalReason {
+    SubnetDemocracy, // Subnet voted out or deactivated by subnet democracy
+    MaxConsensusEpochsErrors, // Subnet surpassed the maximum consensus epoch errors
+    Offline, // Subnet is offline too many times
+    MinSubnetNodes, // Subnet has less than the minimum nodes after the initialization period
+    MaxPenalties, // Subnet surpassed the maximum subnet penalties count
+  }
+
+	// Reasons a subnet node can be removed from a subnet
+	#[derive(Copy, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+  pub enum SubnetNodeRemovalReason {
+    SelfRemoved, // Subnet node removed itself
+    MaxSequentialAbsent, // Subnet node was absent from consensus data more than `MaxSequentialAbsentSubnetNode` epochs
+    DishonestyProposal, // Subnet node lost a dishonesty proposal
+    MaxAccountPenalties, // Account surpassed the maximum account penalties count
+  }
+
+	// Reasons a subnet node can be demoted to the class below its current class
+	#[derive(Copy, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+  pub enum SubnetNodeDemotionReason {
+    SequentialAbsent, // Subnet node was absent from consensus data `NodeClassDemotionAbsentThreshold` epochs in a row
+    AccountPenalties, // Account reached another `NodeClassDemotionPenaltyThreshold` penalties
+  }
+
+	// Kinds of balances added to the unbonding ledger
+	#[derive(Copy, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+  pub enum UnbondingKind {
+    Stake, // Subnet node stake
+    DelegateStake, // Subnet or subnet node delegate stake
+  }
+
+	// Reasons a chosen validator can be slashed
+	#[derive(Copy, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+  pub enum ValidatorSlashReason {
+    MissedSubmission, // Validator didn't submit consensus data
+    DeviatingSubmission, // Validators scores deviate from the aggregated scores
+    InsufficientAttestation, // Consensus data wasn't attested by `MinAttestationPercentage`
+  }
+
+	// Reasons a subnets penalty count can be increased
+	#[derive(Copy, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+  pub enum SubnetPenaltyReason {
+    MissedSubmission, // No chosen validator submitted consensus data
+    MinSubnetNodes, // Consensus data has less than the minimum subnet nodes
+  }
+
+	// How the epochs validator is chosen from the submittable subnet nodes
+	#[derive(Copy, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+  pub enum ValidatorSelectionMode {
+    Uniform, // Each node has the same chance
+    Stake, // Chance is proportional to stake
+    CappedStake, // Chance is proportional to stake up to `ValidatorStakeWeightCap`
+    SqrtStake, // Chance is proportional to the square root of stake
+  }
+
 	// types
 	#[pallet::type_value]
 	pub fn DefaultAccountId<T: Config>() -> T::AccountId {
@@ -733,6 +1033,10 @@ pub mod pallet {
 		100
 	}
 	#[pallet::type_value]
+	pub fn DefaultSubnetRemovalBounty<T: Config>() -> u128 {
+		1e+18 as u128
+	}
+	#[pallet::type_value]
 	pub fn DefaultRemoveSubnetNodeEpochPercentage<T: Config>() -> u128 {
 		2000
 	}
@@ -918,8 +1222,9 @@ pub mod pallet {
 			validator: T::AccountId::decode(&mut TrailingZeroInput::zeroes()).unwrap(),
 			nodes_count: 0,
 			sum: 0,
-			attests: BTreeSet::new(),
+			attests: BTreeMap::new(),
 			data: Vec::new(),
+			submissions: BTreeMap::new(),
 		}
 	}
 	#[pallet::type_value]
@@ -971,10 +1276,6 @@ pub mod pallet {
 		2
 	}
 	#[pallet::type_value]
-	pub fn DefaultSubnetNodesClasses<T: Config>() -> BTreeMap<T::AccountId, u64> {
-		BTreeMap::new()
-	}
-	#[pallet::type_value]
 	pub fn DefaultZeroU32() -> u32 {
 		0
 	}
@@ -983,6 +1284,14 @@ pub mod pallet {
 		3
 	}
 	#[pallet::type_value]
+	pub fn DefaultNodeClassDemotionAbsentThreshold() -> u32 {
+		2
+	}
+	#[pallet::type_value]
+	pub fn DefaultNodeClassDemotionPenaltyThreshold() -> u32 {
+		4
+	}
+	#[pallet::type_value]
 	pub fn DefaultBaseSubnetNodeMemoryMB() -> u128 {
 		16000
 	}
@@ -999,10 +1308,51 @@ pub mod pallet {
 		2
 	}
 	#[pallet::type_value]
+	pub fn DefaultMaxSubnetCleanupEntries() -> u32 {
+		512
+	}
+	#[pallet::type_value]
+	pub fn DefaultStakeUnbondingEpochs() -> u64 {
+		4
+	}
+	#[pallet::type_value]
+	pub fn DefaultMaxUnbondings() -> u32 {
+		32
+	}
+	#[pallet::type_value]
+	pub fn DefaultValidatorSelection() -> ValidatorSelectionMode {
+		ValidatorSelectionMode::Uniform
+	}
+	#[pallet::type_value]
+	pub fn DefaultValidatorStakeWeightCap() -> u128 {
+		10000e+18 as u128
+	}
+	#[pallet::type_value]
+	pub fn DefaultValidatorsPerEpoch() -> u32 {
+		1
+	}
+	#[pallet::type_value]
+	pub fn DefaultNodeCommissionChangeDelay() -> u64 {
+		2
+	}
+	#[pallet::type_value]
+	pub fn DefaultMaxNodeDelegateStakeRatio() -> u128 {
+		10000
+	}
+	#[pallet::type_value]
+	pub fn DefaultStakeMoveCooldownEpochs() -> u64 {
+		4
+	}
+	#[pallet::type_value]
+	pub fn DefaultEpochRewardsRetentionEpochs() -> u32 {
+		1000
+	}
+	#[pallet::type_value]
 	pub fn DefaultVoteSubnetData() -> VoteSubnetData {
 		let pre_subnet_data = PreSubnetData {
 			path: Vec::new(),
 			memory_mb: 0,
+			class_epochs: Vec::new(),
 		};
 		return VoteSubnetData {
 			data: pre_subnet_data,
@@ -1083,6 +1433,23 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type SubnetNodesInitializationEpochs<T> = StorageValue<_, u64, ValueQuery, DefaultSubnetNodesInitializationEpochs<T>>;
 
+	// Amount paid from the stake vault to the account that removes an ineligible subnet
+	#[pallet::storage]
+	pub type SubnetRemovalBounty<T> = StorageValue<_, u128, ValueQuery, DefaultSubnetRemovalBounty<T>>;
+
+	// Removed subnets that still have stored data to be cleaned up in `on_idle`
+	#[pallet::storage]
+	pub type SubnetsToClean<T> = StorageValue<_, Vec<u32>, ValueQuery>;
+
+	// Maximum storage entries removed per block when cleaning up removed subnets
+	#[pallet::storage]
+	pub type MaxSubnetCleanupEntries<T> = StorageValue<_, u32, ValueQuery, DefaultMaxSubnetCleanupEntries>;
+
+	// Stake and delegate stake balances refunded from removed subnets
+	#[pallet::storage] // account_id --> balance
+	pub type ClaimableBalance<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u128, ValueQuery>;
+
 	// Minimum amount of peers required per subnet
 	// required for subnet activity
 	#[pallet::storage]
@@ -1185,6 +1552,72 @@ pub mod pallet {
 	pub type TotalAccountStake<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, u128, ValueQuery>;
 
+	// Amount of epochs removed stake and delegate stake balances are unbonding before they can be claimed
+	#[pallet::storage]
+	pub type StakeUnbondingEpochs<T> = StorageValue<_, u64, ValueQuery, DefaultStakeUnbondingEpochs>;
+
+	// Maximum unbonding entries per account per subnet
+	#[pallet::storage]
+	pub type MaxUnbondings<T> = StorageValue<_, u32, ValueQuery, DefaultMaxUnbondings>;
+
+	// Removed stake and delegate stake balances waiting to mature, subnet node stake is still slashable
+	#[pallet::storage] // account_id --> subnet_id --> (unbonding epoch, balances)
+	pub type Unbonding<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Identity,
+		u32,
+		BTreeMap<u64, UnbondingBalance>,
+		ValueQuery,
+	>;
+
+	// Amount of epochs an account must wait between moving stake or delegate stake across subnets
+	#[pallet::storage]
+	pub type StakeMoveCooldownEpochs<T> = StorageValue<_, u64, ValueQuery, DefaultStakeMoveCooldownEpochs>;
+
+	// Rewards emitted by `reward_subnets` for each subnet and epoch
+	#[pallet::storage] // subnet_id --> epoch --> rewards
+	pub type EpochRewards<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Identity,
+		u32,
+		EpochRewardsData<T::AccountId>,
+		OptionQuery,
+	>;
+
+	// Amount of epochs `EpochRewards` are kept before being pruned
+	#[pallet::storage]
+	pub type EpochRewardsRetentionEpochs<T> = StorageValue<_, u32, ValueQuery, DefaultEpochRewardsRetentionEpochs>;
+
+	// Oldest epoch of a subnet that may still have `EpochRewards`, pruning continues from it
+	#[pallet::storage] // subnet_id --> epoch
+	pub type EpochRewardsOldestEpoch<T> = StorageMap<_, Blake2_128Concat, u32, u32>;
+
+	// Block of an accounts last stake or delegate stake move
+	#[pallet::storage] // account_id --> block
+	pub type LastStakeMoveBlock<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	// Entropy commitments by subnet nodes to be revealed in the following epoch
+	// Commitments that aren't revealed in time are penalized when the next validators are elected
+	#[pallet::storage] // subnet_id --> account_id --> (epoch, commitment)
+	pub type EntropyCommitments<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		u32,
+		Blake2_128Concat,
+		T::AccountId,
+		(u64, [u8; 32]),
+		OptionQuery,
+	>;
+
+	// Entropy revealed by subnet nodes, used to elect the next epochs validator and accountants
+	#[pallet::storage] // subnet_id --> (epoch, entropy)
+	pub type SubnetEntropy<T> = StorageMap<_, Identity, u32, (u64, [u8; 32]), OptionQuery>;
+
 	// Maximum stake balance per subnet
 	// Only checked on `do_add_stake` and ``
 	// A subnet staker can have greater than the max stake balance although any rewards
@@ -1267,6 +1700,86 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type DelegateStakeRewardsPercentage<T: Config> = StorageValue<_, u128, ValueQuery, DefaultDelegateStakeRewardsPercentage>;
 
+	// Subnet node delegate staking logic
+
+	// Total delegate stake shares of a subnet node
+	#[pallet::storage] // subnet_id --> node account_id --> u128
+	pub type TotalNodeDelegateStakeShares<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		u32,
+		Blake2_128Concat,
+		T::AccountId,
+		u128,
+		ValueQuery,
+	>;
+
+	// Total delegate stake balance of a subnet node, rewards are deposited here
+	#[pallet::storage] // subnet_id --> node account_id --> u128
+	pub type TotalNodeDelegateStakeBalance<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		u32,
+		Blake2_128Concat,
+		T::AccountId,
+		u128,
+		ValueQuery,
+	>;
+
+	// An accounts delegate stake shares per subnet node
+	#[pallet::storage] // account_id --> (subnet_id, node account_id) --> u128
+	pub type AccountNodeDelegateStakeShares<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(u32, T::AccountId),
+		u128,
+		ValueQuery,
+	>;
+
+	#[pallet::storage] // subnet_id --> ((account_id, node account_id), (initialized or removal block))
+	pub type SubnetAccountNodeDelegateStake<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		BTreeMap<(T::AccountId, T::AccountId), u64>,
+		ValueQuery,
+	>;
+
+	// Percentage of a subnet nodes delegators rewards kept by the subnet node
+	#[pallet::storage] // subnet_id --> account_id --> commission
+	pub type NodeCommission<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		u32,
+		Blake2_128Concat,
+		T::AccountId,
+		u128,
+		ValueQuery,
+	>;
+
+	// Commission changes waiting for their epoch to take effect
+	#[pallet::storage] // subnet_id --> account_id --> (commission, epoch)
+	pub type PendingNodeCommission<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		u32,
+		Blake2_128Concat,
+		T::AccountId,
+		(u128, u64),
+		OptionQuery,
+	>;
+
+	// Amount of epochs before a commission change takes effect
+	#[pallet::storage]
+	pub type NodeCommissionChangeDelay<T> = StorageValue<_, u64, ValueQuery, DefaultNodeCommissionChangeDelay>;
+
+	// Maximum delegate stake of a subnet node as a percentage of its own stake
+	// Delegators can never earn more than this ratio of the subnet nodes rewards
+	#[pallet::storage]
+	pub type MaxNodeDelegateStakeRatio<T> = StorageValue<_, u128, ValueQuery, DefaultMaxNodeDelegateStakeRatio>;
+
 	// Rate limit
 	#[pallet::storage] // ( tx_rate_limit )
 	pub type TxRateLimit<T> = StorageValue<_, u64, ValueQuery, DefaultTxRateLimit<T>>;
@@ -1805,19 +2318,35 @@ pub mod pallet {
 		RewardsData<T::AccountId>,
 	>;
 	
-	// The account responsible for validating the epochs rewards data
-	#[pallet::storage] // subnet ID => epoch  => data
-	pub type SubnetRewardsValidator<T: Config> = StorageDoubleMap<
+	// The accounts responsible for validating the epochs rewards data
+	#[pallet::storage] // subnet ID => epoch  => validators
+	pub type SubnetRewardsValidators<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		u32,
 		Identity,
 		u32,
-		T::AccountId,
+		Vec<T::AccountId>,
 		// ValueQuery,
 		// DefaultAccountId<T>
 	>;
 
+	// Amount of validators chosen per subnet per epoch
+	#[pallet::storage]
+	pub type ValidatorsPerEpoch<T> = StorageValue<_, u32, ValueQuery, DefaultValidatorsPerEpoch>;
+
+	// Network validator selection mode, used by subnets without their own mode
+	#[pallet::storage]
+	pub type ValidatorSelection<T> = StorageValue<_, ValidatorSelectionMode, ValueQuery, DefaultValidatorSelection>;
+
+	// Subnet validator selection mode overriding the network mode
+	#[pallet::storage] // subnet_id => mode
+	pub type SubnetValidatorSelection<T> = StorageMap<_, Identity, u32, ValidatorSelectionMode, OptionQuery>;
+
+	// Maximum stake counted towards a nodes chance of being chosen in `CappedStake` mode
+	#[pallet::storage]
+	pub type ValidatorStakeWeightCap<T> = StorageValue<_, u128, ValueQuery, DefaultValidatorStakeWeightCap>;
+
 	#[pallet::storage] // account_id --> u32
 	pub type AttestedEpoch<T: Config> = StorageDoubleMap<
 		_,
@@ -1874,6 +2403,14 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type MaxSequentialAbsentSubnetNode<T> = StorageValue<_, u32, ValueQuery, DefaultMaxSequentialAbsentSubnetNode>;
 
+	// Sequential absences that demote a subnet node to the class below its current class
+	#[pallet::storage]
+	pub type NodeClassDemotionAbsentThreshold<T> = StorageValue<_, u32, ValueQuery, DefaultNodeClassDemotionAbsentThreshold>;
+
+	// Every multiple of account penalties that demotes a subnet node to the class below its current class
+	#[pallet::storage]
+	pub type NodeClassDemotionPenaltyThreshold<T> = StorageValue<_, u32, ValueQuery, DefaultNodeClassDemotionPenaltyThreshold>;
+
 	// If subnet node is absent from inclusion in consensus information or attestings
 	#[pallet::storage] // subnet_id -> class_id -> BTreeMap(account_id, block)
 	pub type SequentialAbsentSubnetNode<T: Config> = StorageDoubleMap<
@@ -1914,6 +2451,13 @@ pub mod pallet {
     }
 	}
 
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+	pub struct SubnetNodeClassification {
+		pub class: SubnetNodeClass, // Highest class of the subnet node
+		pub classified: u64, // Block the class epochs are counted from, the initialized block unless demoted
+		pub promotion_epoch: Option<u64>, // Epoch the subnet node is shifted into the next class, None if in the last class
+	}
+
 	// How many epochs until an account can reach the next node class
 	// e.g. Idle 			2 epochs => account must be Idle for 2 epochs from their initialization epoch
 	//			Included	2 epochs => account must be Included for 2 epochs from their initialization epoch
@@ -1927,26 +2471,71 @@ pub mod pallet {
 		DefaultSubnetNodeClassEpochs
 	>;
 
-	#[pallet::storage] // subnet_id -> class_id -> BTreeMap(account_id, block)
-	pub type SubnetNodesClasses<T: Config> = StorageDoubleMap<
+	// Epochs a subnet node must be in each class on a subnet, overrides `SubnetNodeClassEpochs`
+	#[pallet::storage] // subnet_id --> class --> epochs
+	pub type SubnetNodeClassEpochsOverride<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		u32,
+		Blake2_128Concat,
+		SubnetNodeClass,
+		u64,
+		OptionQuery,
+	>;
+
+	// Highest class of each subnet node, classes are cumulative
+	#[pallet::storage] // subnet_id -> account_id -> SubnetNodeClassification
+	pub type SubnetNodesClassification<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Identity,
+		T::AccountId,
+		SubnetNodeClassification,
+		OptionQuery,
+	>;
+
+	// Count of subnet nodes whose highest class is the class
+	#[pallet::storage] // subnet_id -> class_id -> count
+	pub type SubnetNodeClassCount<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
 		u32,
 		Identity,
 		SubnetNodeClass,
-		BTreeMap<T::AccountId, u64>,
+		u32,
+		ValueQuery,
+		DefaultZeroU32,
+	>;
+
+	// Subnet nodes due to be shifted into their next class on an epoch
+	#[pallet::storage] // subnet_id -> epoch -> Vec<account_id>
+	pub type SubnetNodeClassPromotions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		u32,
+		Identity,
+		u64,
+		Vec<T::AccountId>,
 		ValueQuery,
-		DefaultSubnetNodesClasses<T>,
 	>;
 
+	// Last epoch subnet node classes were shifted on a subnet
+	#[pallet::storage] // subnet_id -> epoch
+	pub type SubnetNodeClassShiftEpoch<T> = StorageMap<_, Identity, u32, u64, OptionQuery>;
+
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Remove a subnet if the subnet has been voted out
-		/// This can be done by anyone as long as the required conditions pass
+		/// Remove a subnet that is no longer eligible to be on the network
+		/// This can be done by anyone as long as one of the removal reasons applies
+		/// The caller receives the `SubnetRemovalBounty` from the stake vault
 		#[pallet::call_index(0)]
 		#[pallet::weight({0})]
 		pub fn remove_subnet(
@@ -1961,105 +2550,27 @@ pub mod pallet {
 			);
 
 			let subnet = SubnetsData::<T>::get(subnet_id).unwrap();
-			let subnet_path: Vec<u8> = subnet.path;
-			let model_initialized: u64 = subnet.initialized;
 
-			// ----
-			// Subnets can be removed by
-			// 		1. Subnet can be voted off
-			//		2. Subnet can reach max zero consensus count
-			//		3. Subnet can be offline too many times
-			//		4. Subnet has min peers after initialization period
-			// ----
+			// Must have at least one of the possible reasons to be removed
+			let reason: Option<SubnetRemovalReason> = Self::get_subnet_removal_reason(subnet_id, &subnet);
+			ensure!(
+				reason.is_some(),
+				Error::<T>::SubnetCantBeRemoved
+			);
 
-			let mut reason_for_removal: Vec<u8> = Vec::new();
+			let block: u64 = Self::get_current_block_as_u64();
 
-			// 1.
-			// Check subnet voted out
-			let activated: bool = match SubnetActivated::<T>::try_get(subnet_path.clone()) {
-				Ok(data) => data.active,
-				Err(()) => false,
-			};
+			Self::do_remove_subnet(block, subnet_id, Some(account_id.clone()), reason.unwrap());
 
-			// Push into reason
-			if !activated {
-				reason_for_removal.push(1)
+			// --- Pay the bounty from the stake vault, up to the vault balance
+			let bounty: u128 = SubnetRemovalBounty::<T>::get().min(StakeVaultBalance::<T>::get());
+			if bounty > 0 {
+				if let Some(bounty_as_balance) = Self::u128_to_balance(bounty) {
+					StakeVaultBalance::<T>::mutate(|n: &mut u128| *n -= bounty);
+					Self::add_balance_to_coldkey_account(&account_id, bounty_as_balance);
+				}
 			}
 
-			// 2.
-			// Subnet can reach max zero consensus count
-			let zero_consensus_epochs: u32 = SubnetConsensusEpochsErrors::<T>::get(subnet_id);
-			let max_zero_consensus_epochs: u32 = MaxSubnetConsensusEpochsErrors::<T>::get();
-			let too_many_max_consensus_epochs: bool = zero_consensus_epochs > max_zero_consensus_epochs;
-
-			// Push into reason
-			if too_many_max_consensus_epochs {
-				reason_for_removal.push(2)
-			}
-
-			// 3.
-			// Check if subnet is offline too many times
-			let is_offline: bool = false;
-
-			// Push into reason
-			if is_offline {
-				reason_for_removal.push(3)
-			}
-
-			// 4.
-			// Check if subnet has min amount of peers
-			// If min peers are not met and initialization epochs has surpassed
-			// then subnet can be removed
-			let total_subnet_nodes: u32 = TotalSubnetNodes::<T>::get(subnet_id);
-			let min_subnet_nodes: u32 = MinSubnetNodes::<T>::get();
-			let block: u64 = Self::get_current_block_as_u64();
-			let mut has_min_peers: bool = true;
-			if total_subnet_nodes < min_subnet_nodes {
-				let epoch_length: u64 = T::EpochLength::get();
-				let subnet_nodes_initialization_epochs: u64 = SubnetNodesInitializationEpochs::<T>::get();
-				// Ensure initialization epochs have passed
-				// If not return false
-				let initialized: bool = block < Self::get_eligible_epoch_block(
-					epoch_length, 
-					model_initialized, 
-					subnet_nodes_initialization_epochs
-				);
-				// Push into reason
-				if !initialized {
-					reason_for_removal.push(4)
-				}	
-			}
-
-			// Must have at least one of the possible reasons to be removed
-			ensure!(
-				!activated || too_many_max_consensus_epochs || is_offline || !has_min_peers,
-				Error::<T>::SubnetCantBeRemoved
-			);
-
-			// Remove unique path
-			SubnetPaths::<T>::remove(subnet_path.clone());
-			// Remove subnet data
-			SubnetsData::<T>::remove(subnet_id);
-
-			// We don't subtract TotalSubnets since it's used for ids
-
-			// Remove all peers data
-			let _ = SubnetNodesData::<T>::clear_prefix(subnet_id, u32::MAX, None);
-			let _ = TotalSubnetNodes::<T>::remove(subnet_id);
-			let _ = SubnetNodeAccount::<T>::clear_prefix(subnet_id, u32::MAX, None);
-
-			// Remove all subnet consensus data
-			let _ = SubnetPenaltyCount::<T>::remove(subnet_id);
-			let _ = SubnetNodesClasses::<T>::clear_prefix(subnet_id, u32::MAX, None);
-	
-			Self::deposit_event(Event::SubnetRemoved { 
-				account: account_id, 
-				subnet_id: subnet_id, 
-				subnet_path: subnet_path.clone(),
-				reason: reason_for_removal,
-				block: block
-			});
-
 			Ok(())
 		}
 
@@ -2147,26 +2658,12 @@ pub mod pallet {
 			// This ensures others cannot claim to own a PeerId they are not the owner of
 			// Self::validate_signature(&Encode::encode(&peer_id), &signature, &signer)?;
 
-			// ========================
-			// Insert peer into storage
-			// ========================
-			let subnet_node: SubnetNode<T::AccountId> = SubnetNode {
-				account_id: account_id.clone(),
-				peer_id: peer_id.clone(),
-				initialized: block,
-			};
-			// Insert SubnetNodesData with account_id as key
-			SubnetNodesData::<T>::insert(subnet_id, account_id.clone(), subnet_node);
-
-			// Insert subnet peer account to keep peer_ids unique within subnets
-			SubnetNodeAccount::<T>::insert(subnet_id, peer_id.clone(), account_id.clone());
-
 			// Insert unstaking reinforcements
 			// This data is specifically used for allowing unstaking after being removed
 			// SubnetAccount is not removed from storage until the peer has unstaked their entire stake balance
 			// This stores the block they are initialized at
 			// If removed, the initialized block will be replace with the removal block
-			let mut model_accounts: BTreeMap<T::AccountId, u64> = SubnetAccount::<T>::get(subnet_id);
+			let model_accounts: BTreeMap<T::AccountId, u64> = SubnetAccount::<T>::get(subnet_id);
 			// let model_account: Option<&u64> = model_accounts.get(&account_id.clone());
 			let block_initialized_or_removed: u64 = match model_accounts.get(&account_id.clone()) {
 				Some(block_initialized_or_removed) => *block_initialized_or_removed,
@@ -2190,39 +2687,10 @@ pub mod pallet {
 				);	
 			}
 
-			// Update to current block
-			model_accounts.insert(account_id.clone(), block);
-			SubnetAccount::<T>::insert(subnet_id, model_accounts);
-
-			if let Ok(mut node_class) = SubnetNodesClasses::<T>::try_get(subnet_id, SubnetNodeClass::Idle) {
-				node_class.insert(account_id.clone(), block);
-				SubnetNodesClasses::<T>::insert(subnet_id, SubnetNodeClass::Idle, node_class);	
-			} else {
-				// If new subnet, initialize classes
-				let mut node_class: BTreeMap<T::AccountId, u64> = BTreeMap::new();
-				node_class.insert(account_id.clone(), block);
-				SubnetNodesClasses::<T>::insert(subnet_id, SubnetNodeClass::Idle, node_class);	
-			}
-
-			// let mut node_class: BTreeMap<T::AccountId, u64> = SubnetNodesClasses::<T>::get(subnet_id, SubnetNodeClass::Idle).unwrap();
-			// node_class.insert(account_id.clone(), block);
-			// SubnetNodesClasses::<T>::insert(subnet_id, SubnetNodeClass::Idle, node_class);
-
-			// Add subnet_id to account
-			// Account can only have a subnet peer per subnet so we don't check if it exists
-			AccountSubnets::<T>::append(account_id.clone(), subnet_id);
-
-			// Increase total subnet peers
-			TotalSubnetNodes::<T>::mutate(subnet_id, |n: &mut u32| *n += 1);
-
-			Self::deposit_event(
-				Event::SubnetNodeAdded { 
-					subnet_id: subnet_id, 
-					account_id: account_id.clone(), 
-					peer_id: peer_id.clone(),
-					block: block
-				}
-			);
+			// ========================
+			// Insert peer into storage
+			// ========================
+			Self::do_insert_subnet_node(subnet_id, account_id, peer_id, block);
 
 			Ok(())
 		}
@@ -2278,7 +2746,7 @@ pub mod pallet {
 				Error::<T>::InvalidRemoveOrUpdateSubnetNodeBlock
 			);
 
-			let submit_epochs = SubnetNodeClassEpochs::<T>::get(SubnetNodeClass::Submittable);
+			let submit_epochs = Self::get_subnet_node_class_epochs(subnet_id, SubnetNodeClass::Submittable);
 
 			// Check if subnet peer is eligible for consensus submission
 			//
@@ -2352,7 +2820,7 @@ pub mod pallet {
 			// We don't check consensus steps here because a subnet peers stake isn't included in calculating rewards 
 			// that hasn't reached their consensus submission epoch yet
 			
-			Self::do_remove_subnet_node(block, subnet_id, account_id.clone());
+			Self::do_remove_subnet_node(block, subnet_id, account_id.clone(), SubnetNodeRemovalReason::SelfRemoved);
 
 			Ok(())
 		}
@@ -2527,7 +2995,7 @@ pub mod pallet {
 
 			// Insert or update the accounts subnet stake add block
 			model_account_delegate_stakes.insert(account_id.clone(), block);
-			SubnetAccount::<T>::insert(subnet_id, model_account_delegate_stakes);
+			SubnetAccountDelegateStake::<T>::insert(subnet_id, model_account_delegate_stakes);
 
 			Self::do_add_delegate_stake(
 				origin, 
@@ -2649,6 +3117,7 @@ pub mod pallet {
 				block, 
 				epoch_length,
 				epoch as u32,
+				Self::full_attestation(),
 			)
 		}
 
@@ -2705,6 +3174,189 @@ pub mod pallet {
 				vote
 			)
 		}
+
+		/// Claim stake and delegate stake balances refunded from removed subnets
+		#[pallet::call_index(15)]
+		#[pallet::weight({0})]
+		pub fn claim_balance(
+			origin: OriginFor<T>, 
+		) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+			Self::do_claim_balance(account_id)
+		}
+
+		/// Claim stake and delegate stake balances that finished unbonding on a subnet
+		#[pallet::call_index(16)]
+		#[pallet::weight({0})]
+		pub fn claim_unbonded(
+			origin: OriginFor<T>, 
+			subnet_id: u32,
+		) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+			Self::do_claim_unbonded(account_id, subnet_id)
+		}
+
+		/// Commit to entropy used to elect the subnets validator and accountants
+		/// 
+		/// # Arguments
+		///
+		/// * `commitment` - `blake2_256((entropy, account_id))` of the entropy revealed next epoch.
+		///
+		/// Fails while a previous commitment is unrevealed.
+		///
+		#[pallet::call_index(17)]
+		#[pallet::weight({0})]
+		pub fn commit_entropy(
+			origin: OriginFor<T>, 
+			subnet_id: u32,
+			commitment: [u8; 32],
+		) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+			Self::do_commit_entropy(account_id, subnet_id, commitment)
+		}
+
+		/// Reveal entropy committed to in the previous epoch
+		#[pallet::call_index(18)]
+		#[pallet::weight({0})]
+		pub fn reveal_entropy(
+			origin: OriginFor<T>, 
+			subnet_id: u32,
+			entropy: [u8; 32],
+		) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+			Self::do_reveal_entropy(account_id, subnet_id, entropy)
+		}
+
+		/// Attest the epochs rewards data with a partial agreement
+		/// 
+		/// # Arguments
+		///
+		/// * `attestation` - Percentage of the data the attestor agrees with and the peers it disputes.
+		///
+		#[pallet::call_index(19)]
+		#[pallet::weight({0})]
+		pub fn attest_partial(
+			origin: OriginFor<T>, 
+			subnet_id: u32,
+			attestation: AttestationData,
+		) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+			let block: u64 = Self::get_current_block_as_u64();
+			let epoch_length: u64 = T::EpochLength::get();
+			let epoch: u64 = block / epoch_length;
+
+			Self::do_attest(
+				subnet_id, 
+				account_id,
+				block, 
+				epoch_length,
+				epoch as u32,
+				attestation,
+			)
+		}
+
+		/// Delegate stake to a subnet node
+		/// Delegators share the subnet nodes rewards relative to the nodes stake, minus the nodes commission
+		#[pallet::call_index(20)]
+		#[pallet::weight({0})]
+		pub fn add_to_node_delegate_stake(
+			origin: OriginFor<T>, 
+			subnet_id: u32,
+			node_account_id: T::AccountId,
+			stake_to_be_added: u128,
+		) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+			Self::do_add_node_delegate_stake(
+				account_id,
+				subnet_id,
+				node_account_id,
+				stake_to_be_added,
+			)
+		}
+
+		/// Remove delegate stake shares from a subnet node
+		/// Removed balances are unbonding for `StakeUnbondingEpochs` before they can be claimed
+		#[pallet::call_index(21)]
+		#[pallet::weight({0})]
+		pub fn remove_node_delegate_stake(
+			origin: OriginFor<T>, 
+			subnet_id: u32,
+			node_account_id: T::AccountId,
+			shares_to_be_removed: u128,
+		) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+			Self::do_remove_node_delegate_stake(
+				account_id,
+				subnet_id,
+				node_account_id,
+				shares_to_be_removed,
+			)
+		}
+
+		/// Set the commission kept from the subnet nodes delegators rewards
+		/// Takes effect after `NodeCommissionChangeDelay` epochs
+		///
+		/// # Arguments
+		///
+		/// * `commission` - Commission in 1e4 format.
+		///
+		#[pallet::call_index(22)]
+		#[pallet::weight({0})]
+		pub fn set_node_commission(
+			origin: OriginFor<T>, 
+			subnet_id: u32,
+			commission: u128,
+		) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+			Self::do_set_node_commission(account_id, subnet_id, commission)
+		}
+
+		/// Move stake from one subnet to another without unbonding
+		/// The account must be a subnet node on the subnet the stake is moved to
+		#[pallet::call_index(23)]
+		#[pallet::weight({0})]
+		pub fn move_stake(
+			origin: OriginFor<T>, 
+			from_subnet_id: u32,
+			to_subnet_id: u32,
+			stake_to_be_moved: u128,
+		) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+			Self::do_move_stake(
+				account_id,
+				from_subnet_id,
+				to_subnet_id,
+				stake_to_be_moved,
+			)
+		}
+
+		/// Move delegate stake shares from one subnet to another without unbonding
+		#[pallet::call_index(24)]
+		#[pallet::weight({0})]
+		pub fn move_delegate_stake(
+			origin: OriginFor<T>, 
+			from_subnet_id: u32,
+			to_subnet_id: u32,
+			delegate_stake_shares_to_be_moved: u128,
+		) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+			Self::do_move_delegate_stake(
+				account_id,
+				from_subnet_id,
+				to_subnet_id,
+				delegate_stake_shares_to_be_moved,
+			)
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -2721,6 +3373,12 @@ pub mod pallet {
 				Error::<T>::SubnetExist
 			);
 
+			// Ensure the subnets class epochs are ordered
+			ensure!(
+				subnet_data.class_epochs.is_empty() || Self::is_valid_subnet_node_class_epochs(&subnet_data.class_epochs),
+				Error::<T>::InvalidSubnetNodeClassEpochs
+			);
+
 			// not needed since can only be called by logic in subnet democracy pallet for activating new subnets
 			// let activated: bool = match SubnetActivated::<T>::try_get(subnet_data.clone().path) {
 			// 	Ok(data) => data.active,
@@ -2772,6 +3430,10 @@ pub mod pallet {
 			let subnet_id = subnet_len + 1;
 			
 			let base_node_memory: u128 = BaseSubnetNodeMemoryMB::<T>::get();
+
+			for (class_id, epochs) in subnet_data.class_epochs.iter() {
+				SubnetNodeClassEpochsOverride::<T>::insert(subnet_id, class_id, epochs);
+			}
 	
 			let min_subnet_nodes: u32 = Self::get_min_subnet_nodes(base_node_memory, subnet_data.memory_mb);
 			let target_subnet_nodes: u32 = Self::get_target_subnet_nodes(base_node_memory, min_subnet_nodes);
@@ -2792,6 +3454,9 @@ pub mod pallet {
 			// Increase total subnets. This is used for unique Subnet IDs
 			TotalSubnets::<T>::mutate(|n: &mut u32| *n += 1);
 
+			// Subnet node classes are shifted from the next epoch
+			SubnetNodeClassShiftEpoch::<T>::insert(subnet_id, block / T::EpochLength::get());
+
 			LastSubnetInitializedBlock::<T>::set(block);
 
 			Self::deposit_event(Event::SubnetAdded { 
@@ -2823,178 +3488,224 @@ pub mod pallet {
 				Error::<T>::SubnetNotExist
 			);
 
-			let subnet = SubnetsData::<T>::get(subnet_id).unwrap();
-			let subnet_path: Vec<u8> = subnet.path;
+			// Subnet democracy has already voted the subnet out so no further removal checks are needed
+			let block: u64 = Self::get_current_block_as_u64();
+			Self::do_remove_subnet(block, subnet_id, Some(deactivator), SubnetRemovalReason::SubnetDemocracy);
 
-			// ----
-			// Subnets can be removed by
-			// 		1. Subnet can be voted off
-			//		2. Subnet can reach max zero consensus count
-			//		3. Subnet can be offline too many times
-			//		4. Subnet has min peers after initialization period
-			// ----
+			Ok(())
+		}
 
-			let mut reason_for_removal: Vec<u8> = Vec::new();
+		/// Register the bootstrap subnet nodes of a newly activated subnet
+		///
+		/// Each node is held to the same requirements as `add_subnet_node` and its stake is
+		/// transferred from its account, except the transaction rate limit which doesn't apply
+		/// since the nodes don't sign the activation
+		///
+		/// A node that doesn't meet the requirements is skipped without failing the activation
+		pub fn register_bootstrap_subnet_nodes(
+			subnet_id: u32,
+			subnet_nodes: Vec<BootstrapSubnetNode<T::AccountId>>,
+		) {
+			let block: u64 = Self::get_current_block_as_u64();
 
-			// // 1.
-			// // Check subnet voted out
-			// let activated: bool = match SubnetActivated::<T>::try_get(subnet_path.clone()) {
-			// 	Ok(data) => data.active,
-			// 	Err(()) => false,
-			// };
+			for subnet_node in subnet_nodes.into_iter() {
+				let account_id: T::AccountId = subnet_node.account_id.clone();
+				let peer_id: PeerId = subnet_node.peer_id.clone();
+
+				let result: DispatchResult = frame_support::storage::with_storage_layer(|| {
+					Self::do_register_bootstrap_subnet_node(subnet_id, subnet_node, block)
+				});
+
+				if let Err(error) = result {
+					Self::deposit_event(Event::BootstrapSubnetNodeSkipped { 
+						subnet_id: subnet_id, 
+						account_id: account_id, 
+						peer_id: peer_id, 
+						error: error,
+					});
+				}
+			}
+		}
 
-			// // Push into reason
-			// if !activated {
-			// 	reason_for_removal.push(1)
-			// }
+		/// Stake and insert a single bootstrap subnet node
+		fn do_register_bootstrap_subnet_node(
+			subnet_id: u32,
+			subnet_node: BootstrapSubnetNode<T::AccountId>,
+			block: u64,
+		) -> DispatchResult {
+			let account_id: T::AccountId = subnet_node.account_id;
+			let peer_id: PeerId = subnet_node.peer_id;
+			let stake: u128 = subnet_node.stake;
 
-			// 2.
-			// // Subnet can reach max zero consensus count
-			// let zero_consensus_epochs: u32 = SubnetConsensusEpochsErrors::<T>::get(subnet_id);
-			// let max_zero_consensus_epochs: u32 = MaxSubnetConsensusEpochsErrors::<T>::get();
-			// let too_many_max_consensus_epochs: bool = zero_consensus_epochs > max_zero_consensus_epochs;
+			ensure!(
+				Self::is_account_eligible(account_id.clone()),
+				Error::<T>::AccountIneligible
+			);
 
-			// // Push into reason
-			// if too_many_max_consensus_epochs {
-			// 	reason_for_removal.push(2)
-			// }
+			ensure!(
+				TotalSubnetNodes::<T>::get(subnet_id) < MaxSubnetNodes::<T>::get(),
+				Error::<T>::SubnetNodesMax
+			);
 
-			// 3.
-			// // Check if subnet is offline too many times
-			// let is_offline: bool = false;
+			ensure!(
+				!SubnetNodesData::<T>::contains_key(subnet_id, account_id.clone()),
+				Error::<T>::SubnetNodeExist
+			);
 
-			// // Push into reason
-			// if is_offline {
-			// 	reason_for_removal.push(3)
-			// }
+			ensure!(
+				!SubnetNodeAccount::<T>::contains_key(subnet_id, peer_id.clone()),
+				Error::<T>::PeerIdExist
+			);
 
-			// 4.
-			// Check if subnet has min amount of peers
-			// If min peers are not met and initialization epochs has surpassed
-			// then subnet can be removed
-			// let total_subnet_nodes: u32 = TotalSubnetNodes::<T>::get(subnet_id);
-			// let min_subnet_nodes: u32 = subnet.min_nodes;
-			// let block: u64 = Self::get_current_block_as_u64();
-			// let mut has_min_peers: bool = true;
-			// if total_subnet_nodes < min_subnet_nodes {
-			// 	let model_initialized: u64 = subnet.initialized;
-			// 	let epoch_length: u64 = T::EpochLength::get();
-			// 	let subnet_nodes_initialization_epochs: u64 = SubnetNodesInitializationEpochs::<T>::get();
-			// 	// Ensure initialization epochs have passed
-			// 	// If not return false
-			// 	let initialized: bool = block < Self::get_eligible_epoch_block(
-			// 		epoch_length, 
-			// 		model_initialized, 
-			// 		subnet_nodes_initialization_epochs
-			// 	);
-			// 	// Push into reason
-			// 	if !initialized {
-			// 		reason_for_removal.push(4)
-			// 	}	
-			// }
+			ensure!(
+				Self::validate_peer_id(peer_id.clone()),
+				Error::<T>::InvalidPeerId
+			);
 
-			// Must have at least one of the possible reasons to be removed
-			// ensure!(
-			// 	!activated || too_many_max_consensus_epochs || is_offline || !has_min_peers,
-			// 	Error::<T>::SubnetCantBeRemoved
-			// );
+			ensure!(
+				stake >= MinStakeBalance::<T>::get(),
+				Error::<T>::MinStakeNotReached
+			);
+
+			ensure!(
+				stake <= MaxStakeBalance::<T>::get(),
+				Error::<T>::MaxStakeReached
+			);
+
+			let stake_as_balance = Self::u128_to_balance(stake);
 
-			// Remove unique path
-			SubnetPaths::<T>::remove(subnet_path.clone());
-			// Remove subnet data
-			SubnetsData::<T>::remove(subnet_id);
+			ensure!(
+				stake_as_balance.is_some(),
+				Error::<T>::CouldNotConvertToBalance
+			);
+
+			ensure!(
+				Self::can_remove_balance_from_coldkey_account(&account_id, stake_as_balance.unwrap()),
+				Error::<T>::NotEnoughBalanceToStake
+			);
 
-			// We don't subtract TotalSubnets since it's used for ids
+			ensure!(
+				Self::remove_balance_from_coldkey_account(&account_id, stake_as_balance.unwrap()) == true,
+				Error::<T>::BalanceWithdrawalError
+			);
 
-			// Remove all peers data
-			let _ = SubnetNodesData::<T>::clear_prefix(subnet_id, u32::MAX, None);
-			let _ = TotalSubnetNodes::<T>::remove(subnet_id);
-			let _ = SubnetNodeAccount::<T>::clear_prefix(subnet_id, u32::MAX, None);
+			Self::increase_account_stake(&account_id, subnet_id, stake);
 
-			// Remove all subnet consensus data
-			let _ = SubnetPenaltyCount::<T>::remove(subnet_id);
-			let _ = SubnetNodesClasses::<T>::clear_prefix(subnet_id, u32::MAX, None);
-	
-			Self::deposit_event(Event::SubnetRemoved { 
-				account: deactivator, 
-				subnet_id: subnet_id, 
-				subnet_path: subnet_path.clone(),
-				reason: reason_for_removal,
-				block: 0
-			});
+			Self::deposit_event(Event::StakeAdded(subnet_id, account_id.clone(), stake));
+
+			Self::do_insert_subnet_node(subnet_id, account_id, peer_id, block);
 
 			Ok(())
 		}
 
-		fn is_model_eligible(
-			subnet_id: u32, 
-			subnet_path: Vec<u8>, 
-			model_initialized: u64
-		) -> (bool, Vec<u8>) {
-			let mut reason_for_removal: Vec<u8> = Vec::new();
+		/// Insert a staked subnet node into storage as `Idle`
+		fn do_insert_subnet_node(
+			subnet_id: u32,
+			account_id: T::AccountId,
+			peer_id: PeerId,
+			block: u64,
+		) {
+			let subnet_node: SubnetNode<T::AccountId> = SubnetNode {
+				account_id: account_id.clone(),
+				peer_id: peer_id.clone(),
+				initialized: block,
+			};
+			// Insert SubnetNodesData with account_id as key
+			SubnetNodesData::<T>::insert(subnet_id, account_id.clone(), subnet_node);
 
-			// 1.
-			// check subnet voted out
-			// let voted: bool = match SubnetVoteOut::<T>::try_get(subnet_path.clone()) {
-			// 	Ok(vote) => vote,
-			// 	Err(()) => false,
-			// };
+			// Insert subnet peer account to keep peer_ids unique within subnets
+			SubnetNodeAccount::<T>::insert(subnet_id, peer_id.clone(), account_id.clone());
 
-			let activated: bool = match SubnetActivated::<T>::try_get(subnet_path.clone()) {
+			// Update unstaking reinforcements to current block
+			SubnetAccount::<T>::mutate(subnet_id, |accounts: &mut BTreeMap<T::AccountId, u64>| {
+				accounts.insert(account_id.clone(), block);
+			});
+
+			// --- Classify as Idle and schedule the shift into the next class
+			Self::classify_subnet_node(subnet_id, &account_id, SubnetNodeClass::Idle, block);
+
+			// Add subnet_id to account
+			// Account can only have a subnet peer per subnet so we don't check if it exists
+			AccountSubnets::<T>::append(account_id.clone(), subnet_id);
+
+			// Increase total subnet peers
+			TotalSubnetNodes::<T>::mutate(subnet_id, |n: &mut u32| *n += 1);
+
+			Self::deposit_event(
+				Event::SubnetNodeAdded { 
+					subnet_id: subnet_id, 
+					account_id: account_id, 
+					peer_id: peer_id,
+					block: block
+				}
+			);
+		}
+
+		/// Returns the reason a subnet can be removed, if any
+		// ----
+		// Subnets can be removed by
+		// 		1. Subnet can be voted off
+		//		2. Subnet can reach max zero consensus count
+		//		3. Subnet can be offline too many times
+		//		4. Subnet has min peers after initialization period
+		//		5. Subnet can reach max penalties count
+		// ----
+		pub fn get_subnet_removal_reason(
+			subnet_id: u32, 
+			subnet: &SubnetData, 
+		) -> Option<SubnetRemovalReason> {
+			// 1.
+			// Check subnet voted out
+			let activated: bool = match SubnetActivated::<T>::try_get(subnet.path.clone()) {
 				Ok(data) => data.active,
 				Err(()) => false,
 			};
 
-			// Push into reason
 			if !activated {
-				reason_for_removal.push(1)
+				return Some(SubnetRemovalReason::SubnetDemocracy)
 			}
 
 			// 2.
 			// Subnet can reach max zero consensus count
 			let zero_consensus_epochs: u32 = SubnetConsensusEpochsErrors::<T>::get(subnet_id);
 			let max_zero_consensus_epochs: u32 = MaxSubnetConsensusEpochsErrors::<T>::get();
-			let too_many_max_consensus_epochs: bool = zero_consensus_epochs > max_zero_consensus_epochs;
 
-			// Push into reason
-			if too_many_max_consensus_epochs {
-				reason_for_removal.push(2)
+			if zero_consensus_epochs > max_zero_consensus_epochs {
+				return Some(SubnetRemovalReason::MaxConsensusEpochsErrors)
 			}
 
 			// 3.
 			// Check if subnet is offline too many times
-			let is_offline: bool = false;
-
-			// Push into reason
-			if is_offline {
-				reason_for_removal.push(3)
-			}
+			// *NOT IMPLEMENTED YET
 
 			// 4.
 			// Check if subnet has min amount of peers
 			// If min peers are not met and initialization epochs has surpassed
 			// then subnet can be removed
 			let total_subnet_nodes: u32 = TotalSubnetNodes::<T>::get(subnet_id);
-			let min_subnet_nodes: u32 = MinSubnetNodes::<T>::get();
-			let mut has_min_peers: bool = true;
-			if total_subnet_nodes < min_subnet_nodes {
+			if total_subnet_nodes < subnet.min_nodes {
 				let block: u64 = Self::get_current_block_as_u64();
 				let epoch_length: u64 = T::EpochLength::get();
 				let subnet_nodes_initialization_epochs: u64 = SubnetNodesInitializationEpochs::<T>::get();
 				// Ensure initialization epochs have passed
-				// If not return false
-				let has_min_peers: bool = block < Self::get_eligible_epoch_block(
+				let initialized: bool = block >= Self::get_eligible_epoch_block(
 					epoch_length, 
-					model_initialized, 
+					subnet.initialized, 
 					subnet_nodes_initialization_epochs
 				);
-				// Push into reason
-				if !has_min_peers {
-					reason_for_removal.push(4)
-				}	
+
+				if initialized {
+					return Some(SubnetRemovalReason::MinSubnetNodes)
+				}
 			}
 
-			(!activated || too_many_max_consensus_epochs || is_offline || !has_min_peers, reason_for_removal)
+			// 5.
+			// Subnet can reach max penalties count
+			if SubnetPenaltyCount::<T>::get(subnet_id) > MaxSubnetPenalties::<T>::get() {
+				return Some(SubnetRemovalReason::MaxPenalties)
+			}
+
+			None
 		}
 
 		/// Validates the signature of the given data with the provided signer's account ID.
@@ -3039,14 +3750,14 @@ pub mod pallet {
 				let epoch: u64 = block / epoch_length;
 
 				// Reward subnets for the previous epoch
-				Self::reward_subnets(block, (epoch - 1) as u32, epoch_length);
+				let (subnets, max_subnet_nodes, removed_subnet_nodes, removed_subnets) =
+					Self::reward_subnets(block, (epoch - 1) as u32, epoch_length);
 
 				// --- Update subnet nodes classifications
-				Self::shift_node_classes(block, epoch_length);
+				let (shifted_subnets, max_promotions) = Self::shift_node_classes(block, epoch_length);
 
-				return Weight::from_parts(207_283_478_000, 22166406)
-					.saturating_add(T::DbWeight::get().reads(18250_u64))
-					.saturating_add(T::DbWeight::get().writes(12002_u64));
+				return T::WeightInfo::reward_subnets(subnets, max_subnet_nodes, removed_subnet_nodes, removed_subnets)
+					.saturating_add(T::WeightInfo::shift_node_classes(shifted_subnets, max_promotions));
 			}
 
 			// Run the block succeeding form consensus
@@ -3055,20 +3766,21 @@ pub mod pallet {
 				let epoch: u64 = block / epoch_length;
 
 				// Choose validators and accountants for the current epoch
-				Self::do_choose_validator_and_accountants(block, epoch as u32, epoch_length);
+				let (subnets, max_subnet_nodes) = Self::do_choose_validator_and_accountants(block, epoch as u32, epoch_length);
 
-				return Weight::from_parts(153_488_564_000, 21699450)
-					.saturating_add(T::DbWeight::get().reads(6118_u64))
-					.saturating_add(T::DbWeight::get().writes(6082_u64));
+				return T::WeightInfo::do_choose_validator_and_accountants(subnets, max_subnet_nodes);
 			}
 	
 			return Weight::from_parts(8_054_000, 1638)
 				.saturating_add(T::DbWeight::get().reads(1_u64))
 		}
 
-		// fn on_idle(block_number: BlockNumberFor<T>) {
+		fn on_idle(block_number: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let block: u64 = Self::convert_block_as_u64(block_number);
 
-		// }
+			// Clean up removed subnets with the leftover block weight
+			Self::do_clean_removed_subnets(block, remaining_weight)
+		}
 
 		// fn offchain_worker(block_number: BlockNumberFor<T>) {
 			// designated for testnet v2.0
@@ -3148,6 +3860,7 @@ pub mod pallet {
 			let pre_subnet_data = PreSubnetData {
 				path: self.subnet_path.clone(),
 				memory_mb: self.memory_mb.clone(),
+				class_epochs: Vec::new(),
 			};
 		
 			let vote_subnet_data = VoteSubnetData {
@@ -3163,6 +3876,8 @@ pub mod pallet {
 			// Increase total subnets count
 			TotalSubnets::<T>::mutate(|n: &mut u32| *n += 1);
 
+			SubnetNodeClassShiftEpoch::<T>::insert(subnet_id, 0);
+
 			StakeVaultBalance::<T>::mutate(|n: &mut u128| *n += 10000000000000000000);
 			let mut count = 0;
 			for (account_id, subnet_path, peer_id) in &self.subnet_nodes {
@@ -3216,15 +3931,7 @@ pub mod pallet {
 				// -- Increase total subnet stake
 				TotalSubnetStake::<T>::mutate(subnet_id, |n: &mut u128| *n += stake_amount.clone());
 
-				if let Ok(mut node_class) = SubnetNodesClasses::<T>::try_get(subnet_id, SubnetNodeClass::Idle) {
-					node_class.insert(account_id.clone(), 0);
-					SubnetNodesClasses::<T>::insert(subnet_id, SubnetNodeClass::Idle, node_class);	
-				} else {
-					// If new subnet, initialize classes
-					let mut node_class: BTreeMap<T::AccountId, u64> = BTreeMap::new();
-					node_class.insert(account_id.clone(), 0);
-					SubnetNodesClasses::<T>::insert(subnet_id, SubnetNodeClass::Idle, node_class);	
-				}
+				Self::classify_subnet_node(subnet_id, &account_id, SubnetNodeClass::Idle, 0);
 	
 				AccountSubnets::<T>::append(account_id.clone(), subnet_id);
 
@@ -3255,6 +3962,22 @@ pub trait IncreaseStakeVault {
 	fn increase_stake_vault(amount: u128) -> DispatchResult;
 }
 
+// Randomness used to shuffle subnet nodes when electing validators and accountants
+pub trait ElectionRandomness {
+	fn random_seed(subnet_id: u32, epoch: u32) -> [u8; 32];
+}
+
+// Portion of the block subsidy paid into the stake vault on a block
+pub trait StakeVaultSubsidy {
+	fn stake_vault_subsidy(block: u64) -> u128;
+}
+
+impl StakeVaultSubsidy for () {
+	fn stake_vault_subsidy(_block: u64) -> u128 {
+		0
+	}
+}
+
 
 impl<T: Config<AccountId = AccountId>, AccountId> SubnetVote<AccountId> for Pallet<T> {
 	fn vote_model_in(vote_subnet_data: VoteSubnetData) -> DispatchResult {
@@ -3265,14 +3988,29 @@ impl<T: Config<AccountId = AccountId>, AccountId> SubnetVote<AccountId> for Pall
 		SubnetActivated::<T>::insert(vote_subnet_data.clone().data.path, vote_subnet_data.clone());
 		Ok(())
 	}
-	fn vote_activated(activator: AccountId, path: Vec<u8>, proposer: AccountId, vote_subnet_data: VoteSubnetData) -> DispatchResult {
-		SubnetActivated::<T>::insert(path, vote_subnet_data.clone());
+	fn vote_activated(
+		activator: AccountId, 
+		path: Vec<u8>, 
+		proposer: AccountId, 
+		vote_subnet_data: VoteSubnetData,
+		subnet_nodes: Vec<BootstrapSubnetNode<AccountId>>,
+	) -> DispatchResult {
+		SubnetActivated::<T>::insert(path.clone(), vote_subnet_data.clone());
 
-		Self::activate_subnet(
-			activator, 
-			proposer,
-			vote_subnet_data.clone().data,
-		)
+		// --- Bootstrap nodes that can't be registered are skipped, only a failed activation reverts
+		frame_support::storage::with_storage_layer(|| {
+			Self::activate_subnet(
+				activator, 
+				proposer,
+				vote_subnet_data.clone().data,
+			)?;
+
+			let subnet_id: u32 = SubnetPaths::<T>::get(path).ok_or(Error::<T>::SubnetNotExist)?;
+
+			Self::register_bootstrap_subnet_nodes(subnet_id, subnet_nodes);
+
+			Ok(())
+		})
 	}
 	fn vote_deactivated(deactivator: AccountId, path: Vec<u8>, proposer: AccountId, vote_subnet_data: VoteSubnetData) -> DispatchResult {
 		SubnetActivated::<T>::insert(path, vote_subnet_data.clone());
@@ -3353,12 +4091,15 @@ impl<T: Config<AccountId = AccountId>, AccountId> SubnetVote<AccountId> for Pall
 		let base_node_memory: u128 = BaseSubnetNodeMemoryMB::<T>::get();
 		Self::get_target_subnet_nodes(base_node_memory, min_subnet_nodes)
 	}
+	fn is_valid_subnet_node_class_epochs(class_epochs: Vec<(SubnetNodeClass, u64)>) -> bool {
+		class_epochs.is_empty() || Self::is_valid_subnet_node_class_epochs(&class_epochs)
+	}
 }
 
 pub trait SubnetVote<AccountId> {
 	fn vote_model_in(vote_subnet_data: VoteSubnetData) -> DispatchResult;
 	fn vote_model_out(vote_subnet_data: VoteSubnetData) -> DispatchResult;
-	fn vote_activated(activator: AccountId, path: Vec<u8>, proposer: AccountId, vote_subnet_data: VoteSubnetData) -> DispatchResult;
+	fn vote_activated(activator: AccountId, path: Vec<u8>, proposer: AccountId, vote_subnet_data: VoteSubnetData, subnet_nodes: Vec<BootstrapSubnetNode<AccountId>>) -> DispatchResult;
 	fn vote_deactivated(deactivator: AccountId, path: Vec<u8>, proposer: AccountId, vote_subnet_data: VoteSubnetData) -> DispatchResult;
 	fn get_total_models() -> u32;
 	fn get_model_initialization_cost() -> u128;
@@ -3373,6 +4114,7 @@ pub trait SubnetVote<AccountId> {
 	fn get_total_model_errors(id: u32) -> u32;
 	fn get_min_subnet_nodes(memory_mb: u128) -> u32;
 	fn get_target_subnet_nodes(min_subnet_nodes: u32) -> u32;
+	fn is_valid_subnet_node_class_epochs(class_epochs: Vec<(SubnetNodeClass, u64)>) -> bool;
 }
 
 // Admin logic
@@ -3440,6 +4182,54 @@ impl<T: Config> AdminInterface for Pallet<T> {
 	fn set_remove_subnet_node_epoch_percentage(value: u128) -> DispatchResult {
 		Self::set_remove_subnet_node_epoch_percentage(value)
 	}
+	fn set_subnet_removal_bounty(value: u128) -> DispatchResult {
+		Self::set_subnet_removal_bounty(value)
+	}
+	fn set_stake_unbonding_epochs(value: u64) -> DispatchResult {
+		Self::set_stake_unbonding_epochs(value)
+	}
+	fn set_validator_selection(value: ValidatorSelectionMode) -> DispatchResult {
+		Self::set_validator_selection(value)
+	}
+	fn set_subnet_validator_selection(subnet_id: u32, value: Option<ValidatorSelectionMode>) -> DispatchResult {
+		Self::set_subnet_validator_selection(subnet_id, value)
+	}
+	fn set_validator_stake_weight_cap(value: u128) -> DispatchResult {
+		Self::set_validator_stake_weight_cap(value)
+	}
+	fn set_validators_per_epoch(value: u32) -> DispatchResult {
+		Self::set_validators_per_epoch(value)
+	}
+	fn set_node_commission_change_delay(value: u64) -> DispatchResult {
+		Self::set_node_commission_change_delay(value)
+	}
+	fn set_max_node_delegate_stake_ratio(value: u128) -> DispatchResult {
+		Self::set_max_node_delegate_stake_ratio(value)
+	}
+	fn set_stake_move_cooldown_epochs(value: u64) -> DispatchResult {
+		Self::set_stake_move_cooldown_epochs(value)
+	}
+	fn set_epoch_rewards_retention_epochs(value: u32) -> DispatchResult {
+		Self::set_epoch_rewards_retention_epochs(value)
+	}
+	fn set_subnet_node_class_epochs(class_id: SubnetNodeClass, value: u64) -> DispatchResult {
+		Self::set_subnet_node_class_epochs(class_id, value)
+	}
+	fn set_subnet_node_class_epochs_override(subnet_id: u32, class_epochs: Vec<(SubnetNodeClass, u64)>) -> DispatchResult {
+		Self::set_subnet_node_class_epochs_override(subnet_id, class_epochs)
+	}
+	fn set_node_class_demotion_absent_threshold(value: u32) -> DispatchResult {
+		Self::set_node_class_demotion_absent_threshold(value)
+	}
+	fn set_node_class_demotion_penalty_threshold(value: u32) -> DispatchResult {
+		Self::set_node_class_demotion_penalty_threshold(value)
+	}
+	fn set_base_subnet_reward(value: u128) -> DispatchResult {
+		Self::set_base_subnet_reward(value)
+	}
+	fn set_slash_percentage(value: u128) -> DispatchResult {
+		Self::set_slash_percentage(value)
+	}
 }
 
 pub trait AdminInterface {
@@ -3464,4 +4254,20 @@ pub trait AdminInterface {
 	fn set_model_per_peer_init_cost(value: u128) -> DispatchResult;
 	fn set_model_consensus_unconfirmed_threshold(value: u128) -> DispatchResult;
 	fn set_remove_subnet_node_epoch_percentage(value: u128) -> DispatchResult;
+	fn set_subnet_removal_bounty(value: u128) -> DispatchResult;
+	fn set_stake_unbonding_epochs(value: u64) -> DispatchResult;
+	fn set_validator_selection(value: ValidatorSelectionMode) -> DispatchResult;
+	fn set_subnet_validator_selection(subnet_id: u32, value: Option<ValidatorSelectionMode>) -> DispatchResult;
+	fn set_validator_stake_weight_cap(value: u128) -> DispatchResult;
+	fn set_validators_per_epoch(value: u32) -> DispatchResult;
+	fn set_node_commission_change_delay(value: u64) -> DispatchResult;
+	fn set_max_node_delegate_stake_ratio(value: u128) -> DispatchResult;
+	fn set_stake_move_cooldown_epochs(value: u64) -> DispatchResult;
+	fn set_epoch_rewards_retention_epochs(value: u32) -> DispatchResult;
+	fn set_subnet_node_class_epochs(class_id: SubnetNodeClass, value: u64) -> DispatchResult;
+	fn set_subnet_node_class_epochs_override(subnet_id: u32, class_epochs: Vec<(SubnetNodeClass, u64)>) -> DispatchResult;
+	fn set_node_class_demotion_absent_threshold(value: u32) -> DispatchResult;
+	fn set_node_class_demotion_penalty_threshold(value: u32) -> DispatchResult;
+	fn set_base_subnet_reward(value: u128) -> DispatchResult;
+	fn set_slash_percentage(value: u128) -> DispatchResult;
 }
\ No newline at end of file
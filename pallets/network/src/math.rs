@@ -20,10 +20,13 @@
 
 use super::*;
 
+/// Percentages are defined by default with 2 decimals of precision (100.00)
+pub const PERCENTAGE_FACTOR: u128 = 10000;
+
 impl<T: Config> Pallet<T> {
   // Percentages are defined by default with 2 decimals of precision (100.00). 
 	// The precision is indicated by PERCENTAGE_FACTOR
-	pub const PERCENTAGE_FACTOR: u128 = 10000;
+	pub const PERCENTAGE_FACTOR: u128 = PERCENTAGE_FACTOR;
   pub const HALF_PERCENT: u128 = Self::PERCENTAGE_FACTOR / 2;
   
   /// Percentage Math
@@ -128,4 +131,40 @@ impl<T: Config> Pallet<T> {
   pub fn wmul(x: u128, y: u128) -> u128 {
     ((x * y) + (1e+18 as u128 / 2)) / 1e+18 as u128
   }
+
+  /// Percentage of growth of compounding a rate over a number of periods
+  // `rate` is in `PERCENTAGE_FACTOR_V2` format, the result is in `PERCENTAGE_FACTOR` format
+  // Saturates on overflow
+  pub fn compound_percentage(rate: u128, periods: u64) -> u128 {
+    let mut base: u128 = Self::PERCENTAGE_FACTOR_V2.saturating_add(rate);
+    let mut result: u128 = Self::PERCENTAGE_FACTOR_V2;
+    let mut periods: u64 = periods;
+
+    // Exponentiation by squaring
+    while periods > 0 {
+      if periods & 1 == 1 {
+        result = result.saturating_mul(base) / Self::PERCENTAGE_FACTOR_V2;
+      }
+      base = base.saturating_mul(base) / Self::PERCENTAGE_FACTOR_V2;
+      periods >>= 1;
+    }
+
+    result.saturating_sub(Self::PERCENTAGE_FACTOR_V2) / (Self::PERCENTAGE_FACTOR_V2 / Self::PERCENTAGE_FACTOR)
+  }
+
+  /// Integer square root, rounds down
+  pub fn sqrt(x: u128) -> u128 {
+    if x < 2 {
+      return x
+    }
+
+    // Newton's method
+    let mut z: u128 = x;
+    let mut y: u128 = x / 2 + 1;
+    while y < z {
+      z = y;
+      y = (x / y + y) / 2;
+    }
+    z
+  }
 }
\ No newline at end of file
@@ -0,0 +1,288 @@
+// Copyright (C) Hypertensor.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Storage migrations of the network pallet
+
+use super::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use frame_support::{Blake2_128Concat, Identity};
+
+pub mod v1 {
+  use super::*;
+
+  /// `RewardsData` before each chosen validator submitted its own data and attestations
+  /// carried an agreement
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+  pub struct OldRewardsData<AccountId> {
+    pub validator: AccountId,
+    pub nodes_count: u32,
+    pub sum: u128,
+    pub attests: BTreeSet<AccountId>,
+    pub data: Vec<SubnetNodeData>,
+  }
+
+  /// `PreSubnetData` before activation proposals could override the subnet node class epochs
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+  pub struct OldPreSubnetData {
+    pub path: Vec<u8>,
+    pub memory_mb: u128,
+  }
+
+  impl From<OldPreSubnetData> for PreSubnetData {
+    fn from(old: OldPreSubnetData) -> Self {
+      PreSubnetData {
+        path: old.path,
+        memory_mb: old.memory_mb,
+        class_epochs: Vec::new(),
+      }
+    }
+  }
+
+  /// `VoteSubnetData` holding the `OldPreSubnetData` layout
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+  pub struct OldVoteSubnetData {
+    pub data: OldPreSubnetData,
+    pub active: bool,
+  }
+
+  /// The single chosen validator of each epoch before `SubnetRewardsValidators`
+  #[frame_support::storage_alias]
+  pub type SubnetRewardsValidator<T: Config> = StorageDoubleMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    u32,
+    Identity,
+    u32,
+    <T as frame_system::Config>::AccountId,
+  >;
+
+  /// Move the chosen validators into `SubnetRewardsValidators` and translate the stored rewards data
+  /// and activated subnet data
+  // The validators data becomes its entry in `submissions` and each attestation fully agrees
+  // Activated subnets keep the networks subnet node class epochs
+  pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+
+  impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+    fn on_runtime_upgrade() -> Weight {
+      let db_weight = T::DbWeight::get();
+
+      if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return db_weight.reads(1)
+      }
+
+      let mut reads: u64 = 1;
+      let mut writes: u64 = 0;
+
+      for (subnet_id, epoch, validator) in SubnetRewardsValidator::<T>::drain() {
+        SubnetRewardsValidators::<T>::insert(subnet_id, epoch, vec![validator]);
+        reads += 1;
+        writes += 2;
+      }
+
+      SubnetRewardsSubmission::<T>::translate::<OldRewardsData<T::AccountId>, _>(|_, _, old| {
+        reads += 1;
+        writes += 1;
+
+        let mut submissions: BTreeMap<T::AccountId, Vec<SubnetNodeData>> = BTreeMap::new();
+        submissions.insert(old.validator.clone(), old.data.clone());
+
+        Some(RewardsData {
+          validator: old.validator,
+          nodes_count: old.nodes_count,
+          sum: old.sum,
+          attests: old.attests
+            .into_iter()
+            .map(|account_id| (account_id, Pallet::<T>::full_attestation()))
+            .collect(),
+          data: old.data,
+          submissions: submissions,
+        })
+      });
+
+      SubnetActivated::<T>::translate::<OldVoteSubnetData, _>(|_, old| {
+        reads += 1;
+        writes += 1;
+
+        Some(VoteSubnetData {
+          data: old.data.into(),
+          active: old.active,
+        })
+      });
+
+      StorageVersion::new(1).put::<Pallet<T>>();
+      writes += 1;
+
+      db_weight.reads_writes(reads, writes)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+      let validators: u32 = SubnetRewardsValidator::<T>::iter().count() as u32;
+      let submissions: u32 = SubnetRewardsSubmission::<T>::iter_keys().count() as u32;
+      let activated: u32 = SubnetActivated::<T>::iter_keys().count() as u32;
+
+      Ok((validators, submissions, activated).encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+      let (validators, submissions, activated): (u32, u32, u32) = Decode::decode(&mut &state[..])
+        .map_err(|_| "MigrateToV1: invalid pre upgrade state")?;
+
+      ensure!(
+        Pallet::<T>::on_chain_storage_version() >= 1,
+        "MigrateToV1: storage version not updated"
+      );
+
+      ensure!(
+        SubnetRewardsValidator::<T>::iter_keys().count() == 0,
+        "MigrateToV1: chosen validators not drained"
+      );
+
+      // --- Every stored value must decode in the new layout
+      ensure!(
+        SubnetRewardsValidators::<T>::iter().count() as u32 >= validators,
+        "MigrateToV1: chosen validators lost"
+      );
+
+      ensure!(
+        SubnetRewardsSubmission::<T>::iter().count() as u32 == submissions,
+        "MigrateToV1: rewards data lost"
+      );
+
+      ensure!(
+        SubnetActivated::<T>::iter().count() as u32 == activated,
+        "MigrateToV1: activated subnet data lost"
+      );
+
+      Ok(())
+    }
+  }
+}
+
+pub mod v2 {
+  use super::*;
+
+  /// Cumulative subnet node classes before `SubnetNodesClassification`
+  // Each node is stored in every class up to its highest with the block it entered the class
+  #[frame_support::storage_alias]
+  pub type SubnetNodesClasses<T: Config> = StorageDoubleMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    u32,
+    Identity,
+    SubnetNodeClass,
+    BTreeMap<<T as frame_system::Config>::AccountId, u64>,
+  >;
+
+  /// Classify each subnet node by its highest class in `SubnetNodesClasses`
+  // Class counts and promotions are rebuilt from the classification and each subnet is shifted
+  // from the current epoch
+  pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
+
+  impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+    fn on_runtime_upgrade() -> Weight {
+      let db_weight = T::DbWeight::get();
+
+      if Pallet::<T>::on_chain_storage_version() >= 2 {
+        return db_weight.reads(1)
+      }
+
+      let mut reads: u64 = 1;
+      let mut writes: u64 = 0;
+
+      // --- Highest class of each subnet node and the block it entered that class
+      let mut classifications: BTreeMap<(u32, T::AccountId), (SubnetNodeClass, u64)> = BTreeMap::new();
+
+      for (subnet_id, class_id, nodes) in SubnetNodesClasses::<T>::drain() {
+        reads += 1;
+        writes += 1;
+        for (account_id, block) in nodes {
+          let entry = classifications.entry((subnet_id, account_id)).or_insert((class_id, block));
+          if class_id.index() > entry.0.index() {
+            *entry = (class_id, block);
+          }
+        }
+      }
+
+      let block: u64 = Pallet::<T>::get_current_block_as_u64();
+      let epoch: u64 = block / T::EpochLength::get();
+
+      for subnet_id in SubnetsData::<T>::iter_keys() {
+        reads += 1;
+        writes += 1;
+        SubnetNodeClassShiftEpoch::<T>::insert(subnet_id, epoch);
+      }
+
+      for ((subnet_id, account_id), (class_id, classified)) in classifications {
+        reads += 1;
+        if !SubnetNodesData::<T>::contains_key(subnet_id, &account_id) {
+          continue
+        }
+        reads += 5;
+        writes += 3;
+        Pallet::<T>::classify_subnet_node(subnet_id, &account_id, class_id, classified);
+      }
+
+      StorageVersion::new(2).put::<Pallet<T>>();
+      writes += 1;
+
+      db_weight.reads_writes(reads, writes)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+      let mut nodes: BTreeSet<(u32, T::AccountId)> = BTreeSet::new();
+      for (subnet_id, _, classes) in SubnetNodesClasses::<T>::iter() {
+        for account_id in classes.into_keys() {
+          if SubnetNodesData::<T>::contains_key(subnet_id, &account_id) {
+            nodes.insert((subnet_id, account_id));
+          }
+        }
+      }
+
+      Ok((nodes.len() as u32).encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+      let nodes: u32 = Decode::decode(&mut &state[..])
+        .map_err(|_| "MigrateToV2: invalid pre upgrade state")?;
+
+      ensure!(
+        Pallet::<T>::on_chain_storage_version() >= 2,
+        "MigrateToV2: storage version not updated"
+      );
+
+      ensure!(
+        SubnetNodesClasses::<T>::iter_keys().count() == 0,
+        "MigrateToV2: subnet node classes not drained"
+      );
+
+      ensure!(
+        SubnetNodesClassification::<T>::iter().count() as u32 == nodes,
+        "MigrateToV2: subnet node classifications lost"
+      );
+
+      let counted: u32 = SubnetNodeClassCount::<T>::iter_values().sum();
+      ensure!(
+        counted == nodes,
+        "MigrateToV2: subnet node class counts mismatch"
+      );
+
+      Ok(())
+    }
+  }
+}
@@ -136,6 +136,26 @@ parameter_types! {
 	pub const EpochLength: u64 = 100;
   pub const NetworkPalletId: PalletId = PalletId(*b"/network");
   pub const SubnetInitializationCost: u128 = 100_000_000_000_000_000_000;
+  pub static ElectionSeed: [u8; 32] = [0u8; 32];
+  pub static BlockStakeVaultSubsidy: u128 = 1_000_000_000_000_000_000;
+}
+
+// Deterministic election randomness for tests, change `ElectionSeed` to change the elected nodes
+pub struct MockElectionRandomness;
+
+impl ElectionRandomness for MockElectionRandomness {
+  fn random_seed(subnet_id: u32, epoch: u32) -> [u8; 32] {
+    sp_io::hashing::blake2_256(&(ElectionSeed::get(), subnet_id, epoch).encode())
+  }
+}
+
+// Constant stake vault subsidy for tests, change `BlockStakeVaultSubsidy` to change the subsidy
+pub struct MockStakeVaultSubsidy;
+
+impl StakeVaultSubsidy for MockStakeVaultSubsidy {
+  fn stake_vault_subsidy(_block: u64) -> u128 {
+    BlockStakeVaultSubsidy::get()
+  }
 }
 
 impl Config for Test {
@@ -150,6 +170,8 @@ impl Config for Test {
   type OffchainSignature = Signature;
 	type OffchainPublic = AccountPublic;
   type Randomness = InsecureRandomnessCollectiveFlip;
+  type ElectionRandomness = MockElectionRandomness;
+  type StakeVaultSubsidy = MockStakeVaultSubsidy;
 	type PalletId = NetworkPalletId;
   type SubnetInitializationCost = SubnetInitializationCost;
 }
@@ -0,0 +1,393 @@
+// Copyright (C) Hypertensor.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Enables accounts to delegate stake to individual subnet nodes for a portion of their rewards
+// Subnet nodes keep a commission of their delegators rewards
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+  pub fn do_add_node_delegate_stake(
+    account_id: T::AccountId,
+    subnet_id: u32,
+    node_account_id: T::AccountId,
+    delegate_stake_to_be_added: u128,
+  ) -> DispatchResult {
+    // --- Ensure subnet node exists
+    ensure!(
+      SubnetNodesData::<T>::contains_key(subnet_id, node_account_id.clone()),
+      Error::<T>::SubnetNodeNotExist
+    );
+
+    let delegate_stake_as_balance = Self::u128_to_balance(delegate_stake_to_be_added);
+
+    ensure!(
+      delegate_stake_as_balance.is_some(),
+      Error::<T>::CouldNotConvertToBalance
+    );
+
+    let account_delegate_stake_shares: u128 = AccountNodeDelegateStakeShares::<T>::get(&account_id, (subnet_id, node_account_id.clone()));
+    let total_node_delegated_stake_shares = TotalNodeDelegateStakeShares::<T>::get(subnet_id, &node_account_id);
+    let total_node_delegated_stake_balance = TotalNodeDelegateStakeBalance::<T>::get(subnet_id, &node_account_id);
+
+    // --- Get accounts current balance
+    let account_delegate_stake_balance = Self::convert_to_balance(
+      account_delegate_stake_shares,
+      total_node_delegated_stake_shares,
+      total_node_delegated_stake_balance
+    );
+
+    ensure!(
+      account_delegate_stake_balance.saturating_add(delegate_stake_to_be_added) <= MaxDelegateStakeBalance::<T>::get(),
+      Error::<T>::MaxDelegatedStakeReached
+    );
+
+    // --- Ensure the subnet nodes delegate stake stays within `MaxNodeDelegateStakeRatio` of its own stake
+    ensure!(
+      total_node_delegated_stake_balance.saturating_add(delegate_stake_to_be_added) <= Self::get_max_node_delegate_stake(subnet_id, &node_account_id),
+      Error::<T>::MaxNodeDelegateStakeRatioReached
+    );
+
+    // --- Ensure the callers account_id has enough balance to perform the transaction.
+    ensure!(
+      Self::can_remove_balance_from_coldkey_account(&account_id, delegate_stake_as_balance.unwrap()),
+      Error::<T>::NotEnoughBalanceToStake
+    );
+
+    let block: u64 = Self::get_current_block_as_u64();
+    ensure!(
+      !Self::exceeds_tx_rate_limit(Self::get_last_tx_block(&account_id), block),
+      Error::<T>::TxRateLimitExceeded
+    );
+
+    // --- Ensure the remove operation from the account_id is a success.
+    ensure!(
+      Self::remove_balance_from_coldkey_account(&account_id, delegate_stake_as_balance.unwrap()) == true,
+      Error::<T>::BalanceWithdrawalError
+    );
+
+    // --- Get amount to be added as shares based on stake to balance added to account
+    let mut delegate_stake_to_be_added_as_shares = Self::convert_to_shares(
+      delegate_stake_to_be_added,
+      total_node_delegated_stake_shares,
+      total_node_delegated_stake_balance
+    );
+
+    // --- Mitigate inflation attack
+    if total_node_delegated_stake_shares == 0 {
+      TotalNodeDelegateStakeShares::<T>::mutate(subnet_id, &node_account_id, |n: &mut u128| *n += 1000);
+      delegate_stake_to_be_added_as_shares = delegate_stake_to_be_added_as_shares.saturating_sub(1000);
+    }
+
+    // --- Check rounding errors
+    ensure!(
+      delegate_stake_to_be_added_as_shares != 0,
+      Error::<T>::CouldNotConvertToShares
+    );
+
+    Self::increase_account_node_delegate_stake_shares(
+      &account_id,
+      subnet_id,
+      &node_account_id,
+      delegate_stake_to_be_added,
+      delegate_stake_to_be_added_as_shares,
+    );
+
+    // --- Update the accounts node delegate stake add block
+    SubnetAccountNodeDelegateStake::<T>::mutate(subnet_id, |accounts| {
+      accounts.insert((account_id.clone(), node_account_id.clone()), block);
+    });
+
+    // Set last block for rate limiting
+    Self::set_last_tx_block(&account_id, block);
+
+    Self::deposit_event(
+      Event::NodeDelegateStakeAdded {
+        subnet_id: subnet_id,
+        node_account_id: node_account_id,
+        account_id: account_id,
+        amount: delegate_stake_to_be_added,
+      }
+    );
+
+    Ok(())
+  }
+
+  pub fn do_remove_node_delegate_stake(
+    account_id: T::AccountId,
+    subnet_id: u32,
+    node_account_id: T::AccountId,
+    delegate_stake_shares_to_be_removed: u128,
+  ) -> DispatchResult {
+    // --- Ensure that the delegate_stake amount to be removed is above zero.
+    ensure!(
+      delegate_stake_shares_to_be_removed > 0,
+      Error::<T>::NotEnoughStakeToWithdraw
+    );
+
+    let account_delegate_stake_shares: u128 = AccountNodeDelegateStakeShares::<T>::get(&account_id, (subnet_id, node_account_id.clone()));
+
+    // --- Ensure that the account has enough delegate_stake to withdraw.
+    ensure!(
+      account_delegate_stake_shares >= delegate_stake_shares_to_be_removed,
+      Error::<T>::NotEnoughStakeToWithdraw
+    );
+
+    let epoch_length: u64 = T::EpochLength::get();
+    let block: u64 = Self::get_current_block_as_u64();
+
+    let block_added: u64 = SubnetAccountNodeDelegateStake::<T>::get(subnet_id)
+      .get(&(account_id.clone(), node_account_id.clone()))
+      .copied()
+      .unwrap_or(0);
+
+    // --- Ensure min required epochs have surpassed to unstake
+    ensure!(
+      block >= Self::get_eligible_epoch_block(
+        epoch_length,
+        block_added,
+        MinRequiredDelegateUnstakeEpochs::<T>::get()
+      ),
+      Error::<T>::RequiredDelegateUnstakeEpochsNotMet
+    );
+
+    ensure!(
+      !Self::exceeds_tx_rate_limit(Self::get_last_tx_block(&account_id), block),
+      Error::<T>::TxRateLimitExceeded
+    );
+
+    // --- Get balance of the shares to be removed
+    let delegate_stake_to_be_removed = Self::convert_to_balance(
+      delegate_stake_shares_to_be_removed,
+      TotalNodeDelegateStakeShares::<T>::get(subnet_id, &node_account_id),
+      TotalNodeDelegateStakeBalance::<T>::get(subnet_id, &node_account_id)
+    );
+
+    // --- Ensure that we can convert this u128 to a balance.
+    ensure!(
+      Self::u128_to_balance(delegate_stake_to_be_removed).is_some(),
+      Error::<T>::CouldNotConvertToBalance
+    );
+
+    Self::decrease_account_node_delegate_stake_shares(
+      &account_id,
+      subnet_id,
+      &node_account_id,
+      delegate_stake_to_be_removed,
+      delegate_stake_shares_to_be_removed,
+    );
+
+    // --- Remove from the subnets node delegate stake accounts if no shares remain
+    if AccountNodeDelegateStakeShares::<T>::get(&account_id, (subnet_id, node_account_id.clone())) == 0 {
+      SubnetAccountNodeDelegateStake::<T>::mutate(subnet_id, |accounts| {
+        accounts.remove(&(account_id.clone(), node_account_id.clone()));
+      });
+    }
+
+    // --- Add the balance to the accounts unbonding ledger to be claimed after `StakeUnbondingEpochs`
+    Self::do_add_unbonding(&account_id, subnet_id, delegate_stake_to_be_removed, UnbondingKind::DelegateStake)?;
+
+    // Set last block for rate limiting
+    Self::set_last_tx_block(&account_id, block);
+
+    Self::deposit_event(
+      Event::NodeDelegateStakeRemoved {
+        subnet_id: subnet_id,
+        node_account_id: node_account_id,
+        account_id: account_id,
+        amount: delegate_stake_to_be_removed,
+      }
+    );
+
+    Ok(())
+  }
+
+  pub fn increase_account_node_delegate_stake_shares(
+    account_id: &T::AccountId,
+    subnet_id: u32,
+    node_account_id: &T::AccountId,
+    amount: u128,
+    shares: u128,
+  ) {
+    // -- increase account node delegate staking shares balance
+    AccountNodeDelegateStakeShares::<T>::mutate(account_id, (subnet_id, node_account_id.clone()), |n: &mut u128| *n = n.saturating_add(shares));
+
+    // -- increase total node delegate stake balance
+    TotalNodeDelegateStakeBalance::<T>::mutate(subnet_id, node_account_id, |n: &mut u128| *n = n.saturating_add(amount));
+
+    // -- increase total node delegate stake shares
+    TotalNodeDelegateStakeShares::<T>::mutate(subnet_id, node_account_id, |n: &mut u128| *n = n.saturating_add(shares));
+  }
+
+  pub fn decrease_account_node_delegate_stake_shares(
+    account_id: &T::AccountId,
+    subnet_id: u32,
+    node_account_id: &T::AccountId,
+    amount: u128,
+    shares: u128,
+  ) {
+    // -- decrease account node delegate staking shares balance
+    AccountNodeDelegateStakeShares::<T>::mutate(account_id, (subnet_id, node_account_id.clone()), |n: &mut u128| *n = n.saturating_sub(shares));
+
+    // -- decrease total node delegate stake balance
+    TotalNodeDelegateStakeBalance::<T>::mutate(subnet_id, node_account_id, |n: &mut u128| *n = n.saturating_sub(amount));
+
+    // -- decrease total node delegate stake shares
+    TotalNodeDelegateStakeShares::<T>::mutate(subnet_id, node_account_id, |n: &mut u128| *n = n.saturating_sub(shares));
+  }
+
+  /// Schedule a subnet nodes commission change
+  // Changes take effect after `NodeCommissionChangeDelay` epochs so delegators can react to them
+  pub fn do_set_node_commission(
+    account_id: T::AccountId,
+    subnet_id: u32,
+    commission: u128,
+  ) -> DispatchResult {
+    ensure!(
+      SubnetNodesData::<T>::contains_key(subnet_id, account_id.clone()),
+      Error::<T>::SubnetNodeNotExist
+    );
+
+    ensure!(
+      commission <= Self::PERCENTAGE_FACTOR,
+      Error::<T>::InvalidNodeCommission
+    );
+
+    let epoch: u64 = Self::get_current_block_as_u64() / T::EpochLength::get();
+    let effective_epoch: u64 = epoch.saturating_add(NodeCommissionChangeDelay::<T>::get());
+
+    PendingNodeCommission::<T>::insert(subnet_id, account_id.clone(), (commission, effective_epoch));
+
+    Self::deposit_event(
+      Event::NodeCommissionScheduled {
+        subnet_id: subnet_id,
+        account_id: account_id,
+        commission: commission,
+        epoch: effective_epoch,
+      }
+    );
+
+    Ok(())
+  }
+
+  /// Get a subnet nodes commission on `epoch`, applying its pending commission change if it is due
+  pub fn update_node_commission(
+    subnet_id: u32,
+    account_id: &T::AccountId,
+    epoch: u64,
+  ) -> u128 {
+    if let Some((commission, effective_epoch)) = PendingNodeCommission::<T>::get(subnet_id, account_id) {
+      if effective_epoch <= epoch {
+        NodeCommission::<T>::insert(subnet_id, account_id, commission);
+        PendingNodeCommission::<T>::remove(subnet_id, account_id);
+
+        Self::deposit_event(
+          Event::NodeCommissionUpdated {
+            subnet_id: subnet_id,
+            account_id: account_id.clone(),
+            commission: commission,
+          }
+        );
+
+        return commission
+      }
+    }
+
+    NodeCommission::<T>::get(subnet_id, account_id)
+  }
+
+  /// Split a subnet nodes reward between the subnet node and its delegators
+  // Delegators receive their share of the reward relative to the nodes stake, minus the nodes commission
+  // Delegate stake above `MaxNodeDelegateStakeRatio` of the nodes stake doesn't earn a share, e.g. if the
+  // node was slashed, so delegators can't take over the subnet nodes rewards
+  // Returns the subnet nodes portion, the delegators portion is deposited into the nodes delegate stake balance
+  pub fn distribute_node_delegate_stake_reward(
+    subnet_id: u32,
+    account_id: &T::AccountId,
+    epoch: u64,
+    reward: u128,
+  ) -> u128 {
+    let delegate_stake_balance: u128 = TotalNodeDelegateStakeBalance::<T>::get(subnet_id, account_id);
+    if delegate_stake_balance == 0 || reward == 0 {
+      return reward
+    }
+
+    let commission: u128 = Self::update_node_commission(subnet_id, account_id, epoch);
+
+    let delegators_reward: u128 = Self::get_node_delegators_reward(
+      subnet_id,
+      account_id,
+      AccountSubnetStake::<T>::get(account_id, subnet_id),
+      commission,
+      reward,
+    );
+
+    TotalNodeDelegateStakeBalance::<T>::mutate(subnet_id, account_id, |n: &mut u128| *n = n.saturating_add(delegators_reward));
+
+    reward.saturating_sub(delegators_reward)
+  }
+
+  /// Delegators portion of a subnet nodes reward
+  pub fn get_node_delegators_reward(
+    subnet_id: u32,
+    account_id: &T::AccountId,
+    node_stake_balance: u128,
+    commission: u128,
+    reward: u128,
+  ) -> u128 {
+    let delegate_stake_balance: u128 = TotalNodeDelegateStakeBalance::<T>::get(subnet_id, account_id)
+      .min(Self::percent_mul(node_stake_balance, MaxNodeDelegateStakeRatio::<T>::get()));
+    let delegate_stake_percentage: u128 = Self::percent_div(
+      delegate_stake_balance,
+      node_stake_balance.saturating_add(delegate_stake_balance)
+    );
+
+    let delegators_reward: u128 = Self::percent_mul(reward, delegate_stake_percentage);
+    let commission_reward: u128 = Self::percent_mul(delegators_reward, commission);
+    delegators_reward.saturating_sub(commission_reward)
+  }
+
+  /// Commission of a subnet node on an epoch including a pending commission that takes effect by then
+  pub fn get_node_commission(
+    subnet_id: u32,
+    account_id: &T::AccountId,
+    epoch: u64,
+  ) -> u128 {
+    match PendingNodeCommission::<T>::get(subnet_id, account_id) {
+      Some((commission, effective_epoch)) if effective_epoch <= epoch => commission,
+      _ => NodeCommission::<T>::get(subnet_id, account_id),
+    }
+  }
+
+  /// Maximum delegate stake balance of a subnet node based on its own stake
+  pub fn get_max_node_delegate_stake(subnet_id: u32, account_id: &T::AccountId) -> u128 {
+    Self::percent_mul(
+      AccountSubnetStake::<T>::get(account_id, subnet_id),
+      MaxNodeDelegateStakeRatio::<T>::get()
+    )
+  }
+
+  pub fn get_node_delegate_stake_balance(
+    account_id: &T::AccountId,
+    subnet_id: u32,
+    node_account_id: &T::AccountId,
+  ) -> u128 {
+    Self::convert_to_balance(
+      AccountNodeDelegateStakeShares::<T>::get(account_id, (subnet_id, node_account_id.clone())),
+      TotalNodeDelegateStakeShares::<T>::get(subnet_id, node_account_id),
+      TotalNodeDelegateStakeBalance::<T>::get(subnet_id, node_account_id)
+    )
+  }
+}
@@ -37,9 +37,8 @@ impl<T: Config> Pallet<T> {
     );
   
     // --- Ensure proposer is accountant - Only this category of nodes can propose and vote on proposals
-    let accountant_nodes = SubnetNodesClasses::<T>::get(subnet_id, SubnetNodeClass::Accountant);
     ensure!(
-      accountant_nodes.get(&account_id) != None,
+      Self::is_subnet_node_class(subnet_id, &account_id, SubnetNodeClass::Accountant),
       Error::<T>::NodeAccountantEpochNotReached
     );
 
@@ -60,7 +59,7 @@ impl<T: Config> Pallet<T> {
 
     // --- Ensure the minimum required subnet peers exist
     // --- Only accountants can vote on proposals
-    let accountant_nodes = SubnetNodesClasses::<T>::get(subnet_id, SubnetNodeClass::Accountant);
+    let accountant_nodes = Self::get_classified_subnet_nodes(subnet_id, SubnetNodeClass::Accountant);
     let accountant_nodes_count = accountant_nodes.len();
 
     // There must always be the required minimum subnet peers for each vote
@@ -406,7 +405,7 @@ impl<T: Config> Pallet<T> {
     if yays_len > nays_len {
       // --- Plaintiff wins
       // --- Remove defendant
-      Self::do_remove_subnet_node(block, subnet_id, proposal.defendant);
+      Self::do_remove_subnet_node(block, subnet_id, proposal.defendant, SubnetNodeRemovalReason::DishonestyProposal);
       T::Currency::deposit_creating(&proposal.plaintiff, plaintiff_bond_as_balance.unwrap());
       // --- Distribute bond to voters in consensus
       Self::distribute_bond(
@@ -14,6 +14,7 @@
 // limitations under the License.
 
 use super::*;
+use sp_std::marker::PhantomData;
 
 impl<T: Config> Pallet<T> {
 
@@ -55,4 +56,181 @@ impl<T: Config> Pallet<T> {
 
 		random_number
 	}
-}
\ No newline at end of file
+
+	/// Shuffle `items` in place with a Fisher-Yates shuffle seeded by `seed`
+	// The same seed always returns the same order
+	pub fn shuffle<A>(items: &mut Vec<A>, seed: [u8; 32]) {
+		let len = items.len();
+		if len < 2 {
+			return
+		}
+
+		for i in (1..len).rev() {
+			let j = Self::seeded_random_number_below(seed, i as u32, i as u128 + 1) as usize;
+			items.swap(i, j);
+		}
+	}
+
+	/// Derive a random number from a seed and nonce
+	pub fn seeded_random_number(seed: [u8; 32], nonce: u32) -> u128 {
+		let hash: [u8; 32] = sp_io::hashing::blake2_256(&(seed, nonce).encode());
+		<u128>::decode(&mut hash.as_ref())
+			.expect("32 byte hashes should always be bigger than u128; qed")
+	}
+
+	/// Derive a uniformly distributed random number below `max` from a seed and nonce
+	// Numbers below `2^128 % max` are redrawn so the modulus doesn't favor lower values
+	pub fn seeded_random_number_below(seed: [u8; 32], nonce: u32, max: u128) -> u128 {
+		if max == 0 {
+			return 0
+		}
+
+		let threshold: u128 = (u128::MAX % max + 1) % max;
+
+		let mut random_number: u128 = Self::seeded_random_number(seed, nonce);
+		let mut attempt: u32 = 0;
+		while random_number < threshold {
+			attempt += 1;
+			let hash: [u8; 32] = sp_io::hashing::blake2_256(&(seed, nonce, attempt).encode());
+			random_number = <u128>::decode(&mut hash.as_ref())
+				.expect("32 byte hashes should always be bigger than u128; qed");
+		}
+
+		random_number % max
+	}
+
+	/// Penalize subnet nodes that committed entropy and didn't reveal it in the following epoch
+	// Withholding a reveal is the only way left to bias the election seed, so it costs the
+	// account a penalty
+	pub fn penalize_unrevealed_entropy(subnet_id: u32, epoch: u64) {
+		let unrevealed: Vec<(T::AccountId, u64)> = EntropyCommitments::<T>::iter_prefix(subnet_id)
+			.filter(|(_, (commit_epoch, _))| commit_epoch + 1 < epoch)
+			.map(|(account_id, (commit_epoch, _))| (account_id, commit_epoch))
+			.collect();
+
+		let demotion_penalty_threshold: u32 = NodeClassDemotionPenaltyThreshold::<T>::get();
+
+		for (account_id, commit_epoch) in unrevealed {
+			EntropyCommitments::<T>::remove(subnet_id, account_id.clone());
+
+			let penalty_count: u32 = AccountPenaltyCount::<T>::mutate(&account_id, |n: &mut u32| {
+				*n += 1;
+				*n
+			});
+
+			Self::deposit_event(
+				Event::EntropyUnrevealed {
+					subnet_id: subnet_id,
+					account_id: account_id.clone(),
+					commit_epoch: commit_epoch,
+					penalty_count: penalty_count,
+				}
+			);
+
+			if Self::is_node_class_demotion_threshold(penalty_count, demotion_penalty_threshold) {
+				Self::demote_subnet_node(subnet_id, &account_id, SubnetNodeDemotionReason::AccountPenalties);
+			}
+
+			Self::declassify_ineligible_account(&account_id);
+		}
+	}
+
+	/// Commit to entropy that will be revealed in the following epoch
+	// The commitment is `blake2_256((entropy, account_id))`
+	pub fn do_commit_entropy(
+		account_id: T::AccountId,
+		subnet_id: u32,
+		commitment: [u8; 32],
+	) -> DispatchResult {
+		// --- Only subnet nodes can contribute entropy
+		ensure!(
+			SubnetNodesData::<T>::contains_key(subnet_id, account_id.clone()),
+			Error::<T>::SubnetNodeNotExist
+		);
+
+		// --- An unrevealed commitment can't be replaced, otherwise withholding its reveal would
+		//     escape `penalize_unrevealed_entropy`
+		ensure!(
+			!EntropyCommitments::<T>::contains_key(subnet_id, account_id.clone()),
+			Error::<T>::EntropyCommitmentExists
+		);
+
+		let block: u64 = Self::get_current_block_as_u64();
+		let epoch: u64 = block / T::EpochLength::get();
+
+		EntropyCommitments::<T>::insert(subnet_id, account_id, (epoch, commitment));
+
+		Ok(())
+	}
+
+	/// Reveal entropy committed to in the previous epoch
+	// Revealed entropy is folded into the subnets entropy for the epoch and is used to elect
+	// the validator and accountants of the next epoch
+	pub fn do_reveal_entropy(
+		account_id: T::AccountId,
+		subnet_id: u32,
+		entropy: [u8; 32],
+	) -> DispatchResult {
+		let (commit_epoch, commitment) = match EntropyCommitments::<T>::get(subnet_id, account_id.clone()) {
+			Some(commitment) => commitment,
+			None => return Err(Error::<T>::NoEntropyCommitment.into()),
+		};
+
+		let block: u64 = Self::get_current_block_as_u64();
+		let epoch: u64 = block / T::EpochLength::get();
+
+		// --- Entropy must be revealed in the epoch following the commitment
+		ensure!(
+			epoch == commit_epoch + 1,
+			Error::<T>::InvalidEntropyRevealEpoch
+		);
+
+		ensure!(
+			sp_io::hashing::blake2_256(&(entropy, account_id.clone()).encode()) == commitment,
+			Error::<T>::InvalidEntropyReveal
+		);
+
+		EntropyCommitments::<T>::remove(subnet_id, account_id.clone());
+
+		SubnetEntropy::<T>::mutate(subnet_id, |subnet_entropy: &mut Option<(u64, [u8; 32])>| {
+			let accumulated: [u8; 32] = match subnet_entropy {
+				Some((entropy_epoch, accumulated)) if *entropy_epoch == epoch => *accumulated,
+				_ => [0u8; 32],
+			};
+			*subnet_entropy = Some((epoch, sp_io::hashing::blake2_256(&(accumulated, entropy).encode())));
+		});
+
+		Self::deposit_event(Event::EntropyRevealed { subnet_id: subnet_id, account_id: account_id, epoch: epoch });
+
+		Ok(())
+	}
+}
+
+/// Seeds elections from `T::Randomness`
+pub struct BlockRandomness<T>(PhantomData<T>);
+
+impl<T: Config> ElectionRandomness for BlockRandomness<T> {
+	fn random_seed(subnet_id: u32, epoch: u32) -> [u8; 32] {
+		let (random_seed, _) = T::Randomness::random(&(T::PalletId::get(), subnet_id, epoch).encode());
+		sp_io::hashing::blake2_256(&random_seed.encode())
+	}
+}
+
+/// Seeds elections from the entropy revealed by subnet nodes in the previous epoch mixed with
+/// `T::Randomness`
+// The last node to reveal can still choose to withhold, this is not bias resistant. Withheld
+// reveals are penalized in `penalize_unrevealed_entropy` to make withholding costly
+pub struct CommitRevealRandomness<T>(PhantomData<T>);
+
+impl<T: Config> ElectionRandomness for CommitRevealRandomness<T> {
+	fn random_seed(subnet_id: u32, epoch: u32) -> [u8; 32] {
+		let subnet_entropy: [u8; 32] = match SubnetEntropy::<T>::get(subnet_id) {
+			Some((entropy_epoch, entropy)) if entropy_epoch + 1 == epoch as u64 => entropy,
+			_ => [0u8; 32],
+		};
+
+		sp_io::hashing::blake2_256(
+			&(subnet_entropy, BlockRandomness::<T>::random_seed(subnet_id, epoch)).encode()
+		)
+	}
+}
@@ -17,9 +17,18 @@ use super::*;
 use sp_runtime::Saturating;
 
 impl<T: Config> Pallet<T> {
-  pub fn reward_subnets(block: u64, epoch: u32, epoch_length: u64) {
+  /// Maximum `EpochRewards` records of a subnet pruned on each recorded epoch
+  // Pruning more than one record per epoch catches up after `EpochRewardsRetentionEpochs` is lowered
+  pub const MAX_EPOCH_REWARDS_PRUNE: u32 = 8;
+
+  /// Reward the subnets for an epoch
+  // Returns the count of subnets, the most subnet nodes in a subnet, the count of removed subnet nodes
+  // and the count of removed subnets for weighing the epoch
+  pub fn reward_subnets(block: u64, epoch: u32, epoch_length: u64) -> (u32, u32, u32, u32) {
     let min_attestation_percentage = MinAttestationPercentage::<T>::get();
     let max_absent = MaxSequentialAbsentSubnetNode::<T>::get();
+    let demotion_absent_threshold = NodeClassDemotionAbsentThreshold::<T>::get();
+    let demotion_penalty_threshold = NodeClassDemotionPenaltyThreshold::<T>::get();
 
     let node_removal_threshold = NodeAgainstConsensusRemovalThreshold::<T>::get();
     
@@ -31,15 +40,41 @@ impl<T: Config> Pallet<T> {
     let subnet_reward: u128 = Self::percent_mul(base_subnet_reward, delegate_stake_rewards_percentage);
     let delegate_stake_reward: u128 = base_subnet_reward.saturating_sub(subnet_reward);
 
+    let mut subnets: u32 = 0;
+    let mut max_subnet_nodes: u32 = 0;
+    let mut removed_subnet_nodes: u32 = 0;
+
     for (subnet_id, data) in SubnetsData::<T>::iter() {
+      subnets += 1;
+      max_subnet_nodes = max_subnet_nodes.max(TotalSubnetNodes::<T>::get(subnet_id));
+
       let min_nodes = data.min_nodes;
       // --- We don't check for minimum nodes because nodes cannot validate or attest if they are not met
-      if let Ok(submission) = SubnetRewardsSubmission::<T>::try_get(subnet_id, epoch) {
+      if let Ok(mut submission) = SubnetRewardsSubmission::<T>::try_get(subnet_id, epoch) {
+        // --- Aggregate the validators submissions into the epochs rewards data
+        let (aggregated_data, deviating_validators) = Self::aggregate_submissions(&submission.submissions);
+        submission.sum = aggregated_data.iter().fold(0, |acc, d| acc.saturating_add(d.score));
+        submission.data = aggregated_data;
+        SubnetRewardsSubmission::<T>::insert(subnet_id, epoch, submission.clone());
+
         let data_len = submission.data.len();
-        let submission_nodes_count: u128 = submission.nodes_count as u128;
-        let submission_attestations: u128 = submission.attests.len() as u128;
-        let attestation_percentage: u128 = Self::percent_div(submission_attestations, submission_nodes_count);
-        let validator: T::AccountId = submission.validator;
+        let attestation_percentage: u128 = Self::get_attestation_percentage(subnet_id, &submission);
+
+        // --- Slash chosen validators that didn't submit or whose scores deviate from the aggregated scores
+        let validators: Vec<T::AccountId> = SubnetRewardsValidators::<T>::get(subnet_id, epoch).unwrap_or_default();
+        for validator in validators.iter() {
+          if !submission.submissions.contains_key(validator) {
+            Self::slash_validator(subnet_id, validator.clone(), 0, ValidatorSlashReason::MissedSubmission);
+          } else if deviating_validators.contains(validator) {
+            Self::slash_validator(subnet_id, validator.clone(), 0, ValidatorSlashReason::DeviatingSubmission);
+          }
+        }
+
+        // --- Validators responsible for the aggregated data
+        let honest_validators: Vec<T::AccountId> = submission.submissions.keys()
+          .filter(|account_id| !deviating_validators.contains(account_id))
+          .cloned()
+          .collect();
 
         // --- If validator submitted no data, or less than the minimum required subnet nodes 
         //     we assume the subnet is broken
@@ -47,7 +82,7 @@ impl<T: Config> Pallet<T> {
         if (data_len as u32) < min_nodes {
           // --- Increase the penalty count for the subnet
           // If the subnet is broken, the validator can avoid slashing by submitting consensus with null data
-          SubnetPenaltyCount::<T>::mutate(subnet_id, |n: &mut u32| *n += 1);
+          Self::increase_subnet_penalty_count(subnet_id, SubnetPenaltyReason::MinSubnetNodes);
 
           // --- If subnet nodes aren't in consensus this is true
           // Since we can assume the subnet is in a broken state, we don't slash the validator
@@ -56,21 +91,54 @@ impl<T: Config> Pallet<T> {
           // --- If the subnet nodes are not in agreement with the validator that the model is broken, we
           //     increase the penalty score for the validator
           if attestation_percentage < min_attestation_percentage {
-            AccountPenaltyCount::<T>::mutate(validator, |n: &mut u32| *n += 1);
+            for validator in honest_validators.iter() {
+              let penalty_count: u32 = AccountPenaltyCount::<T>::mutate(validator, |n: &mut u32| {
+                *n += 1;
+                *n
+              });
+
+              Self::deposit_event(
+                Event::ValidatorPenalized {
+                  subnet_id: subnet_id,
+                  account_id: validator.clone(),
+                  attestation_percentage: attestation_percentage,
+                  penalty_count: penalty_count,
+                }
+              );
+
+              if Self::is_node_class_demotion_threshold(penalty_count, demotion_penalty_threshold) {
+                Self::demote_subnet_node(subnet_id, validator, SubnetNodeDemotionReason::AccountPenalties);
+              }
+
+              Self::declassify_ineligible_account(validator);
+            }
           }
           continue;
         }
 
         if min_attestation_percentage > attestation_percentage {
-          // --- Slash validator and increase penalty score
-          Self::slash_validator(subnet_id, validator, attestation_percentage);
+          // --- Slash validators and increase penalty score
+          for validator in honest_validators.iter() {
+            Self::slash_validator(
+              subnet_id,
+              validator.clone(),
+              attestation_percentage,
+              ValidatorSlashReason::InsufficientAttestation,
+            );
+          }
           
           // --- Attestation not successful, move on to next subnet
           continue
         }
 
+        // --- The validator reward is split between the validators responsible for the aggregated data
+        let validator_reward_share: u128 = Self::get_validator_reward(attestation_percentage)
+          .checked_div(honest_validators.len() as u128)
+          .unwrap_or(0);
+
         let sum: u128 = submission.sum;
         let mut rewarded: BTreeSet<T::AccountId> = BTreeSet::new();
+        let mut node_rewards: Vec<NodeEpochReward<T::AccountId>> = Vec::new();
         for subnet_node in SubnetNodesData::<T>::iter_prefix_values(subnet_id) {
           let account_id: T::AccountId = subnet_node.account_id;
           let peer_id: PeerId = subnet_node.peer_id;
@@ -100,9 +168,27 @@ impl<T: Config> Pallet<T> {
               let absent_count = SequentialAbsentSubnetNode::<T>::get(subnet_id, account_id.clone());
               SequentialAbsentSubnetNode::<T>::insert(subnet_id, account_id.clone(), absent_count + 1);
 
+              Self::deposit_event(
+                Event::SubnetNodeAbsent {
+                  subnet_id: subnet_id,
+                  account_id: account_id.clone(),
+                  count: absent_count + 1,
+                  max: max_absent,
+                }
+              );
+
               // --- Ensure maximum sequential removal consensus threshold is reached
               if absent_count + 1 > max_absent {
-                Self::do_remove_subnet_node(block, subnet_id, account_id.clone());
+                Self::do_remove_subnet_node(
+                  block,
+                  subnet_id,
+                  account_id.clone(),
+                  SubnetNodeRemovalReason::MaxSequentialAbsent,
+                );
+                removed_subnet_nodes += 1;
+              } else if Self::is_node_class_demotion_threshold(absent_count + 1, demotion_absent_threshold) {
+                // --- Demote the subnet node if not removed
+                Self::demote_subnet_node(subnet_id, &account_id, SubnetNodeDemotionReason::SequentialAbsent);
               }
             }
             continue;
@@ -112,7 +198,7 @@ impl<T: Config> Pallet<T> {
           // --- Increase account penalty score???
           // We don't penalize accounts for not attesting data in case data is corrupted
           // It is up to subnet nodes to remove them via consensus
-          if !submission.attests.contains(&account_id) {
+          if !submission.attests.contains_key(&account_id) {
             continue
           }
 
@@ -124,8 +210,10 @@ impl<T: Config> Pallet<T> {
           // --- Calculate score percentage of total subnet rewards
           let mut account_reward: u128 = Self::percent_mul(score_percentage, subnet_reward);
 
-          if account_id == validator {
-            account_reward += Self::get_validator_reward(attestation_percentage);    
+          let mut validator_reward: u128 = 0;
+          if honest_validators.contains(&account_id) {
+            validator_reward = validator_reward_share;
+            account_reward += validator_reward;
           }
 
           // --- Skip if no rewards to give
@@ -133,12 +221,27 @@ impl<T: Config> Pallet<T> {
             continue;
           }
 
+          // --- Share the reward with the subnet nodes delegators
+          let node_reward: u128 = Self::distribute_node_delegate_stake_reward(
+            subnet_id,
+            &account_id,
+            epoch as u64,
+            account_reward,
+          );
+
           // --- Increase account stake and emit event
           Self::increase_account_stake(
             &account_id,
             subnet_id, 
-            account_reward,
+            node_reward,
           ); 
+
+          node_rewards.push(NodeEpochReward {
+            account_id: account_id,
+            reward: node_reward,
+            validator_reward: validator_reward,
+            node_delegate_stake_reward: account_reward.saturating_sub(node_reward),
+          });
         }
 
         // --- Portion of delegate staking
@@ -147,20 +250,96 @@ impl<T: Config> Pallet<T> {
           delegate_stake_reward,
         );
 
+        Self::record_epoch_rewards(subnet_id, epoch, node_rewards, delegate_stake_reward);
+
         // --- Increment down subnet penalty score on successful epochs
         SubnetPenaltyCount::<T>::mutate(subnet_id, |n: &mut u32| n.saturating_dec());
-      } else if let Ok(rewards_validator) = SubnetRewardsValidator::<T>::try_get(subnet_id, epoch) {
-        // --- If there is no submission but validator chosen, increase penalty on subnet and validator
+      } else if let Ok(rewards_validators) = SubnetRewardsValidators::<T>::try_get(subnet_id, epoch) {
+        // --- If there is no submission but validators chosen, increase penalty on subnet and validators
         // --- Increase the penalty count for the subnet
         // The next validator on the next epoch can increment the penalty score down
-        SubnetPenaltyCount::<T>::mutate(subnet_id, |n: &mut u32| *n += 1);
+        Self::increase_subnet_penalty_count(subnet_id, SubnetPenaltyReason::MissedSubmission);
 
-        // If validator didn't submit anything, then slash
-        // Even if a subnet is in a broken state, the chosen validator must submit blank data
-        Self::slash_validator(subnet_id, rewards_validator, 0);
+        // If validators didn't submit anything, then slash
+        // Even if a subnet is in a broken state, the chosen validators must submit blank data
+        for rewards_validator in rewards_validators {
+          Self::slash_validator(subnet_id, rewards_validator, 0, ValidatorSlashReason::MissedSubmission);
+        }
       }
+    }
 
-      // TODO: Automatically remove subnet if greater than max penalties count
+    // --- Remove subnets that surpassed the maximum penalties count
+    let max_subnet_penalties: u32 = MaxSubnetPenalties::<T>::get();
+    let removable_subnets: Vec<u32> = SubnetsData::<T>::iter_keys()
+      .filter(|subnet_id| SubnetPenaltyCount::<T>::get(subnet_id) > max_subnet_penalties)
+      .collect();
+
+    let removed_subnets: u32 = removable_subnets.len() as u32;
+    for subnet_id in removable_subnets {
+      Self::do_remove_subnet(block, subnet_id, None, SubnetRemovalReason::MaxPenalties);
     }
+
+    (subnets, max_subnet_nodes, removed_subnet_nodes, removed_subnets)
+  }
+
+  /// Increase a subnets penalty count and emit the reason
+  pub fn increase_subnet_penalty_count(subnet_id: u32, reason: SubnetPenaltyReason) {
+    let penalty_count: u32 = SubnetPenaltyCount::<T>::mutate(subnet_id, |n: &mut u32| {
+      *n += 1;
+      *n
+    });
+
+    Self::deposit_event(
+      Event::SubnetPenalized {
+        subnet_id: subnet_id,
+        reason: reason,
+        penalty_count: penalty_count,
+      }
+    );
+  }
+
+  /// Store the rewards of a subnets epoch and prune the records `EpochRewardsRetentionEpochs` or more behind it
+  pub fn record_epoch_rewards(
+    subnet_id: u32,
+    epoch: u32,
+    nodes: Vec<NodeEpochReward<T::AccountId>>,
+    delegate_stake_reward: u128,
+  ) {
+    let validator_rewards: u128 = nodes.iter().fold(0, |acc, node| acc.saturating_add(node.validator_reward));
+    let total: u128 = nodes.iter().fold(delegate_stake_reward, |acc, node| {
+      acc.saturating_add(node.reward).saturating_add(node.node_delegate_stake_reward)
+    });
+
+    EpochRewards::<T>::insert(
+      subnet_id,
+      epoch,
+      EpochRewardsData {
+        total: total,
+        validator_rewards: validator_rewards,
+        delegate_stake_reward: delegate_stake_reward,
+        nodes: nodes,
+      }
+    );
+
+    // --- Prune from the subnets oldest record, bounded by `MAX_EPOCH_REWARDS_PRUNE`
+    let retention_epochs: u32 = EpochRewardsRetentionEpochs::<T>::get();
+    let mut oldest_epoch: u32 = EpochRewardsOldestEpoch::<T>::get(subnet_id).unwrap_or(epoch);
+    let mut pruned: u32 = 0;
+    while oldest_epoch.saturating_add(retention_epochs) <= epoch && pruned < Self::MAX_EPOCH_REWARDS_PRUNE {
+      EpochRewards::<T>::remove(subnet_id, oldest_epoch);
+      oldest_epoch += 1;
+      pruned += 1;
+    }
+    EpochRewardsOldestEpoch::<T>::insert(subnet_id, oldest_epoch);
+
+    Self::deposit_event(
+      Event::EpochRewarded {
+        subnet_id: subnet_id,
+        epoch: epoch,
+        total: total,
+        validator_rewards: validator_rewards,
+        delegate_stake_reward: delegate_stake_reward,
+      }
+    );
   }
 }
\ No newline at end of file
@@ -133,8 +133,8 @@ impl<T: Config> Pallet<T> {
       SubnetAccount::<T>::insert(subnet_id.clone(), model_accounts);
     }
 
-    // --- 9. We add the balancer to the account_id.  If the above fails we will not credit this account_id.
-    Self::add_balance_to_coldkey_account(&account_id, stake_to_be_removed_as_currency.unwrap());
+    // --- 9. Add the balance to the accounts unbonding ledger to be claimed after `StakeUnbondingEpochs`
+    Self::do_add_unbonding(&account_id, subnet_id, stake_to_be_removed, UnbondingKind::Stake)?;
     
     // Set last block for rate limiting
     Self::set_last_tx_block(&account_id, block);
@@ -252,4 +252,125 @@ impl<T: Config> Pallet<T> {
   > {
     input.try_into().ok()
   }
+
+  /// Move stake between subnets without unbonding
+  // The accounts total stake doesn't change
+  pub fn do_move_stake(
+    account_id: T::AccountId,
+    from_subnet_id: u32,
+    to_subnet_id: u32,
+    stake_to_be_moved: u128,
+  ) -> DispatchResult {
+    let epoch_length: u64 = T::EpochLength::get();
+    let block: u64 = Self::get_current_block_as_u64();
+
+    // --- Ensure consensus isn't being formed or emissions are being generated
+    ensure!(
+      !Self::is_in_consensus_steps(block, epoch_length),
+      Error::<T>::InvalidSubmitEpochLength
+    );
+
+    Self::ensure_can_move_stake(&account_id, from_subnet_id, to_subnet_id, block, epoch_length)?;
+
+    // --- Stake can only be added to subnets the account is a subnet node on
+    ensure!(
+      SubnetNodesData::<T>::contains_key(to_subnet_id, account_id.clone()),
+      Error::<T>::SubnetNodeNotExist
+    );
+
+    ensure!(
+      stake_to_be_moved > 0,
+      Error::<T>::NotEnoughStakeToWithdraw
+    );
+
+    let from_stake_balance: u128 = AccountSubnetStake::<T>::get(&account_id, from_subnet_id);
+
+    ensure!(
+      from_stake_balance >= stake_to_be_moved,
+      Error::<T>::NotEnoughStakeToWithdraw
+    );
+
+    // --- Subnet nodes must keep the minimum required stake balance on the subnet the stake is moved from
+    if SubnetNodesData::<T>::contains_key(from_subnet_id, account_id.clone()) {
+      ensure!(
+        from_stake_balance.saturating_sub(stake_to_be_moved) >= MinStakeBalance::<T>::get(),
+        Error::<T>::MinStakeNotReached
+      );
+    }
+
+    let to_stake_balance: u128 = AccountSubnetStake::<T>::get(&account_id, to_subnet_id);
+
+    ensure!(
+      to_stake_balance.saturating_add(stake_to_be_moved) <= MaxStakeBalance::<T>::get(),
+      Error::<T>::MaxStakeReached
+    );
+
+    // --- Move the subnet stake balances
+    AccountSubnetStake::<T>::insert(&account_id, from_subnet_id, from_stake_balance.saturating_sub(stake_to_be_moved));
+    TotalSubnetStake::<T>::mutate(from_subnet_id, |n: &mut u128| *n = n.saturating_sub(stake_to_be_moved));
+
+    AccountSubnetStake::<T>::insert(&account_id, to_subnet_id, to_stake_balance.saturating_add(stake_to_be_moved));
+    TotalSubnetStake::<T>::mutate(to_subnet_id, |n: &mut u128| *n = n.saturating_add(stake_to_be_moved));
+
+    // --- If subnet stake balance is zero, remove from SubnetAccount
+    if from_stake_balance == stake_to_be_moved {
+      SubnetAccount::<T>::mutate(from_subnet_id, |accounts| {
+        accounts.remove(&account_id);
+      });
+    }
+
+    LastStakeMoveBlock::<T>::insert(&account_id, block);
+
+    Self::deposit_event(
+      Event::StakeMoved {
+        from_subnet_id: from_subnet_id,
+        to_subnet_id: to_subnet_id,
+        account_id: account_id,
+        amount: stake_to_be_moved,
+      }
+    );
+
+    Ok(())
+  }
+
+  /// Ensure an account can move stake or delegate stake between the subnets
+  // Moves have their own cooldown and chosen validators can't move stake out of the subnet
+  // before being rewarded or slashed
+  pub fn ensure_can_move_stake(
+    account_id: &T::AccountId,
+    from_subnet_id: u32,
+    to_subnet_id: u32,
+    block: u64,
+    epoch_length: u64,
+  ) -> DispatchResult {
+    ensure!(
+      from_subnet_id != to_subnet_id,
+      Error::<T>::InvalidStakeMove
+    );
+
+    ensure!(
+      SubnetsData::<T>::contains_key(to_subnet_id),
+      Error::<T>::SubnetNotExist
+    );
+
+    let last_move_block: u64 = LastStakeMoveBlock::<T>::get(account_id);
+    ensure!(
+      last_move_block == 0 || block >= Self::get_eligible_epoch_block(
+        epoch_length,
+        last_move_block,
+        StakeMoveCooldownEpochs::<T>::get()
+      ),
+      Error::<T>::StakeMoveCooldownNotMet
+    );
+
+    let epoch: u32 = (block / epoch_length) as u32;
+    let is_validator: bool = SubnetRewardsValidators::<T>::get(from_subnet_id, epoch)
+      .map_or(false, |validators| validators.contains(account_id));
+    ensure!(
+      !is_validator,
+      Error::<T>::ValidatorStakeMoveNotAllowed
+    );
+
+    Ok(())
+  }
 }
\ No newline at end of file
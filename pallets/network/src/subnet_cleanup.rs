@@ -0,0 +1,282 @@
+// Copyright (C) Hypertensor.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Removes the stored data of subnets removed from the network in bounded batches
+// Stake and delegate stake balances are refunded to `ClaimableBalance`
+
+use super::*;
+use sp_io::MultiRemovalResults;
+
+impl<T: Config> Pallet<T> {
+  /// Clean up the first subnet in `SubnetsToClean` using up to `remaining_weight`
+  // Returns the weight consumed
+  pub fn do_clean_removed_subnets(block: u64, remaining_weight: Weight) -> Weight {
+    let db_weight = T::DbWeight::get();
+
+    // --- Reads `SubnetsToClean` and `MaxSubnetCleanupEntries`
+    let mut weight: Weight = db_weight.reads(2);
+    if remaining_weight.any_lt(weight.saturating_add(Self::subnet_cleanup_base_weight())) {
+      return Weight::zero()
+    }
+
+    let mut subnets_to_clean: Vec<u32> = SubnetsToClean::<T>::get();
+    if subnets_to_clean.is_empty() {
+      return db_weight.reads(1)
+    }
+
+    // --- Get the amount of entries that can be removed within the remaining weight
+    let entry_weight: Weight = Self::subnet_cleanup_entry_weight();
+    let max_entries: u64 = remaining_weight
+      .saturating_sub(weight)
+      .saturating_sub(Self::subnet_cleanup_base_weight())
+      .ref_time()
+      .checked_div(entry_weight.ref_time())
+      .unwrap_or(u64::MAX);
+    let limit: u32 = max_entries.min(MaxSubnetCleanupEntries::<T>::get() as u64) as u32;
+
+    if limit == 0 {
+      return weight
+    }
+
+    let subnet_id: u32 = subnets_to_clean[0];
+    let (complete, removed, clean_weight) = Self::clean_subnet(subnet_id, limit);
+
+    weight = weight
+      .saturating_add(entry_weight.saturating_mul(removed as u64))
+      .saturating_add(clean_weight);
+
+    if complete {
+      subnets_to_clean.remove(0);
+      SubnetsToClean::<T>::put(subnets_to_clean);
+      weight = weight.saturating_add(db_weight.writes(1));
+
+      Self::deposit_event(Event::SubnetCleaned { subnet_id: subnet_id, block: block });
+    }
+
+    weight
+  }
+
+  /// Remove up to `limit` stored entries of a removed subnet
+  // Returns true if the subnet has no remaining data, the amount of entries removed and the
+  // weight of the reads and writes done outside of the removed entries
+  pub fn clean_subnet(subnet_id: u32, limit: u32) -> (bool, u32, Weight) {
+    let db_weight = T::DbWeight::get();
+    let mut weight: Weight = Weight::zero();
+    let mut removed: u32 = 0;
+
+    // --- Refund stake balances to claimable balances
+    // `SubnetAccount` holds every account with a stake balance on the subnet
+    let mut subnet_accounts: BTreeMap<T::AccountId, u64> = SubnetAccount::<T>::get(subnet_id);
+    weight = weight.saturating_add(db_weight.reads_writes(1, 1));
+    let stake_accounts: Vec<T::AccountId> = subnet_accounts.keys().take(limit as usize).cloned().collect();
+    for account_id in stake_accounts {
+      let stake_balance: u128 = AccountSubnetStake::<T>::get(&account_id, subnet_id);
+      if stake_balance > 0 {
+        Self::decrease_account_stake(&account_id, subnet_id, stake_balance);
+        ClaimableBalance::<T>::mutate(&account_id, |n: &mut u128| *n += stake_balance);
+      }
+      AccountSubnetStake::<T>::remove(&account_id, subnet_id);
+      subnet_accounts.remove(&account_id);
+      removed += 1;
+    }
+
+    if !subnet_accounts.is_empty() {
+      SubnetAccount::<T>::insert(subnet_id, subnet_accounts);
+      return (false, removed, weight)
+    }
+    SubnetAccount::<T>::remove(subnet_id);
+
+    // --- Refund delegate stake balances to claimable balances
+    let mut delegate_accounts: BTreeMap<T::AccountId, u64> = SubnetAccountDelegateStake::<T>::get(subnet_id);
+    weight = weight.saturating_add(db_weight.reads_writes(1, 1));
+    let delegate_stake_accounts: Vec<T::AccountId> = delegate_accounts
+      .keys()
+      .take(limit.saturating_sub(removed) as usize)
+      .cloned()
+      .collect();
+    for account_id in delegate_stake_accounts {
+      let shares: u128 = AccountSubnetDelegateStakeShares::<T>::get(&account_id, subnet_id);
+      if shares > 0 {
+        let delegate_stake_balance: u128 = Self::convert_to_balance(
+          shares,
+          TotalSubnetDelegateStakeShares::<T>::get(subnet_id),
+          TotalSubnetDelegateStakeBalance::<T>::get(subnet_id)
+        );
+        Self::decrease_account_delegate_stake_shares(&account_id, subnet_id, delegate_stake_balance, shares);
+        ClaimableBalance::<T>::mutate(&account_id, |n: &mut u128| *n += delegate_stake_balance);
+      }
+      AccountSubnetDelegateStakeShares::<T>::remove(&account_id, subnet_id);
+      delegate_accounts.remove(&account_id);
+      removed += 1;
+    }
+
+    if !delegate_accounts.is_empty() {
+      SubnetAccountDelegateStake::<T>::insert(subnet_id, delegate_accounts);
+      return (false, removed, weight)
+    }
+    SubnetAccountDelegateStake::<T>::remove(subnet_id);
+
+    // --- Refund subnet node delegate stake balances to claimable balances
+    let mut node_delegate_accounts: BTreeMap<(T::AccountId, T::AccountId), u64> = SubnetAccountNodeDelegateStake::<T>::get(subnet_id);
+    weight = weight.saturating_add(db_weight.reads_writes(1, 1));
+    let node_delegate_stake_accounts: Vec<(T::AccountId, T::AccountId)> = node_delegate_accounts
+      .keys()
+      .take(limit.saturating_sub(removed) as usize)
+      .cloned()
+      .collect();
+    for (account_id, node_account_id) in node_delegate_stake_accounts {
+      let shares: u128 = AccountNodeDelegateStakeShares::<T>::get(&account_id, (subnet_id, node_account_id.clone()));
+      if shares > 0 {
+        let delegate_stake_balance: u128 = Self::get_node_delegate_stake_balance(&account_id, subnet_id, &node_account_id);
+        Self::decrease_account_node_delegate_stake_shares(&account_id, subnet_id, &node_account_id, delegate_stake_balance, shares);
+        ClaimableBalance::<T>::mutate(&account_id, |n: &mut u128| *n += delegate_stake_balance);
+      }
+      AccountNodeDelegateStakeShares::<T>::remove(&account_id, (subnet_id, node_account_id.clone()));
+      node_delegate_accounts.remove(&(account_id, node_account_id));
+      removed += 1;
+    }
+
+    if !node_delegate_accounts.is_empty() {
+      SubnetAccountNodeDelegateStake::<T>::insert(subnet_id, node_delegate_accounts);
+      return (false, removed, weight)
+    }
+    SubnetAccountNodeDelegateStake::<T>::remove(subnet_id);
+
+    // --- Any remaining subnet node delegate stake balances go to the stake vault
+    let node_delegate_stake_balances: Vec<(T::AccountId, u128)> = TotalNodeDelegateStakeBalance::<T>::iter_prefix(subnet_id)
+      .take(limit.saturating_sub(removed) as usize)
+      .collect();
+    for (node_account_id, remaining_balance) in node_delegate_stake_balances {
+      StakeVaultBalance::<T>::mutate(|n: &mut u128| *n += remaining_balance);
+      TotalNodeDelegateStakeBalance::<T>::remove(subnet_id, node_account_id);
+      removed += 1;
+    }
+
+    weight = weight.saturating_add(db_weight.reads(1));
+    if TotalNodeDelegateStakeBalance::<T>::iter_key_prefix(subnet_id).next().is_some() {
+      return (false, removed, weight)
+    }
+
+    // --- Any remaining delegate stake balance from rounding or the inflation attack mitigation
+    //     shares goes to the stake vault
+    let remaining_delegate_stake_balance: u128 = TotalSubnetDelegateStakeBalance::<T>::take(subnet_id);
+    StakeVaultBalance::<T>::mutate(|n: &mut u128| *n += remaining_delegate_stake_balance);
+    TotalSubnetDelegateStakeShares::<T>::remove(subnet_id);
+    weight = weight.saturating_add(db_weight.reads_writes(2, 3));
+
+    // --- Remove subnet nodes
+    let subnet_nodes: Vec<SubnetNode<T::AccountId>> = SubnetNodesData::<T>::iter_prefix_values(subnet_id)
+      .take(limit.saturating_sub(removed) as usize)
+      .collect();
+    for subnet_node in subnet_nodes {
+      let account_id: T::AccountId = subnet_node.account_id;
+      SubnetNodesData::<T>::remove(subnet_id, account_id.clone());
+      SubnetNodeAccount::<T>::remove(subnet_id, subnet_node.peer_id);
+      AccountSubnets::<T>::mutate(account_id, |subnet_ids: &mut Vec<u32>| subnet_ids.retain(|&x| x != subnet_id));
+      removed += 1;
+    }
+
+    weight = weight.saturating_add(db_weight.reads(1));
+    if SubnetNodesData::<T>::iter_key_prefix(subnet_id).next().is_some() {
+      return (false, removed, weight)
+    }
+
+    // --- Remove subnet data stored by class, epoch, proposal or account
+    let clear_prefixes: [fn(u32, u32) -> MultiRemovalResults; 17] = [
+      |subnet_id, limit| SubnetNodeAccount::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| SubnetNodesClassification::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| SubnetNodeClassCount::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| SubnetNodeClassPromotions::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| SubnetRewardsSubmission::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| SubnetRewardsValidators::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| CurrentAccountants::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| AccountantData::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| Proposals::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| SequentialAbsentSubnetNode::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| AttestedEpoch::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| EntropyCommitments::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| TotalNodeDelegateStakeShares::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| NodeCommission::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| PendingNodeCommission::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| EpochRewards::<T>::clear_prefix(subnet_id, limit, None),
+      |subnet_id, limit| SubnetNodeClassEpochsOverride::<T>::clear_prefix(subnet_id, limit, None),
+    ];
+
+    for clear_prefix in clear_prefixes {
+      let remaining: u32 = limit.saturating_sub(removed);
+      if remaining == 0 {
+        return (false, removed, weight)
+      }
+      let results: MultiRemovalResults = clear_prefix(subnet_id, remaining);
+      removed += results.unique;
+      // --- Reading the prefix is counted even when it has no entries left
+      weight = weight.saturating_add(db_weight.reads(1));
+      if results.maybe_cursor.is_some() {
+        return (false, removed, weight)
+      }
+    }
+
+    // --- Remove the remaining subnet values
+    TotalSubnetNodes::<T>::remove(subnet_id);
+    TotalSubnetStake::<T>::remove(subnet_id);
+    SubnetPenaltyCount::<T>::remove(subnet_id);
+    SubnetConsensusEpochsErrors::<T>::remove(subnet_id);
+    AccountantDataCount::<T>::remove(subnet_id);
+    CurrentAccountant::<T>::remove(subnet_id);
+    CurrentAccountant2::<T>::remove(subnet_id);
+    SubnetEntropy::<T>::remove(subnet_id);
+    SubnetValidatorSelection::<T>::remove(subnet_id);
+    SubnetNodeClassShiftEpoch::<T>::remove(subnet_id);
+    EpochRewardsOldestEpoch::<T>::remove(subnet_id);
+    weight = weight.saturating_add(db_weight.writes(11));
+
+    (true, removed, weight)
+  }
+
+  /// Upper bound of the weight `clean_subnet` uses outside of the removed entries
+  pub fn subnet_cleanup_base_weight() -> Weight {
+    T::DbWeight::get().reads_writes(24, 17)
+  }
+
+  /// Approximate weight of removing one stored entry of a removed subnet
+  // Refunding a stake balance is the heaviest entry
+  pub fn subnet_cleanup_entry_weight() -> Weight {
+    T::DbWeight::get().reads_writes(6, 7)
+  }
+
+  pub fn do_claim_balance(account_id: T::AccountId) -> DispatchResult {
+    let claimable_balance: u128 = ClaimableBalance::<T>::get(&account_id);
+
+    ensure!(
+      claimable_balance > 0,
+      Error::<T>::NoClaimableBalance
+    );
+
+    // --- Ensure that we can convert this u128 to a balance.
+    let claimable_balance_as_currency = Self::u128_to_balance(claimable_balance);
+    ensure!(
+      claimable_balance_as_currency.is_some(),
+      Error::<T>::CouldNotConvertToBalance
+    );
+
+    ClaimableBalance::<T>::remove(&account_id);
+
+    Self::add_balance_to_coldkey_account(&account_id, claimable_balance_as_currency.unwrap());
+
+    Self::deposit_event(Event::BalanceClaimed(account_id, claimable_balance));
+
+    Ok(())
+  }
+}
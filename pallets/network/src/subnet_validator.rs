@@ -20,7 +20,8 @@ use rand::RngCore;
 
 impl<T: Config> Pallet<T> {
   /// Submit subnet scores per subnet node
-  /// Validator of the epoch receives rewards when attestation passes consensus
+  /// Validators of the epoch receive rewards when attestation passes consensus and their
+  /// scores don't deviate from the aggregated scores
   pub fn do_validate(
     subnet_id: u32, 
     account_id: T::AccountId,
@@ -29,37 +30,29 @@ impl<T: Config> Pallet<T> {
     epoch: u32,
     mut data: Vec<SubnetNodeData>,
   ) -> DispatchResult {
-    // TODO: Track how many nodes leave AFTER the validator submits their consensus data
-    // This allows us to measure the delta between attestation percentage versus validator data
-    // e.g. If there are 1000 validators and 100 leave on the block following, we know the max
-    // attestation percentage will only be 90%. We can alter the attestation percentage up to 100% based on
-    // the amount of nodes that left during the epoch following the validators entry.
-    //
-    // Each attestor will be able to track this to get an accurate measurement of the validators
-    // consensus data before attesting.
-    // e.g. If 1000 validators and 10 leaves, the attestors measurement of the validators consensus data will be
-    // accurate up to a maximum of 99.0%, we can calculate the delta between consensus datas accuracy at the current
-    // space in time, versus the accuracy including the validators that left afterwards. If 1 left, we can increase the
-    // attestation percentage up to 100% based on the amount of nodes that left during the epoch following the validators entry.
-    // We can also track not only the count, but who left for the greatest accuracy
+    // Nodes that leave after the submission don't skew the attestation percentage because it is
+    // measured against the stake of the nodes still submittable when rewards are distributed
+    // See `get_attestation_percentage`
 
     // --- Ensure current subnet validator 
-    let validator = SubnetRewardsValidator::<T>::get(subnet_id, epoch).ok_or(Error::<T>::InvalidValidator)?;
+    let validators: Vec<T::AccountId> = SubnetRewardsValidators::<T>::get(subnet_id, epoch).ok_or(Error::<T>::InvalidValidator)?;
     
     ensure!(
-      account_id == validator,
+      validators.contains(&account_id),
       Error::<T>::InvalidValidator
     );
 
+    let submission: Option<RewardsData<T::AccountId>> = SubnetRewardsSubmission::<T>::get(subnet_id, epoch);
+
     // --- Ensure not submitted already
     ensure!(
-      !SubnetRewardsSubmission::<T>::contains_key(subnet_id, epoch),
+      submission.as_ref().map_or(true, |submission| !submission.submissions.contains_key(&account_id)),
       Error::<T>::SubnetRewardsAlreadySubmitted
     );
 
     // --- Get count of eligible nodes that can be submitted for consensus rewards
     // This is the maximum amount of nodes that can be entered
-    let included_nodes_count = SubnetNodesClasses::<T>::get(subnet_id, SubnetNodeClass::Included).len();
+    let included_nodes_count = Self::get_classified_subnet_nodes_count(subnet_id, SubnetNodeClass::Included);
     // let accountant_nodes_count = SubnetNodesClasses::<T>::get(subnet_id, SubnetNodeClass::Accountant).len();
 
     // --- Ensure data isn't greater than current registered subnet peers
@@ -79,20 +72,35 @@ impl<T: Config> Pallet<T> {
       scores_sum += d.score;
     }
 
-    let submittable_nodes_count = SubnetNodesClasses::<T>::get(subnet_id, SubnetNodeClass::Submittable).len();
-
     // If data.len() is 0 then the validator is deeming the epoch as invalid
 
+    // --- Add to the epochs submission, submissions are aggregated at the end of the epoch
+    if let Some(mut submission) = submission {
+      // --- Validator auto-attests the epoch
+      submission.attests.insert(account_id.clone(), Self::full_attestation());
+      submission.submissions.insert(account_id, data);
+
+      SubnetRewardsSubmission::<T>::insert(subnet_id, epoch, submission);
+
+      return Ok(())
+    }
+
+    let submittable_nodes_count = Self::get_classified_subnet_nodes_count(subnet_id, SubnetNodeClass::Submittable);
+
     // --- Validator auto-attests the epoch
-    let mut attests: BTreeSet<T::AccountId> = BTreeSet::new();
-    attests.insert(account_id.clone());
+    let mut attests: BTreeMap<T::AccountId, AttestationData> = BTreeMap::new();
+    attests.insert(account_id.clone(), Self::full_attestation());
+
+    let mut submissions: BTreeMap<T::AccountId, Vec<SubnetNodeData>> = BTreeMap::new();
+    submissions.insert(account_id.clone(), data.clone());
 
     let rewards_data: RewardsData<T::AccountId> = RewardsData {
       validator: account_id,
       nodes_count: submittable_nodes_count as u32,
       sum: scores_sum,
       attests: attests,
-      data: data
+      data: data,
+      submissions: submissions,
     };
 
     SubnetRewardsSubmission::<T>::insert(subnet_id, epoch, rewards_data);
@@ -108,23 +116,36 @@ impl<T: Config> Pallet<T> {
     block: u64, 
     epoch_length: u64,
     epoch: u32,
+    attestation: AttestationData,
   ) -> DispatchResult {
-    let submittable_nodes = SubnetNodesClasses::<T>::get(subnet_id, SubnetNodeClass::Submittable);
     // --- Ensure epoch eligible for attesting - must be submittable
     ensure!(
-      submittable_nodes.get(&account_id) != None,
+      Self::is_subnet_node_class(subnet_id, &account_id, SubnetNodeClass::Submittable),
       Error::<T>::NodeConsensusSubmitEpochNotReached
     );
 
+    ensure!(
+      attestation.agreement <= Self::PERCENTAGE_FACTOR,
+      Error::<T>::InvalidAttestation
+    );
+
     SubnetRewardsSubmission::<T>::try_mutate_exists(
       subnet_id,
       epoch.clone(),
       |maybe_params| -> DispatchResult {
         let params = maybe_params.as_mut().ok_or(Error::<T>::InvalidSubnetRewardsSubmission)?;
-        let mut attests = &mut params.attests;
-        attests.insert(account_id.clone());
 
-        params.attests = attests.clone();
+        // --- Disagreements are measured against the aggregated data of the submissions so far,
+        //     not only the first validators data
+        let (aggregated_data, _) = Self::aggregate_submissions(&params.submissions);
+
+        ensure!(
+          attestation.disagreements.len() <= aggregated_data.len() &&
+            attestation.disagreements.iter().all(|peer_id| aggregated_data.iter().any(|d| d.peer_id == *peer_id)),
+          Error::<T>::InvalidAttestation
+        );
+
+        params.attests.insert(account_id.clone(), attestation);
         Ok(())
       }
     )?;
@@ -195,35 +216,108 @@ impl<T: Config> Pallet<T> {
   //   Ok(())
   // }
 
-  pub fn choose_validator(
-    block: u64,
+  /// Choose the validators from the shuffled submittable subnet nodes
+  // Returns the chosen validators
+  pub fn choose_validators(
+    shuffled_account_ids: &Vec<T::AccountId>,
+    seed: [u8; 32],
     subnet_id: u32,
     min_subnet_nodes: u32,
+    validators_per_epoch: u32,
     epoch: u32,
-  ) {
-    let node_sets: BTreeMap<T::AccountId, u64> = SubnetNodesClasses::<T>::get(subnet_id, SubnetNodeClass::Submittable);
-
+  ) -> Vec<T::AccountId> {
     // --- Ensure min subnet peers that are submittable are at least the minimum required
     // --- Consensus cannot begin until this minimum is reached
     // --- If not min subnet peers count then accountant isn't needed
-    if (node_sets.len() as u32) < min_subnet_nodes {
-      return
+    if (shuffled_account_ids.len() as u32) < min_subnet_nodes {
+      return Vec::new()
     }
 
-    let account_ids: Vec<T::AccountId> = node_sets.iter()
-      .map(|x| x.0.clone())
-      .collect();
+    // --- The previous epochs validators are only chosen again when there aren't enough other candidates
+    let previous_validators: Vec<T::AccountId> = match epoch {
+      0 => Vec::new(),
+      _ => SubnetRewardsValidators::<T>::get(subnet_id, epoch - 1).unwrap_or_default(),
+    };
 
-    // --- Get eligible validator
-    let validator: Option<T::AccountId> = Self::get_random_account(
-      block,
-      account_ids,
-    );
+    let (mut candidates, mut repeat_candidates): (Vec<&T::AccountId>, Vec<&T::AccountId>) = shuffled_account_ids.iter()
+      .partition(|account_id| !previous_validators.contains(*account_id));
+
+    let mode: ValidatorSelectionMode = Self::get_validator_selection(subnet_id);
+
+    let mut validators: Vec<T::AccountId> = Vec::new();
+
+    while (validators.len() as u32) < validators_per_epoch {
+      if candidates.is_empty() {
+        if repeat_candidates.is_empty() {
+          break
+        }
+        candidates.append(&mut repeat_candidates);
+      }
+
+      let index: usize = match mode {
+        // --- Shuffled accounts are taken in order
+        ValidatorSelectionMode::Uniform => 0,
+        mode => Self::choose_stake_weighted_index(&candidates, seed, validators.len() as u32, subnet_id, mode),
+      };
+
+      validators.push(candidates.remove(index).clone());
+    }
     
-    // --- Insert validator for next epoch
-    if let Some(validator) = validator {
-      SubnetRewardsValidator::<T>::insert(subnet_id, epoch, validator);
+    // --- Insert validators for next epoch
+    if !validators.is_empty() {
+      SubnetRewardsValidators::<T>::insert(subnet_id, epoch, validators.clone());
+    }
+
+    validators
+  }
+
+  /// Choose the index of an account with a chance proportional to its stake weight
+  pub fn choose_stake_weighted_index(
+    candidates: &Vec<&T::AccountId>,
+    seed: [u8; 32],
+    nonce: u32,
+    subnet_id: u32,
+    mode: ValidatorSelectionMode,
+  ) -> usize {
+    let weights: Vec<u128> = candidates.iter()
+      .map(|account_id| {
+        Self::get_validator_stake_weight(AccountSubnetStake::<T>::get(*account_id, subnet_id), mode)
+      })
+      .collect();
+
+    let total_weight: u128 = weights.iter().fold(0, |acc, weight| acc.saturating_add(*weight));
+
+    // --- Fall back to the first shuffled account if no candidate has stake
+    if total_weight == 0 {
+      return 0
     }
+
+    // --- Use nonces the shuffle never uses
+    let mut random_weight: u128 = Self::seeded_random_number_below(seed, u32::MAX - nonce, total_weight);
+
+    for (index, weight) in weights.iter().enumerate() {
+      if random_weight < *weight {
+        return index
+      }
+      random_weight -= weight;
+    }
+
+    0
+  }
+
+  /// Weight of a stake balance towards being chosen as validator
+  pub fn get_validator_stake_weight(stake: u128, mode: ValidatorSelectionMode) -> u128 {
+    match mode {
+      ValidatorSelectionMode::Uniform => 1,
+      ValidatorSelectionMode::Stake => stake,
+      ValidatorSelectionMode::CappedStake => stake.min(ValidatorStakeWeightCap::<T>::get()),
+      ValidatorSelectionMode::SqrtStake => Self::sqrt(stake),
+    }
+  }
+
+  /// Subnets validator selection mode, falls back to the network mode
+  pub fn get_validator_selection(subnet_id: u32) -> ValidatorSelectionMode {
+    SubnetValidatorSelection::<T>::get(subnet_id).unwrap_or(ValidatorSelection::<T>::get())
   }
 
   // pub fn choose_validator_v1(
@@ -257,33 +351,6 @@ impl<T: Config> Pallet<T> {
   //   }
   // }
 
-  // Get random account within subnet
-  fn get_random_account(
-    block: u64,
-    account_ids: Vec<T::AccountId>,
-  ) -> Option<T::AccountId> {
-    // --- Get accountant
-    let accounts_len = account_ids.len();
-    if accounts_len == 0 {
-      return None;
-    }
-      
-    // --- Get random number within the amount of eligible peers
-    // let rand_num = small_rng.next_u32();
-    // let rand_index = rand_num % (account_ids.len() as u32);
-    log::error!("get_random_account accounts_len {:?}", accounts_len);
-    log::error!("get_random_account block {:?}", block);
-
-    let rand_index = Self::get_random_number((accounts_len - 1) as u32, block as u32);
-    log::error!("get_random_account rand_index {:?}", rand_index);
-    log::info!("get_random_account rand_index {:?}", rand_index);
-
-    // --- Choose random accountant from eligible accounts
-    let new_account: &T::AccountId = &account_ids[rand_index as usize];
-        
-    Some(new_account.clone())
-  }
-  
   // Get random account within subnet
   fn get_random_account_v1(
     small_rng: &mut SmallRng,
@@ -307,8 +374,132 @@ impl<T: Config> Pallet<T> {
     Some(new_account.clone())
   }
 
+  /// Aggregate the submissions of the epochs validators into one set of scores
+  // Each peers score is the median of the validators scores after filtering outliers, a peer missing
+  // from a submission counts as a score of 0 for that validator
+  // Peers are only included if more than half of the validators submitted them
+  // Returns the aggregated data and the validators that deviate beyond `MaximumOutlierDeltaPercent`
+  pub fn aggregate_submissions(
+    submissions: &BTreeMap<T::AccountId, Vec<SubnetNodeData>>,
+  ) -> (Vec<SubnetNodeData>, Vec<T::AccountId>) {
+    let submissions_len: usize = submissions.len();
+
+    // --- Scores of each validator by peer
+    let submissions_scores: BTreeMap<&T::AccountId, BTreeMap<PeerId, u128>> = submissions.iter()
+      .map(|(account_id, data)| {
+        (account_id, data.iter().map(|d| (d.peer_id.clone(), d.score)).collect())
+      })
+      .collect();
+
+    let mut peer_scores: BTreeMap<PeerId, Vec<u128>> = BTreeMap::new();
+    for scores in submissions_scores.values() {
+      for (peer_id, score) in scores.iter() {
+        peer_scores.entry(peer_id.clone()).or_insert(Vec::new()).push(*score);
+      }
+    }
+
+    let mut aggregated_data: Vec<SubnetNodeData> = Vec::new();
+    for (peer_id, mut scores) in peer_scores.into_iter() {
+      if scores.len() * 2 <= submissions_len {
+        continue
+      }
+
+      scores.resize(submissions_len, 0);
+
+      aggregated_data.push(SubnetNodeData {
+        peer_id: peer_id,
+        score: Self::get_median(Self::filter_outliers(scores)),
+      });
+    }
+
+    // --- Measure each validators total deviation from the aggregated scores
+    let max_delta: u128 = MaximumOutlierDeltaPercent::<T>::get() as u128 * 100;
+    let aggregated_sum: u128 = aggregated_data.iter().fold(0, |acc, d| acc.saturating_add(d.score));
+
+    let mut deviating_validators: Vec<T::AccountId> = Vec::new();
+    for (account_id, scores) in submissions_scores.iter() {
+      let mut delta: u128 = 0;
+      for d in aggregated_data.iter() {
+        let score: u128 = scores.get(&d.peer_id).copied().unwrap_or(0);
+        delta = delta.saturating_add(score.abs_diff(d.score));
+      }
+
+      // --- Peers that weren't aggregated count fully towards the delta
+      for (peer_id, score) in scores.iter() {
+        if !aggregated_data.iter().any(|d| d.peer_id == *peer_id) {
+          delta = delta.saturating_add(*score);
+        }
+      }
+
+      let deviates: bool = match aggregated_sum {
+        0 => delta > 0,
+        _ => Self::percent_div(delta, aggregated_sum) > max_delta,
+      };
+
+      if deviates {
+        deviating_validators.push((*account_id).clone());
+      }
+    }
+
+    (aggregated_data, deviating_validators)
+  }
+
+  /// Percentage of the subnets stake that attested the rewards data, weighted by each attestors agreement
+  // Only nodes that are submittable at the end of the epoch are counted so nodes leaving
+  // after the submission don't lower the percentage
+  pub fn get_attestation_percentage(
+    subnet_id: u32,
+    submission: &RewardsData<T::AccountId>,
+  ) -> u128 {
+    let submittable_nodes: BTreeMap<T::AccountId, u64> = Self::get_classified_subnet_nodes(subnet_id, SubnetNodeClass::Submittable);
+
+    let mut total_stake: u128 = 0;
+    let mut attested_stake: u128 = 0;
+    for account_id in submittable_nodes.keys() {
+      let stake: u128 = AccountSubnetStake::<T>::get(account_id, subnet_id);
+      total_stake = total_stake.saturating_add(stake);
+
+      if let Some(attestation) = submission.attests.get(account_id) {
+        let agreement: u128 = Self::get_attestation_agreement(attestation, &submission.data);
+        attested_stake = attested_stake.saturating_add(Self::percent_mul(stake, agreement));
+      }
+    }
+
+    Self::percent_div(attested_stake, total_stake)
+  }
+
+  /// Attestors agreement with the rewards data
+  // Disputed peers lower the agreement by their share of the data
+  pub fn get_attestation_agreement(
+    attestation: &AttestationData,
+    data: &Vec<SubnetNodeData>,
+  ) -> u128 {
+    let agreement: u128 = attestation.agreement.min(Self::PERCENTAGE_FACTOR);
+
+    if attestation.disagreements.is_empty() || data.is_empty() {
+      return agreement
+    }
+
+    let data_len: u128 = data.len() as u128;
+    let disputed: u128 = data.iter()
+      .filter(|d| attestation.disagreements.contains(&d.peer_id))
+      .count() as u128;
+
+    let undisputed_percentage: u128 = Self::percent_div(data_len - disputed, data_len);
+
+    Self::percent_mul(agreement, undisputed_percentage)
+  }
+
+  pub fn full_attestation() -> AttestationData {
+    AttestationData {
+      agreement: Self::PERCENTAGE_FACTOR,
+      disagreements: Vec::new(),
+    }
+  }
+
   /// Return the validators reward that submitted data on the previous epoch
   // The attestation percentage must be greater than the MinAttestationPercentage
+  // The reward is split between the validators whose submissions weren't deviating
   pub fn get_validator_reward(
     attestation_percentage: u128,
   ) -> u128 {
@@ -318,7 +509,12 @@ impl<T: Config> Pallet<T> {
     Self::percent_mul(BaseReward::<T>::get(), attestation_percentage)
   }
 
-  pub fn slash_validator(subnet_id: u32, validator: T::AccountId, attestation_percentage: u128) {
+  pub fn slash_validator(
+    subnet_id: u32,
+    validator: T::AccountId,
+    attestation_percentage: u128,
+    reason: ValidatorSlashReason,
+  ) {
     // We never ensure balance is above 0 because any validator chosen must have the target stake
     // balance at a minimum
 
@@ -326,9 +522,15 @@ impl<T: Config> Pallet<T> {
     // This could be greater than the target stake balance
     let account_model_stake: u128 = AccountSubnetStake::<T>::get(validator.clone(), subnet_id);
 
+    // --- Stake that is unbonding is still slashable
+    let unbonding_balance: u128 = Self::get_slashable_unbonding_balance(&validator, subnet_id);
+
     // --- Get slash amount up to max slash
     //
-    let mut slash_amount: u128 = Self::percent_mul(account_model_stake, SlashPercentage::<T>::get());
+    let mut slash_amount: u128 = Self::percent_mul(
+      account_model_stake.saturating_add(unbonding_balance), 
+      SlashPercentage::<T>::get()
+    );
     // --- Update slash amount up to attestation percent
     slash_amount = Self::percent_mul(slash_amount, Self::PERCENTAGE_FACTOR - attestation_percentage);
     // --- Update slash amount up to max slash
@@ -338,13 +540,38 @@ impl<T: Config> Pallet<T> {
     }
     
     // --- Decrease account stake
+    let stake_slash_amount: u128 = slash_amount.min(account_model_stake);
     Self::decrease_account_stake(
       &validator.clone(),
       subnet_id, 
-      slash_amount,
+      stake_slash_amount,
     );
 
+    // --- Slash the remaining amount from unbonding balances
+    Self::slash_unbonding(&validator, subnet_id, slash_amount - stake_slash_amount);
+
     // --- Increase validator penalty count
-    AccountPenaltyCount::<T>::mutate(validator, |n: &mut u32| *n += 1);
+    let penalty_count: u32 = AccountPenaltyCount::<T>::mutate(&validator, |n: &mut u32| {
+      *n += 1;
+      *n
+    });
+
+    Self::deposit_event(
+      Event::ValidatorSlashed {
+        subnet_id: subnet_id,
+        account_id: validator.clone(),
+        amount: slash_amount,
+        attestation_percentage: attestation_percentage,
+        reason: reason,
+        penalty_count: penalty_count,
+      }
+    );
+
+    // --- Demote the validator each time its penalties reach the demotion threshold
+    if Self::is_node_class_demotion_threshold(penalty_count, NodeClassDemotionPenaltyThreshold::<T>::get()) {
+      Self::demote_subnet_node(subnet_id, &validator, SubnetNodeDemotionReason::AccountPenalties);
+    }
+
+    Self::declassify_ineligible_account(&validator);
   }
 }
\ No newline at end of file
@@ -24,7 +24,9 @@ use sp_runtime::traits::Header;
 use log::info;
 use sp_core::{H256, U256};
 // use parity_scale_codec::Decode;
-use frame_support::traits::{OnInitialize, Currency};
+use frame_support::traits::{OnInitialize, OnIdle, Currency};
+use frame_support::weights::Weight;
+use sp_std::collections::btree_map::BTreeMap;
 use crate::{
   Error, SubnetNodeData, SubnetNodeConsensusResults, AccountPenaltyCount, TotalStake, 
   StakeVaultBalance, SubnetPaths, NodeRemovalThreshold,
@@ -38,11 +40,23 @@ use crate::{
   MaxSubnetConsensusUnconfirmedConsecutiveEpochs, SubnetConsensusUnconfirmedConsecutiveEpochsCount,
   VotingPeriod, MinRequiredNodeAccountantEpochs, ProposalsCount, ChallengePeriod, VoteType,
   AccountSubnetDelegateStakeShares,TotalSubnetDelegateStakeShares, TotalSubnetDelegateStakeBalance,
+  SubnetAccountDelegateStake,
   MinRequiredDelegateUnstakeEpochs, TotalSubnets, CurrentAccountant2, AccountantDataCount, PropsType,
-  AccountantDataNodeParams, SubnetRewardsValidator, SubnetRewardsSubmission, BaseSubnetReward, BaseReward,
-  DelegateStakeRewardsPercentage, SubnetNodesClasses, SubnetNodeClass, SubnetNodeClassEpochs,
+  AccountantDataNodeParams, SubnetRewardsValidators, SubnetRewardsSubmission, BaseSubnetReward, BaseReward,
+  DelegateStakeRewardsPercentage, SubnetNodesClassification, SubnetNodeClass, SubnetNodeClassEpochs,
   SubnetPenaltyCount, MaxSequentialAbsentSubnetNode, SequentialAbsentSubnetNode, PreSubnetData,
-  CurrentAccountants, TargetAccountantsLength
+  CurrentAccountants, TargetAccountantsLength, SubnetsData, MaxSubnetPenalties, SubnetActivated,
+  VoteSubnetData, SubnetNodesInitializationEpochs, SubnetRemovalBounty, SubnetsToClean, ClaimableBalance,
+  MaxSubnetCleanupEntries, SubnetRemovalReason, TotalSubnetStake, AccountSubnets, Unbonding,
+  StakeUnbondingEpochs, MaxUnbondings, UnbondingBalance, UnbondingKind, SlashPercentage, MaxSlashAmount, EntropyCommitments, SubnetEntropy,
+  ElectionRandomness, CommitRevealRandomness, ValidatorSelectionMode, ValidatorSelection,
+  SubnetValidatorSelection, ValidatorStakeWeightCap, ValidatorsPerEpoch, AttestationData,
+  AccountNodeDelegateStakeShares, TotalNodeDelegateStakeShares, TotalNodeDelegateStakeBalance,
+  SubnetAccountNodeDelegateStake, NodeCommission, PendingNodeCommission, NodeCommissionChangeDelay,
+  StakeMoveCooldownEpochs, EpochSchedule, EpochRoles,
+  EpochRewards, EpochRewardsOldestEpoch, ValidatorSlashReason, SubnetNodeRemovalReason, SubnetNodeDemotionReason,
+  NodeClassDemotionAbsentThreshold, NodeClassDemotionPenaltyThreshold, SubnetNodeClassCount,
+  SubnetNodeClassPromotions, SubnetNodeClassShiftEpoch, BootstrapSubnetNode, SubnetVote, MaxSubnets,
 };
 use frame_support::weights::Pays;
 use frame_support::BoundedVec;
@@ -98,6 +112,7 @@ fn build_subnet(subnet_path: Vec<u8>) {
   let add_subnet_data = PreSubnetData {
     path: subnet_path.clone().into(),
     memory_mb: 50000,
+    class_epochs: Vec::new(),
   };
   assert_ok!(
     Network::activate_subnet(
@@ -294,10 +309,23 @@ fn post_remove_subnet_node_ensures(n: u32, subnet_id: u32) {
   let model_account = model_accounts.get(&account(n));
   assert_eq!(model_accounts.get(&account(n)), Some(&System::block_number()));
 
-  for class_id in SubnetNodeClass::iter() {
-    let node_sets = SubnetNodesClasses::<Test>::get(subnet_id, class_id);
-    assert_eq!(node_sets.get(&account(n)), None);
-  }
+  assert_eq!(SubnetNodesClassification::<Test>::get(subnet_id, account(n)), None);
+}
+
+fn post_clean_subnet_ensures(n: u32, subnet_id: u32) {
+  let subnet_node_data = SubnetNodesData::<Test>::try_get(subnet_id, account(n));
+  assert_eq!(subnet_node_data, Err(()));
+
+  let subnet_node_account = SubnetNodeAccount::<Test>::try_get(subnet_id, peer(n));
+  assert_eq!(subnet_node_account, Err(()));
+
+  let model_accounts = SubnetAccount::<Test>::get(subnet_id.clone());
+  assert_eq!(model_accounts.get(&account(n)), None);
+
+  assert_eq!(AccountSubnetStake::<Test>::get(account(n), subnet_id.clone()), 0);
+  assert_eq!(AccountSubnets::<Test>::get(account(n)).contains(&subnet_id), false);
+
+  assert_eq!(SubnetNodesClassification::<Test>::get(subnet_id, account(n)), None);
 }
 
 fn post_remove_unstake_ensures(n: u32, subnet_id: u32) {
@@ -422,6 +450,7 @@ fn test_remove_model() {
     let add_subnet_data = PreSubnetData {
       path: subnet_path.clone().into(),
       memory_mb: 50000,
+      class_epochs: Vec::new(),
     };
     assert_ok!(
       Network::deactivate_subnet(
@@ -471,6 +500,82 @@ fn test_remove_model_err() {
   })
 }
 
+#[test]
+fn test_remove_subnet_min_subnet_nodes() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+    let subnet = SubnetsData::<Test>::get(subnet_id).unwrap();
+
+    SubnetActivated::<Test>::insert(
+      subnet_path.clone(), 
+      VoteSubnetData {
+        data: PreSubnetData {
+          path: subnet_path.clone(),
+          memory_mb: 50000,
+          class_epochs: Vec::new(),
+        },
+        active: true,
+      }
+    );
+
+    let epoch_length = EpochLength::get();
+    let initialization_epochs = SubnetNodesInitializationEpochs::<Test>::get();
+    System::set_block_number(subnet.initialized + initialization_epochs * epoch_length);
+
+    let stake_vault_balance = StakeVaultBalance::<Test>::get();
+    let bounty = SubnetRemovalBounty::<Test>::get();
+    let balance = Balances::free_balance(&account(1));
+
+    assert_ok!(
+      Network::remove_subnet(
+        RuntimeOrigin::signed(account(1)),
+        subnet_id,
+      )
+    );
+
+    assert_eq!(SubnetsData::<Test>::contains_key(subnet_id), false);
+    assert_eq!(SubnetPaths::<Test>::contains_key(subnet_path.clone()), false);
+    assert_eq!(StakeVaultBalance::<Test>::get(), stake_vault_balance - bounty);
+    assert_eq!(Balances::free_balance(&account(1)), balance + bounty);
+  })
+}
+
+#[test]
+fn test_remove_subnet_cant_be_removed_err() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    SubnetActivated::<Test>::insert(
+      subnet_path.clone(), 
+      VoteSubnetData {
+        data: PreSubnetData {
+          path: subnet_path.clone(),
+          memory_mb: 50000,
+          class_epochs: Vec::new(),
+        },
+        active: true,
+      }
+    );
+
+    // --- Initialization epochs have not passed
+    assert_err!(
+      Network::remove_subnet(
+        RuntimeOrigin::signed(account(1)),
+        subnet_id,
+      ),
+      Error::<Test>::SubnetCantBeRemoved
+    );
+  })
+}
+
 // #[test]
 // fn test_add_model_max_models_err() {
 //   new_test_ext().execute_with(|| {
@@ -1139,7 +1244,7 @@ fn test_add_subnet_node() {
 
     amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
-    let node_set = SubnetNodesClasses::<Test>::get(subnet_id.clone(), SubnetNodeClass::Idle);
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Idle);
     assert_eq!(node_set.len(), n_peers as usize);
 
     assert_eq!(Network::total_stake(), amount_staked);
@@ -1176,7 +1281,7 @@ fn test_update_subnet_node_peer_id_existing_err() {
       )
     );
 
-    let node_set = SubnetNodesClasses::<Test>::get(subnet_id.clone(), SubnetNodeClass::Idle);
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Idle);
     assert_eq!(node_set.len(), 1);
 
     assert_err!(
@@ -3249,6 +3354,199 @@ fn test_remove_stake() {
   });
 }
 
+#[test]
+fn test_claim_unbonded() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    System::set_block_number(System::block_number() + CONSENSUS_STEPS);
+
+    assert_ok!(
+      Network::add_to_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        amount,
+      ) 
+    );
+
+    let epoch_length = EpochLength::get();
+    let min_required_unstake_epochs = MinRequiredUnstakeEpochs::<Test>::get();
+    System::set_block_number(System::block_number() + epoch_length * min_required_unstake_epochs);
+
+    let balance = Balances::free_balance(&account(0));
+
+    assert_ok!(
+      Network::remove_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        amount,
+      )
+    );
+
+    // --- Balance isn't paid out until it matures
+    assert_eq!(Balances::free_balance(&account(0)), balance);
+    assert_eq!(Network::get_unbonding_balance(&account(0), subnet_id.clone()), amount);
+
+    let epoch = System::block_number() / epoch_length;
+    let stake_unbonding_epochs = StakeUnbondingEpochs::<Test>::get();
+    let unbondings = Unbonding::<Test>::get(account(0), subnet_id.clone());
+    assert_eq!(
+      unbondings.get(&(epoch + stake_unbonding_epochs)),
+      Some(&UnbondingBalance { stake: amount, delegate_stake: 0 })
+    );
+
+    assert_err!(
+      Network::claim_unbonded(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+      ),
+      Error::<Test>::NoUnbondedBalance
+    );
+
+    System::set_block_number(System::block_number() + epoch_length * stake_unbonding_epochs);
+
+    assert_ok!(
+      Network::claim_unbonded(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+      )
+    );
+
+    assert_eq!(Balances::free_balance(&account(0)), balance + amount);
+    assert_eq!(Network::get_unbonding_balance(&account(0), subnet_id.clone()), 0);
+    assert_eq!(Unbonding::<Test>::contains_key(account(0), subnet_id.clone()), false);
+  });
+}
+
+#[test]
+fn test_add_unbonding_max_unbondings_err() {
+  new_test_ext().execute_with(|| {
+    let amount: u128 = 1000000000000000000000;
+    let epoch_length = EpochLength::get();
+
+    MaxUnbondings::<Test>::set(2);
+
+    assert_ok!(Network::do_add_unbonding(&account(0), 1, amount, UnbondingKind::Stake));
+
+    // --- Same epoch unbondings are merged
+    assert_ok!(Network::do_add_unbonding(&account(0), 1, amount, UnbondingKind::Stake));
+    assert_eq!(Unbonding::<Test>::get(account(0), 1).len(), 1);
+
+    System::set_block_number(System::block_number() + epoch_length);
+    assert_ok!(Network::do_add_unbonding(&account(0), 1, amount, UnbondingKind::Stake));
+
+    System::set_block_number(System::block_number() + epoch_length);
+    assert_err!(
+      Network::do_add_unbonding(&account(0), 1, amount, UnbondingKind::Stake),
+      Error::<Test>::MaxUnbondingsReached
+    );
+
+    assert_eq!(Network::get_unbonding_balance(&account(0), 1), amount * 3);
+  });
+}
+
+#[test]
+fn test_slash_validator_unbonding() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    assert_ok!(Network::do_add_unbonding(&account(0), subnet_id.clone(), amount, UnbondingKind::Stake));
+
+    SlashPercentage::<Test>::set(7500);
+    MaxSlashAmount::<Test>::set(amount * 2);
+
+    // --- Events are only deposited after the genesis block
+    System::set_block_number(System::block_number() + 1);
+
+    // --- Slash 75% of stake and unbonding balance with no attestations
+    Network::slash_validator(subnet_id.clone(), account(0), 0, ValidatorSlashReason::MissedSubmission);
+
+    // --- Stake is slashed first with the remainder taken from unbonding
+    assert_eq!(AccountSubnetStake::<Test>::get(account(0), subnet_id.clone()), 0);
+    assert_eq!(Network::get_unbonding_balance(&account(0), subnet_id.clone()), amount / 2);
+
+    System::assert_last_event(
+      RuntimeEvent::Network(
+        crate::Event::ValidatorSlashed {
+          subnet_id: subnet_id,
+          account_id: account(0),
+          amount: amount + amount / 2,
+          attestation_percentage: 0,
+          reason: ValidatorSlashReason::MissedSubmission,
+          penalty_count: 1,
+        }
+      )
+    );
+  });
+}
+
+#[test]
+fn test_slash_validator_unbonding_node_stake_only() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    let epoch_length = EpochLength::get();
+    let stake_unbonding_epochs = StakeUnbondingEpochs::<Test>::get();
+
+    // --- Subnet node stake that matures before the slash
+    assert_ok!(Network::do_add_unbonding(&account(0), subnet_id.clone(), amount, UnbondingKind::Stake));
+
+    System::set_block_number(System::block_number() + epoch_length * stake_unbonding_epochs);
+
+    // --- Delegate stake unbonding alongside the validators subnet node stake
+    assert_ok!(Network::do_add_unbonding(&account(0), subnet_id.clone(), amount, UnbondingKind::DelegateStake));
+
+    SlashPercentage::<Test>::set(10000);
+    MaxSlashAmount::<Test>::set(amount * 4);
+
+    Network::slash_validator(subnet_id.clone(), account(0), 0, ValidatorSlashReason::MissedSubmission);
+
+    // --- Only the subnet node stake is slashed
+    assert_eq!(AccountSubnetStake::<Test>::get(account(0), subnet_id.clone()), 0);
+    assert_eq!(Network::get_unbonding_balance(&account(0), subnet_id.clone()), amount * 2);
+    assert_eq!(Network::get_slashable_unbonding_balance(&account(0), subnet_id.clone()), 0);
+
+    let balance = Balances::free_balance(&account(0));
+
+    assert_ok!(
+      Network::claim_unbonded(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+      )
+    );
+
+    // --- The matured subnet node stake is claimable
+    assert_eq!(Balances::free_balance(&account(0)), balance + amount);
+  });
+}
+
 // #[test]
 // fn test_form_consensus_unconfirm_consensus() {
 //   new_test_ext().execute_with(|| {
@@ -5010,6 +5308,20 @@ fn test_remove_to_delegate_stake() {
       )
     );
 
+    // --- Removed delegate stake is unbonding
+    assert_eq!(Balances::free_balance(&account(0)), balance);
+    assert_eq!(Network::get_unbonding_balance(&account(0), subnet_id.clone()), delegate_balance);
+
+    let stake_unbonding_epochs = StakeUnbondingEpochs::<Test>::get();
+    System::set_block_number(System::block_number() + epoch_length * stake_unbonding_epochs);
+
+    assert_ok!(
+      Network::claim_unbonded(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+      )
+    );
+
     let post_balance = Balances::free_balance(&account(0));
     assert_eq!(post_balance, balance + delegate_balance);
 
@@ -5017,29 +5329,51 @@ fn test_remove_to_delegate_stake() {
 }
 
 #[test]
-fn test_remove_to_delegate_stake_epochs_not_met_err() {
+fn test_add_to_delegate_stake_keeps_subnet_accounts() {
   new_test_ext().execute_with(|| {
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
     build_subnet(subnet_path.clone());
-    let deposit_amount: u128 = 10000000000000000000000;
+
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
     let amount: u128 = 1000000000000000000000;
-    let _ = Balances::deposit_creating(&account(0), deposit_amount);
 
     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
-    let total_model_delegated_stake_shares = TotalSubnetDelegateStakeShares::<Test>::get(subnet_id.clone());
-    let total_model_delegated_stake_balance = TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone());
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
-    let mut delegate_stake_to_be_added_as_shares = Network::convert_to_shares(
-      amount,
-      total_model_delegated_stake_shares,
-      total_model_delegated_stake_balance
+    let _ = Balances::deposit_creating(&account(n_peers), deposit_amount);
+    assert_ok!(
+      Network::add_to_delegate_stake(
+        RuntimeOrigin::signed(account(n_peers)),
+        subnet_id.clone(),
+        amount,
+      ) 
     );
 
-    if total_model_delegated_stake_shares == 0 {
-      delegate_stake_to_be_added_as_shares = delegate_stake_to_be_added_as_shares.saturating_sub(1000);
-    }
+    // --- Delegate stakers are tracked apart from the subnet node stakers
+    let model_accounts = SubnetAccount::<Test>::get(subnet_id.clone());
+    assert_eq!(model_accounts.len(), n_peers as usize);
+    assert_eq!(model_accounts.get(&account(n_peers)), None);
+
+    let model_delegate_accounts = SubnetAccountDelegateStake::<Test>::get(subnet_id.clone());
+    assert_ne!(model_delegate_accounts.get(&account(n_peers)), None);
+  });
+}
+
+#[test]
+fn test_remove_to_delegate_stake_partial_shares() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let _ = Balances::deposit_creating(&account(0), deposit_amount);
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
     assert_ok!(
       Network::add_to_delegate_stake(
@@ -5050,328 +5384,2269 @@ fn test_remove_to_delegate_stake_epochs_not_met_err() {
     );
 
     let delegate_shares = AccountSubnetDelegateStakeShares::<Test>::get(account(0), subnet_id.clone());
-    assert_eq!(delegate_shares, delegate_stake_to_be_added_as_shares);
-    assert_ne!(delegate_shares, 0);
+    let delegate_shares_to_be_removed = delegate_shares / 2;
 
     let total_model_delegated_stake_shares = TotalSubnetDelegateStakeShares::<Test>::get(subnet_id.clone());
     let total_model_delegated_stake_balance = TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone());
 
-    let mut delegate_balance = Network::convert_to_balance(
-      delegate_shares,
+    let delegate_balance_to_be_removed = Network::convert_to_balance(
+      delegate_shares_to_be_removed,
       total_model_delegated_stake_shares,
       total_model_delegated_stake_balance
     );
-    // The first depositor will lose a percentage of their deposit depending on the size
-    // https://docs.openzeppelin.com/contracts/4.x/erc4626#inflation-attack
-    assert_eq!(delegate_balance, delegate_stake_to_be_added_as_shares);
 
-    assert_err!(
+    let epoch_length = EpochLength::get();
+    let min_required_delegate_unstake_epochs = MinRequiredDelegateUnstakeEpochs::<Test>::get();
+
+    System::set_block_number(System::block_number() + epoch_length * min_required_delegate_unstake_epochs);
+
+    assert_ok!(
       Network::remove_delegate_stake(
         RuntimeOrigin::signed(account(0)),
         subnet_id.clone(),
-        delegate_shares,
-      ),
-      Error::<Test>::RequiredDelegateUnstakeEpochsNotMet
+        delegate_shares_to_be_removed,
+      )
+    );
+
+    // --- Only the balance of the removed shares leaves the pool
+    assert_eq!(
+      AccountSubnetDelegateStakeShares::<Test>::get(account(0), subnet_id.clone()),
+      delegate_shares - delegate_shares_to_be_removed
+    );
+    assert_eq!(
+      TotalSubnetDelegateStakeShares::<Test>::get(subnet_id.clone()),
+      total_model_delegated_stake_shares - delegate_shares_to_be_removed
+    );
+    assert_eq!(
+      TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone()),
+      total_model_delegated_stake_balance - delegate_balance_to_be_removed
     );
   });
 }
 
-// #[test]
-// fn test_apr_per_subnet() {
-//   new_test_ext().execute_with(|| {
-//     let max_subnet_nodes = Network::max_subnet_nodes();
-//     let min_stake = MinStakeBalance::<Test>::get();
-//     let epoch_length = EpochLength::get();
-    
-//     let mut previous_apr = f64::MAX;
+#[test]
+fn test_remove_to_delegate_stake_epochs_not_met_err() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let _ = Balances::deposit_creating(&account(0), deposit_amount);
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    let total_model_delegated_stake_shares = TotalSubnetDelegateStakeShares::<Test>::get(subnet_id.clone());
+    let total_model_delegated_stake_balance = TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone());
+
+    let mut delegate_stake_to_be_added_as_shares = Network::convert_to_shares(
+      amount,
+      total_model_delegated_stake_shares,
+      total_model_delegated_stake_balance
+    );
+
+    if total_model_delegated_stake_shares == 0 {
+      delegate_stake_to_be_added_as_shares = delegate_stake_to_be_added_as_shares.saturating_sub(1000);
+    }
+
+    assert_ok!(
+      Network::add_to_delegate_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        amount,
+      ) 
+    );
+
+    let delegate_shares = AccountSubnetDelegateStakeShares::<Test>::get(account(0), subnet_id.clone());
+    assert_eq!(delegate_shares, delegate_stake_to_be_added_as_shares);
+    assert_ne!(delegate_shares, 0);
+
+    let total_model_delegated_stake_shares = TotalSubnetDelegateStakeShares::<Test>::get(subnet_id.clone());
+    let total_model_delegated_stake_balance = TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone());
+
+    let mut delegate_balance = Network::convert_to_balance(
+      delegate_shares,
+      total_model_delegated_stake_shares,
+      total_model_delegated_stake_balance
+    );
+    // The first depositor will lose a percentage of their deposit depending on the size
+    // https://docs.openzeppelin.com/contracts/4.x/erc4626#inflation-attack
+    assert_eq!(delegate_balance, delegate_stake_to_be_added_as_shares);
+
+    assert_err!(
+      Network::remove_delegate_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        delegate_shares,
+      ),
+      Error::<Test>::RequiredDelegateUnstakeEpochsNotMet
+    );
+  });
+}
+
+#[test]
+fn test_add_remove_node_delegate_stake() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    let delegator = account(n_peers + 1);
+    let _ = Balances::deposit_creating(&delegator, deposit_amount);
+
+    assert_ok!(
+      Network::add_to_node_delegate_stake(
+        RuntimeOrigin::signed(delegator.clone()),
+        subnet_id.clone(),
+        account(0),
+        amount,
+      ) 
+    );
+
+    // --- First depositor loses the inflation attack mitigation shares
+    let delegate_shares = AccountNodeDelegateStakeShares::<Test>::get(delegator.clone(), (subnet_id.clone(), account(0)));
+    assert_eq!(delegate_shares, amount - 1000);
+    assert_eq!(TotalNodeDelegateStakeShares::<Test>::get(subnet_id.clone(), account(0)), amount);
+    assert_eq!(TotalNodeDelegateStakeBalance::<Test>::get(subnet_id.clone(), account(0)), amount);
+    assert!(SubnetAccountNodeDelegateStake::<Test>::get(subnet_id.clone()).contains_key(&(delegator.clone(), account(0))));
+
+    let delegate_balance = Network::get_node_delegate_stake_balance(&delegator, subnet_id.clone(), &account(0));
+    assert_eq!(delegate_balance, delegate_shares);
+
+    // --- Delegate stake is only on the subnet node
+    assert_eq!(TotalNodeDelegateStakeBalance::<Test>::get(subnet_id.clone(), account(1)), 0);
+    assert_eq!(TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone()), 0);
+
+    assert_err!(
+      Network::remove_node_delegate_stake(
+        RuntimeOrigin::signed(delegator.clone()),
+        subnet_id.clone(),
+        account(0),
+        delegate_shares,
+      ),
+      Error::<Test>::RequiredDelegateUnstakeEpochsNotMet
+    );
+
+    let epoch_length = EpochLength::get();
+    let min_required_delegate_unstake_epochs = MinRequiredDelegateUnstakeEpochs::<Test>::get();
+
+    System::set_block_number(System::block_number() + epoch_length * min_required_delegate_unstake_epochs);
+
+    assert_ok!(
+      Network::remove_node_delegate_stake(
+        RuntimeOrigin::signed(delegator.clone()),
+        subnet_id.clone(),
+        account(0),
+        delegate_shares,
+      )
+    );
+
+    assert_eq!(AccountNodeDelegateStakeShares::<Test>::get(delegator.clone(), (subnet_id.clone(), account(0))), 0);
+    assert!(!SubnetAccountNodeDelegateStake::<Test>::get(subnet_id.clone()).contains_key(&(delegator.clone(), account(0))));
+
+    // --- Removed delegate stake is unbonding
+    assert_eq!(Network::get_unbonding_balance(&delegator, subnet_id.clone()), delegate_balance);
+  });
+}
+
+#[test]
+fn test_add_to_node_delegate_stake_node_not_exist_err() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let _ = Balances::deposit_creating(&account(0), deposit_amount);
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    assert_err!(
+      Network::add_to_node_delegate_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        account(1),
+        amount,
+      ),
+      Error::<Test>::SubnetNodeNotExist
+    );
+  });
+}
+
+#[test]
+fn test_set_node_commission() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    assert_err!(
+      Network::set_node_commission(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        10001,
+      ),
+      Error::<Test>::InvalidNodeCommission
+    );
+
+    assert_err!(
+      Network::set_node_commission(
+        RuntimeOrigin::signed(account(1)),
+        subnet_id.clone(),
+        1000,
+      ),
+      Error::<Test>::SubnetNodeNotExist
+    );
+
+    assert_ok!(
+      Network::set_node_commission(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        1000,
+      )
+    );
+
+    let epoch_length = EpochLength::get();
+    let epoch = System::block_number() / epoch_length;
+    let effective_epoch = epoch + NodeCommissionChangeDelay::<Test>::get();
+
+    assert_eq!(PendingNodeCommission::<Test>::get(subnet_id.clone(), account(0)), Some((1000, effective_epoch)));
+
+    // --- Commission doesn't change until the delay passes
+    assert_eq!(Network::update_node_commission(subnet_id.clone(), &account(0), effective_epoch - 1), 0);
+    assert_eq!(NodeCommission::<Test>::get(subnet_id.clone(), account(0)), 0);
+
+    assert_eq!(Network::update_node_commission(subnet_id.clone(), &account(0), effective_epoch), 1000);
+    assert_eq!(NodeCommission::<Test>::get(subnet_id.clone(), account(0)), 1000);
+    assert_eq!(PendingNodeCommission::<Test>::get(subnet_id.clone(), account(0)), None);
+  });
+}
+
+#[test]
+fn test_distribute_node_delegate_stake_reward() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    let reward: u128 = 1000000000000000000;
+
+    // --- No delegators, subnet node keeps the reward
+    assert_eq!(Network::distribute_node_delegate_stake_reward(subnet_id.clone(), &account(0), 0, reward), reward);
+
+    let _ = Balances::deposit_creating(&account(1), deposit_amount);
+
+    assert_ok!(
+      Network::add_to_node_delegate_stake(
+        RuntimeOrigin::signed(account(1)),
+        subnet_id.clone(),
+        account(0),
+        amount,
+      ) 
+    );
+
+    NodeCommission::<Test>::insert(subnet_id.clone(), account(0), 1000);
+
+    // --- Delegate stake equals the subnet nodes stake, delegators receive half minus commission
+    let delegators_reward: u128 = Network::percent_mul(reward, 5000);
+    let delegators_reward: u128 = delegators_reward - Network::percent_mul(delegators_reward, 1000);
+
+    let node_reward = Network::distribute_node_delegate_stake_reward(subnet_id.clone(), &account(0), 0, reward);
+    assert_eq!(node_reward, reward - delegators_reward);
+    assert_eq!(TotalNodeDelegateStakeBalance::<Test>::get(subnet_id.clone(), account(0)), amount + delegators_reward);
+  });
+}
+
+#[test]
+fn test_add_to_node_delegate_stake_max_ratio_err() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    let _ = Balances::deposit_creating(&account(1), deposit_amount);
+
+    // --- Delegate stake can't exceed the subnet nodes stake at the default ratio
+    assert_err!(
+      Network::add_to_node_delegate_stake(
+        RuntimeOrigin::signed(account(1)),
+        subnet_id.clone(),
+        account(0),
+        amount + 1,
+      ),
+      Error::<Test>::MaxNodeDelegateStakeRatioReached
+    );
+
+    assert_ok!(
+      Network::add_to_node_delegate_stake(
+        RuntimeOrigin::signed(account(1)),
+        subnet_id.clone(),
+        account(0),
+        amount,
+      ) 
+    );
+
+    assert_err!(
+      Network::add_to_node_delegate_stake(
+        RuntimeOrigin::signed(account(1)),
+        subnet_id.clone(),
+        account(0),
+        1,
+      ),
+      Error::<Test>::MaxNodeDelegateStakeRatioReached
+    );
+  });
+}
+
+#[test]
+fn test_distribute_node_delegate_stake_reward_delegate_stake_exceeds_node_stake() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    // --- Delegate stake is far above the subnet nodes stake, e.g. after the node was slashed
+    let delegate_stake_balance: u128 = amount * 1000;
+    TotalNodeDelegateStakeBalance::<Test>::insert(subnet_id.clone(), account(0), delegate_stake_balance);
+
+    NodeCommission::<Test>::insert(subnet_id.clone(), account(0), 1000);
+
+    let reward: u128 = 1000000000000000000;
+
+    // --- Only delegate stake up to the subnet nodes stake earns, delegators receive half minus commission
+    let delegators_reward: u128 = Network::percent_mul(reward, 5000);
+    let delegators_reward: u128 = delegators_reward - Network::percent_mul(delegators_reward, 1000);
+
+    let node_reward = Network::distribute_node_delegate_stake_reward(subnet_id.clone(), &account(0), 0, reward);
+    assert_eq!(node_reward, reward - delegators_reward);
+    assert!(node_reward > reward / 2);
+    assert_eq!(TotalNodeDelegateStakeBalance::<Test>::get(subnet_id.clone(), account(0)), delegate_stake_balance + delegators_reward);
+  });
+}
+
+#[test]
+fn test_move_stake() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    let subnet_path_2: Vec<u8> = "petals-team/StableBeluga3".into();
+
+    build_subnet(subnet_path.clone());
+    build_subnet(subnet_path_2.clone());
+
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+    let subnet_id_2 = SubnetPaths::<Test>::get(subnet_path_2.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    assert_ok!(
+      Network::add_subnet_node(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id_2.clone(),
+        peer(1),
+        amount,
+      ) 
+    );
+
+    System::set_block_number(System::block_number() + CONSENSUS_STEPS);
+
+    assert_ok!(
+      Network::add_to_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        amount,
+      )
+    );
+
+    assert_err!(
+      Network::move_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        subnet_id.clone(),
+        amount,
+      ),
+      Error::<Test>::InvalidStakeMove
+    );
+
+    // --- Subnet nodes must keep the minimum stake balance
+    assert_err!(
+      Network::move_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        subnet_id_2.clone(),
+        amount * 2,
+      ),
+      Error::<Test>::MinStakeNotReached
+    );
+
+    assert_ok!(
+      Network::move_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        subnet_id_2.clone(),
+        amount,
+      )
+    );
+
+    assert_eq!(Network::account_model_stake(account(0), subnet_id.clone()), amount);
+    assert_eq!(Network::account_model_stake(account(0), subnet_id_2.clone()), amount * 2);
+    assert_eq!(Network::total_model_stake(subnet_id.clone()), amount);
+    assert_eq!(Network::total_model_stake(subnet_id_2.clone()), amount * 2);
+    assert_eq!(Network::total_account_stake(account(0)), amount * 3);
+
+    // --- Moves have a cooldown
+    assert_err!(
+      Network::move_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id_2.clone(),
+        subnet_id.clone(),
+        amount,
+      ),
+      Error::<Test>::StakeMoveCooldownNotMet
+    );
+
+    let epoch_length = EpochLength::get();
+    let cooldown_epochs = StakeMoveCooldownEpochs::<Test>::get();
+    System::set_block_number(System::block_number() + epoch_length * cooldown_epochs);
+
+    assert_ok!(
+      Network::move_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id_2.clone(),
+        subnet_id.clone(),
+        amount,
+      )
+    );
+
+    assert_eq!(Network::account_model_stake(account(0), subnet_id.clone()), amount * 2);
+    assert_eq!(Network::account_model_stake(account(0), subnet_id_2.clone()), amount);
+    assert_eq!(Network::total_account_stake(account(0)), amount * 3);
+  });
+}
+
+#[test]
+fn test_move_stake_validator_err() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    let subnet_path_2: Vec<u8> = "petals-team/StableBeluga3".into();
+
+    build_subnet(subnet_path.clone());
+    build_subnet(subnet_path_2.clone());
+
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+    let subnet_id_2 = SubnetPaths::<Test>::get(subnet_path_2.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    assert_ok!(
+      Network::add_subnet_node(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id_2.clone(),
+        peer(1),
+        amount,
+      ) 
+    );
+
+    System::set_block_number(System::block_number() + CONSENSUS_STEPS);
+
+    assert_ok!(
+      Network::add_to_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        amount,
+      )
+    );
+
+    let epoch_length = EpochLength::get();
+    let epoch = System::block_number() / epoch_length;
+
+    // --- Chosen validators can't move stake before being rewarded or slashed
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
+
+    assert_err!(
+      Network::move_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        subnet_id_2.clone(),
+        amount,
+      ),
+      Error::<Test>::ValidatorStakeMoveNotAllowed
+    );
+  });
+}
+
+#[test]
+fn test_move_delegate_stake() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    let subnet_path_2: Vec<u8> = "petals-team/StableBeluga3".into();
+
+    build_subnet(subnet_path.clone());
+    build_subnet(subnet_path_2.clone());
+
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let _ = Balances::deposit_creating(&account(0), deposit_amount);
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+    let subnet_id_2 = SubnetPaths::<Test>::get(subnet_path_2.clone()).unwrap();
+
+    assert_ok!(
+      Network::add_to_delegate_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        amount,
+      ) 
+    );
+
+    let delegate_shares = AccountSubnetDelegateStakeShares::<Test>::get(account(0), subnet_id.clone());
+    let delegate_balance = Network::convert_to_balance(
+      delegate_shares,
+      TotalSubnetDelegateStakeShares::<Test>::get(subnet_id.clone()),
+      TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone())
+    );
+
+    assert_ok!(
+      Network::move_delegate_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        subnet_id_2.clone(),
+        delegate_shares,
+      )
+    );
+
+    assert_eq!(AccountSubnetDelegateStakeShares::<Test>::get(account(0), subnet_id.clone()), 0);
+    assert!(!SubnetAccountDelegateStake::<Test>::get(subnet_id.clone()).contains_key(&account(0)));
+    assert!(SubnetAccountDelegateStake::<Test>::get(subnet_id_2.clone()).contains_key(&account(0)));
+
+    // --- First depositor on the subnet moved to loses the inflation attack mitigation shares
+    let moved_shares = AccountSubnetDelegateStakeShares::<Test>::get(account(0), subnet_id_2.clone());
+    assert_eq!(moved_shares, delegate_balance - 1000);
+    assert_eq!(TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id_2.clone()), delegate_balance);
+
+    assert_err!(
+      Network::move_delegate_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id_2.clone(),
+        subnet_id.clone(),
+        moved_shares,
+      ),
+      Error::<Test>::StakeMoveCooldownNotMet
+    );
+  });
+}
+
+// #[test]
+// fn test_apr_per_subnet() {
+//   new_test_ext().execute_with(|| {
+//     let max_subnet_nodes = Network::max_subnet_nodes();
+//     let min_stake = MinStakeBalance::<Test>::get();
+//     let epoch_length = EpochLength::get();
+    
+//     let mut previous_apr = f64::MAX;
 //     for n in 0..max_subnet_nodes {
 //       let apr = Network::get_apr(epoch_length, min_stake*(n as u128 + 1));
 //       log::error!("apr          {:?}", apr);
 //       log::error!("previous_apr {:?}", apr);
 
-//       assert!(previous_apr > apr, "previous_apr > apr");
-//       assert_ne!(apr, 0.0, "apr is 0");
-//       previous_apr = apr;
-//     }
-//   });
-// }
+//       assert!(previous_apr > apr, "previous_apr > apr");
+//       assert_ne!(apr, 0.0, "apr is 0");
+//       previous_apr = apr;
+//     }
+//   });
+// }
+
+// #[test]
+// fn test_apr() {
+//   new_test_ext().execute_with(|| {
+//     let max_models = Network::max_models();
+//     let epoch_length = EpochLength::get();
+    
+
+//     let total_staked_balance: u128 = 12000000000000000000000;
+
+//     let mut previous_apr = f64::MAX;
+//     for n in 0..max_models {
+//       TotalSubnets::<Test>::set(n+1);
+//       let apr = Network::get_apr(epoch_length, total_staked_balance*(n as u128 + 1));
+//       log::error!("apr          {:?}", apr);
+//       log::error!("previous_apr {:?}", apr);
+
+//       assert!(previous_apr > apr, "previous_apr > apr");
+//       assert_ne!(apr, 0.0, "apr is 0");
+//       previous_apr = apr;
+//     }
+//   });
+// }
+
+// #[test]
+// fn test_emissions() {
+//   new_test_ext().execute_with(|| {
+//     let max_models = Network::max_models();
+//     let epoch_length = EpochLength::get();
+//     let total_staked_balance: u128 = 12000000000000000000000;
+//     let mut previous_emissions = u128::MAX;
+//     for n in 0..max_models {
+//       TotalSubnets::<Test>::set(n+1);
+//       let emissions = Network::get_epoch_emissions(epoch_length, total_staked_balance*(n as u128 + 1));
+//     }
+//   });
+// }
+
+// #[test]
+// fn test_submit_accountant_data() {
+//   new_test_ext().execute_with(|| {
+//     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+//     let n_peers: u32 = Network::max_subnet_nodes();
+//     build_subnet(subnet_path.clone());
+
+//     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+//     let deposit_amount: u128 = 1000000000000000000000000;
+//     let amount: u128 = 1000000000000000000000;
+//     let mut amount_staked: u128 = 0;
+
+//     System::set_block_number(System::block_number() + CONSENSUS_STEPS);
+
+//     let amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, amount + deposit_amount, amount);
+
+//     make_subnet_node_dishonesty_consensus_proposable();
+
+//     Network::check_and_choose_accountant();
+
+//     let mut current_accountants = CurrentAccountant2::<Test>::get(subnet_id.clone());
+//     let accountant = current_accountants.first_key_value().unwrap();
+//     let accountant_data_count = AccountantDataCount::<Test>::get(subnet_id.clone());
+
+//     let mut accountant_data_peers: Vec<AccountantDataNodeParams> = Vec::new();
+
+//     assert_err!(
+//       Network::submit_accountant_data(
+//         RuntimeOrigin::signed(accountant.0.clone()), 
+//         subnet_id.clone(),
+//         Vec::new(),
+//       ),
+//       Error::<Test>::InvalidAccountantData
+//     );
+
+//     let mut fake_data: Vec<AccountantDataNodeParams> = Vec::new();
+
+//     for n in 0..255 {
+//       fake_data.push(
+//         AccountantDataNodeParams {
+//           peer_id: peer(n),
+//           data: BoundedVec::new()
+//         }
+//       )  
+//     }
+
+//     assert_err!(
+//       Network::submit_accountant_data(
+//         RuntimeOrigin::signed(accountant.0.clone()), 
+//         subnet_id.clone(),
+//         fake_data,
+//       ),
+//       Error::<Test>::InvalidAccountantData
+//     );
+
+//     for n in 0..n_peers {
+//       accountant_data_peers.push(
+//         AccountantDataNodeParams {
+//           peer_id: peer(n),
+//           data: BoundedVec::new()
+//         }
+//       )  
+//     }
+
+//     assert_ok!(
+//       Network::submit_accountant_data(
+//         RuntimeOrigin::signed(accountant.0.clone()), 
+//         subnet_id.clone(),
+//         accountant_data_peers.clone(),
+//       )  
+//     );
+
+//     assert_err!(
+//       Network::submit_accountant_data(
+//         RuntimeOrigin::signed(accountant.0.clone()), 
+//         subnet_id.clone(),
+//         accountant_data_peers.clone(),
+//       ),
+//       Error::<Test>::NotAccountant
+//     );
+
+//   });
+// }
+
+#[test]
+fn test_choose_accountants() {
+  new_test_ext().execute_with(|| {
+    
+    setup_blocks(38);
+
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    let n_peers: u32 = Network::max_subnet_nodes();
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let mut amount_staked: u128 = 0;
+
+    let amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, amount + deposit_amount, amount);
+    make_model_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+
+    Network::shift_node_classes(System::block_number(), epoch_length);
+
+    let epoch = System::block_number() / epoch_length;
+
+    let validators = SubnetRewardsValidators::<Test>::get(subnet_id.clone(), epoch as u32);
+    assert!(validators == None, "Validators should be None");
+
+    let accountants = CurrentAccountants::<Test>::get(subnet_id.clone(), epoch as u32);
+    assert!(accountants == None, "Accountant should be None");
+
+    Network::do_choose_validator_and_accountants(System::block_number(), epoch as u32, epoch_length);
+
+    let validators = SubnetRewardsValidators::<Test>::get(subnet_id.clone(), epoch as u32);
+    assert!(validators != None, "Validators is None");
+    let validator = validators.unwrap().first().cloned();
+
+    let accountants = CurrentAccountants::<Test>::get(subnet_id.clone(), epoch as u32);
+    assert!(accountants != None, "Accountants is None");
+    assert_eq!(accountants.unwrap().len() as u32, TargetAccountantsLength::<Test>::get());
+
+
+    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(validator.unwrap()), 
+        subnet_id.clone(),
+        subnet_node_data_vec.clone()
+      )
+    );
+
+  });
+}
+
+fn setup_blocks(blocks: u64) {
+  let mut parent_hash = System::parent_hash();
+
+  for i in 1..(blocks + 1) {
+    System::reset_events();
+    System::initialize(&i, &parent_hash, &Default::default());
+    InsecureRandomnessCollectiveFlip::on_initialize(i);
+
+    let header = System::finalize();
+    parent_hash = header.hash();
+    System::set_block_number(*header.number());
+  }
+}
+
+#[test]
+fn test_randomness() {
+  new_test_ext().execute_with(|| {
+    setup_blocks(38);
+    let gen_rand_num = Network::generate_random_number(1);
+    log::error!("test_randomness gen_rand_num {:?}", gen_rand_num);
+
+    let rand_num = Network::get_random_number(96, 0);
+    log::error!("test_randomness rand_num {:?}", rand_num);
+
+  });
+}
+
+#[test]
+fn test_shuffle_deterministic() {
+  new_test_ext().execute_with(|| {
+    let items: Vec<u32> = (0..50).collect();
+    let seed: [u8; 32] = [1u8; 32];
+
+    let mut shuffled: Vec<u32> = items.clone();
+    Network::shuffle(&mut shuffled, seed);
+
+    let mut shuffled_again: Vec<u32> = items.clone();
+    Network::shuffle(&mut shuffled_again, seed);
+
+    // --- Same seed returns the same order
+    assert_eq!(shuffled, shuffled_again);
+    assert_ne!(shuffled, items);
+
+    // --- Shuffling is a permutation
+    shuffled.sort();
+    assert_eq!(shuffled, items);
+  });
+}
+
+#[test]
+fn test_seeded_random_number_below() {
+  new_test_ext().execute_with(|| {
+    let seed: [u8; 32] = [1u8; 32];
+
+    assert_eq!(Network::seeded_random_number_below(seed, 0, 0), 0);
+    assert_eq!(Network::seeded_random_number_below(seed, 0, 1), 0);
+
+    // --- Almost half of the u128 range is redrawn for this max
+    let max: u128 = (1u128 << 127) + 1;
+    for nonce in 0..32 {
+      let random_number = Network::seeded_random_number_below(seed, nonce, max);
+      assert!(random_number < max);
+      assert_eq!(random_number, Network::seeded_random_number_below(seed, nonce, max));
+    }
+  });
+}
+
+#[test]
+fn test_choose_validator_and_accountants_unique() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    let n_peers: u32 = Network::max_subnet_nodes();
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, amount + deposit_amount, amount);
+    make_model_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+
+    Network::shift_node_classes(System::block_number(), epoch_length);
+
+    let epoch = System::block_number() / epoch_length;
+
+    ElectionSeed::set([7u8; 32]);
+
+    Network::do_choose_validator_and_accountants(System::block_number(), epoch as u32, epoch_length);
+
+    let validators = SubnetRewardsValidators::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+    let accountants = CurrentAccountants::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+
+    // --- The validator is never one of the accountants
+    assert_eq!(validators.len(), 1);
+    assert!(!accountants.contains_key(&validators[0]));
+    assert_eq!(accountants.len() as u32, TargetAccountantsLength::<Test>::get());
+
+    // --- The same seed elects the same nodes
+    SubnetRewardsValidators::<Test>::remove(subnet_id.clone(), epoch as u32);
+    CurrentAccountants::<Test>::remove(subnet_id.clone(), epoch as u32);
+
+    Network::do_choose_validator_and_accountants(System::block_number(), epoch as u32, epoch_length);
+
+    assert_eq!(SubnetRewardsValidators::<Test>::get(subnet_id.clone(), epoch as u32), Some(validators));
+    assert_eq!(CurrentAccountants::<Test>::get(subnet_id.clone(), epoch as u32), Some(accountants));
+  });
+}
+
+#[test]
+fn test_choose_multiple_validators() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    let n_peers: u32 = Network::max_subnet_nodes();
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, amount + deposit_amount, amount);
+    make_model_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+
+    Network::shift_node_classes(System::block_number(), epoch_length);
+
+    assert_ok!(Network::set_validators_per_epoch(3));
+    assert_eq!(ValidatorsPerEpoch::<Test>::get(), 3);
+
+    let epoch = System::block_number() / epoch_length;
+
+    Network::do_choose_validator_and_accountants(System::block_number(), epoch as u32, epoch_length);
+
+    let mut validators = SubnetRewardsValidators::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+    let accountants = CurrentAccountants::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+
+    for validator in validators.iter() {
+      assert!(!accountants.contains_key(validator));
+    }
+
+    validators.sort();
+    validators.dedup();
+    assert_eq!(validators.len(), 3);
+
+    assert_err!(
+      Network::set_validators_per_epoch(0),
+      Error::<Test>::InvalidValidatorsPerEpoch
+    );
+  });
+}
+
+#[test]
+fn test_choose_validator_stake_weighted() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    let n_peers: u32 = Network::max_subnet_nodes();
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, amount + deposit_amount, amount);
+    make_model_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+
+    Network::shift_node_classes(System::block_number(), epoch_length);
+
+    assert_ok!(Network::set_subnet_validator_selection(subnet_id.clone(), Some(ValidatorSelectionMode::Stake)));
+
+    // --- Only account(0) has stake
+    for n in 1..n_peers {
+      AccountSubnetStake::<Test>::insert(account(n), subnet_id.clone(), 0);
+    }
+
+    let epoch = System::block_number() / epoch_length;
+
+    Network::do_choose_validator_and_accountants(System::block_number(), epoch as u32, epoch_length);
+
+    assert_eq!(SubnetRewardsValidators::<Test>::get(subnet_id.clone(), epoch as u32), Some(vec![account(0)]));
+
+    // --- The previous epochs validator can't be chosen twice in a row
+    System::set_block_number(System::block_number() + epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    Network::do_choose_validator_and_accountants(System::block_number(), epoch as u32, epoch_length);
+
+    let validators = SubnetRewardsValidators::<Test>::get(subnet_id.clone(), epoch as u32);
+    assert!(validators != None, "Validators is None");
+    assert!(!validators.unwrap().contains(&account(0)));
+  });
+}
+
+#[test]
+fn test_get_validator_stake_weight() {
+  new_test_ext().execute_with(|| {
+    let stake: u128 = 1000000000000000000000;
+
+    assert_eq!(Network::get_validator_stake_weight(stake, ValidatorSelectionMode::Uniform), 1);
+    assert_eq!(Network::get_validator_stake_weight(stake, ValidatorSelectionMode::Stake), stake);
+    assert_eq!(Network::get_validator_stake_weight(stake, ValidatorSelectionMode::SqrtStake), 31622776601);
+
+    ValidatorStakeWeightCap::<Test>::set(stake / 2);
+    assert_eq!(Network::get_validator_stake_weight(stake, ValidatorSelectionMode::CappedStake), stake / 2);
+
+    assert_eq!(Network::sqrt(0), 0);
+    assert_eq!(Network::sqrt(1), 1);
+    assert_eq!(Network::sqrt(15), 3);
+    assert_eq!(Network::sqrt(16), 4);
+  });
+}
+
+#[test]
+fn test_get_validator_selection() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    assert_eq!(Network::get_validator_selection(subnet_id.clone()), ValidatorSelectionMode::Uniform);
+
+    assert_ok!(Network::set_validator_selection(ValidatorSelectionMode::SqrtStake));
+    assert_eq!(Network::get_validator_selection(subnet_id.clone()), ValidatorSelectionMode::SqrtStake);
+
+    assert_ok!(Network::set_subnet_validator_selection(subnet_id.clone(), Some(ValidatorSelectionMode::CappedStake)));
+    assert_eq!(Network::get_validator_selection(subnet_id.clone()), ValidatorSelectionMode::CappedStake);
+
+    assert_ok!(Network::set_subnet_validator_selection(subnet_id.clone(), None));
+    assert_eq!(Network::get_validator_selection(subnet_id.clone()), ValidatorSelectionMode::SqrtStake);
+
+    assert_err!(
+      Network::set_subnet_validator_selection(subnet_id.clone() + 1, Some(ValidatorSelectionMode::Stake)),
+      Error::<Test>::SubnetNotExist
+    );
+  });
+}
+
+#[test]
+fn test_commit_reveal_entropy() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    let entropy: [u8; 32] = [3u8; 32];
+    let commitment: [u8; 32] = sp_io::hashing::blake2_256(&(entropy, account(0)).encode());
+
+    assert_err!(
+      Network::commit_entropy(
+        RuntimeOrigin::signed(account(1)),
+        subnet_id.clone(),
+        commitment,
+      ),
+      Error::<Test>::SubnetNodeNotExist
+    );
+
+    assert_err!(
+      Network::reveal_entropy(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        entropy,
+      ),
+      Error::<Test>::NoEntropyCommitment
+    );
+
+    assert_ok!(
+      Network::commit_entropy(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        commitment,
+      )
+    );
+
+    // --- Can't reveal in the commitment epoch
+    assert_err!(
+      Network::reveal_entropy(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        entropy,
+      ),
+      Error::<Test>::InvalidEntropyRevealEpoch
+    );
+
+    let epoch_length = EpochLength::get();
+    System::set_block_number(System::block_number() + epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    assert_err!(
+      Network::reveal_entropy(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        [4u8; 32],
+      ),
+      Error::<Test>::InvalidEntropyReveal
+    );
+
+    let seed_before_reveal = CommitRevealRandomness::<Test>::random_seed(subnet_id.clone(), (epoch + 1) as u32);
+
+    assert_ok!(
+      Network::reveal_entropy(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        entropy,
+      )
+    );
+
+    assert_eq!(EntropyCommitments::<Test>::get(subnet_id.clone(), account(0)), None);
+    assert_eq!(
+      SubnetEntropy::<Test>::get(subnet_id.clone()),
+      Some((epoch, sp_io::hashing::blake2_256(&([0u8; 32], entropy).encode())))
+    );
+
+    // --- Revealed entropy seeds the next epochs election
+    assert_ne!(
+      CommitRevealRandomness::<Test>::random_seed(subnet_id.clone(), (epoch + 1) as u32),
+      seed_before_reveal
+    );
+  });
+}
+
+#[test]
+fn test_commit_entropy_unrevealed_commitment_exists_err() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    let epoch_length = EpochLength::get();
+    System::set_block_number(System::block_number() + 1);
+    let commit_epoch = System::block_number() / epoch_length;
+
+    let commitment: [u8; 32] = sp_io::hashing::blake2_256(&([3u8; 32], account(0)).encode());
+
+    assert_ok!(
+      Network::commit_entropy(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        commitment,
+      )
+    );
+
+    // --- Re-committing in the reveal epoch would move the commitment out of reach of the penalty
+    System::set_block_number(System::block_number() + epoch_length);
+
+    assert_err!(
+      Network::commit_entropy(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        sp_io::hashing::blake2_256(&([4u8; 32], account(0)).encode()),
+      ),
+      Error::<Test>::EntropyCommitmentExists
+    );
+
+    assert_eq!(
+      EntropyCommitments::<Test>::get(subnet_id.clone(), account(0)),
+      Some((commit_epoch, commitment))
+    );
+
+    // --- The withheld commitment is still penalized
+    System::set_block_number(System::block_number() + epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    Network::do_choose_validator_and_accountants(System::block_number(), epoch as u32, epoch_length);
+
+    assert_eq!(EntropyCommitments::<Test>::get(subnet_id.clone(), account(0)), None);
+    assert_eq!(AccountPenaltyCount::<Test>::get(account(0)), 1);
+
+    // --- Once the commitment is revealed or penalized a new one can be made
+    assert_ok!(
+      Network::commit_entropy(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        sp_io::hashing::blake2_256(&([4u8; 32], account(0)).encode()),
+      )
+    );
+  });
+}
+
+#[test]
+fn test_penalize_unrevealed_entropy() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    build_subnet_nodes(subnet_id.clone(), 0, 2, deposit_amount, amount);
+
+    let epoch_length = EpochLength::get();
+
+    // --- Events are only deposited after the genesis block
+    System::set_block_number(System::block_number() + 1);
+    let commit_epoch = System::block_number() / epoch_length;
+
+    assert_ok!(
+      Network::commit_entropy(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        sp_io::hashing::blake2_256(&([3u8; 32], account(0)).encode()),
+      )
+    );
+
+    // --- account(1) commits an epoch later and can still reveal
+    System::set_block_number(System::block_number() + epoch_length);
+
+    assert_ok!(
+      Network::commit_entropy(
+        RuntimeOrigin::signed(account(1)),
+        subnet_id.clone(),
+        sp_io::hashing::blake2_256(&([4u8; 32], account(1)).encode()),
+      )
+    );
+
+    System::set_block_number(System::block_number() + epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    Network::do_choose_validator_and_accountants(System::block_number(), epoch as u32, epoch_length);
+
+    // --- Only the commitment past its reveal epoch is penalized
+    assert_eq!(EntropyCommitments::<Test>::get(subnet_id.clone(), account(0)), None);
+    assert_eq!(AccountPenaltyCount::<Test>::get(account(0)), 1);
+
+    assert_ne!(EntropyCommitments::<Test>::get(subnet_id.clone(), account(1)), None);
+    assert_eq!(AccountPenaltyCount::<Test>::get(account(1)), 0);
+
+    System::assert_has_event(
+      RuntimeEvent::Network(
+        crate::Event::EntropyUnrevealed {
+          subnet_id: subnet_id,
+          account_id: account(0),
+          commit_epoch: commit_epoch,
+          penalty_count: 1,
+        }
+      )
+    );
+  });
+}
+
+// #[test]
+// fn test_propose_dishonesty() {
+//   new_test_ext().execute_with(|| {
+//     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+//     let n_peers: u32 = Network::max_subnet_nodes();
+//     build_subnet(subnet_path.clone());
+
+//     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+//     let deposit_amount: u128 = 1000000000000000000000000;
+//     let amount: u128 = 1000000000000000000000;
+//     let mut amount_staked: u128 = 0;
+
+//     System::set_block_number(System::block_number() + CONSENSUS_STEPS);
+
+//     let amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, amount + deposit_amount, amount);
+
+//     make_subnet_node_dishonesty_consensus_proposable();
+
+//     Network::check_and_choose_accountant();
+
+//     let mut current_accountants = CurrentAccountant2::<Test>::get(subnet_id.clone());
+//     let accountant = current_accountants.first_key_value().unwrap();
+//     let accountant_data_count = AccountantDataCount::<Test>::get(subnet_id.clone());
+
+//     let mut accountant_data_peers: Vec<AccountantDataNodeParams> = Vec::new();
+
+//     for n in 0..n_peers {
+//       accountant_data_peers.push(
+//         AccountantDataNodeParams {
+//           peer_id: peer(n),
+//           data: BoundedVec::new()
+//         }
+//       )  
+//     }
+
+//     assert_ok!(
+//       Network::submit_accountant_data(
+//         RuntimeOrigin::signed(accountant.0.clone()), 
+//         subnet_id.clone(),
+//         accountant_data_peers.clone(),
+//       )  
+//     );
+
+//     let non_empty_data: Vec<u8> = "__data__".into();
+
+//     // propose_dishonesty
+//     assert_ok!(
+//       Network::propose_dishonesty(
+//         RuntimeOrigin::signed(account(1)), 
+//         subnet_id.clone(),
+//         peer(0),
+//         PropsType::DishonestAccountant,
+//         non_empty_data,
+//         Some(1),
+//       )
+//     );
+//   });
+// }
+
+
+
+
+
+
+
+
+
+
+
+#[test]
+fn test_validate() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+    assert_eq!(Network::total_models(), 1);
+
+    make_model_submittable();
+
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let mut amount_staked: u128 = 0;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    // System::set_block_number(System::block_number() + epoch_length);
+
+    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    make_subnet_node_consensus_data_submittable();
+
+    let epoch_length = EpochLength::get();
+
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+
+    Network::shift_node_classes(System::block_number(), epoch_length);
+
+    let epoch = System::block_number() / epoch_length;
+
+    log::error!("epoch is -> {:?}", epoch);
+
+    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+
+    // --- Insert validator
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
+
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        subnet_node_data_vec.clone()
+      )
+    );
+
+    let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+
+    assert_eq!(submission.validator, account(0), "Err: validator");
+    assert_eq!(submission.data.len(), subnet_node_data_vec.len(), "Err: data len");
+    assert_eq!(submission.sum, DEFAULT_SCORE * n_peers as u128, "Err: sum");
+    assert_eq!(submission.attests.len(), 1, "Err: attests");
+    assert_eq!(submission.nodes_count, n_peers, "Err: nodes_count");
+
+    assert_err!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        subnet_node_data_vec.clone()
+      ),
+      Error::<Test>::SubnetRewardsAlreadySubmitted
+    );
+  });
+}
+
+#[test]
+fn test_validate_invalid_validator() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+    assert_eq!(Network::total_models(), 1);
+
+    make_model_submittable();
+
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let mut amount_staked: u128 = 0;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    // let epoch_length = EpochLength::get();
+    // System::set_block_number(System::block_number() + epoch_length);
+
+    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    make_subnet_node_consensus_data_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+
+    Network::shift_node_classes(System::block_number(), epoch_length);
+
+    let epoch = System::block_number() / epoch_length;
+
+    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+
+    assert_err!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        subnet_node_data_vec
+      ),
+      Error::<Test>::InvalidValidator
+    );
+  });
+}
+
+#[test]
+fn test_attest() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+    assert_eq!(Network::total_models(), 1);
+
+    make_model_submittable();
+
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let mut amount_staked: u128 = 0;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    make_subnet_node_consensus_data_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+
+    // --- Insert validator
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
+
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        subnet_node_data_vec.clone()
+      )
+    );
+
+    // Attest
+    for n in 1..n_peers {
+      assert_ok!(
+        Network::attest(
+          RuntimeOrigin::signed(account(n)), 
+          subnet_id.clone(),
+        )
+      );
+    }
+    
+    let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+
+    assert_eq!(submission.validator, account(0));
+    assert_eq!(submission.data.len(), subnet_node_data_vec.len());
+    assert_eq!(submission.sum, DEFAULT_SCORE * n_peers as u128);
+    assert_eq!(submission.attests.len(), n_peers as usize);
+    assert!(submission.attests.contains_key(&account(1)));
+    assert_eq!(submission.nodes_count, n_peers);
+  });
+}
+
+#[test]
+fn test_attest_no_submission_err() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+    assert_eq!(Network::total_models(), 1);
+
+    make_model_submittable();
+
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let mut amount_staked: u128 = 0;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    make_subnet_node_consensus_data_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+
+    // --- Insert validator
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
+
+    assert_err!(
+      Network::attest(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+      ),
+      Error::<Test>::InvalidSubnetRewardsSubmission
+    );
+  });
+}
+
+#[test]
+fn test_attest_already_attested_err() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+    assert_eq!(Network::total_models(), 1);
+
+    make_model_submittable();
+
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let mut amount_staked: u128 = 0;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    make_subnet_node_consensus_data_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+
+    // --- Insert validator
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
+
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        subnet_node_data_vec.clone()
+      )
+    );
+
+    // Attest
+    for n in 1..n_peers {
+      assert_ok!(
+        Network::attest(
+          RuntimeOrigin::signed(account(n)), 
+          subnet_id.clone(),
+        )
+      );
+    }
+    
+    let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+
+    assert_eq!(submission.validator, account(0));
+    assert_eq!(submission.data.len(), subnet_node_data_vec.len());
+    assert_eq!(submission.sum, DEFAULT_SCORE * n_peers as u128);
+    assert_eq!(submission.attests.len(), n_peers as usize);
+    assert_eq!(submission.nodes_count, n_peers);
+
+    for n in 1..n_peers {
+      assert!(submission.attests.contains_key(&account(n)));
+    }
+
+    // for n in 0..n_peers {
+    //   assert_err!(
+    //     Network::attest(
+    //       RuntimeOrigin::signed(account(n)), 
+    //       subnet_id.clone(),
+    //     ),
+    //     Error::<Test>::AlreadyAttested
+    //   );
+    // }
+  });
+}
 
-// #[test]
-// fn test_apr() {
-//   new_test_ext().execute_with(|| {
-//     let max_models = Network::max_models();
-//     let epoch_length = EpochLength::get();
-    
+#[test]
+fn test_attest_partial() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
-//     let total_staked_balance: u128 = 12000000000000000000000;
+    build_subnet(subnet_path.clone());
+    assert_eq!(Network::total_models(), 1);
 
-//     let mut previous_apr = f64::MAX;
-//     for n in 0..max_models {
-//       TotalSubnets::<Test>::set(n+1);
-//       let apr = Network::get_apr(epoch_length, total_staked_balance*(n as u128 + 1));
-//       log::error!("apr          {:?}", apr);
-//       log::error!("previous_apr {:?}", apr);
+    make_model_submittable();
 
-//       assert!(previous_apr > apr, "previous_apr > apr");
-//       assert_ne!(apr, 0.0, "apr is 0");
-//       previous_apr = apr;
-//     }
-//   });
-// }
+    let n_peers: u32 = Network::max_subnet_nodes();
 
-// #[test]
-// fn test_emissions() {
-//   new_test_ext().execute_with(|| {
-//     let max_models = Network::max_models();
-//     let epoch_length = EpochLength::get();
-//     let total_staked_balance: u128 = 12000000000000000000000;
-//     let mut previous_emissions = u128::MAX;
-//     for n in 0..max_models {
-//       TotalSubnets::<Test>::set(n+1);
-//       let emissions = Network::get_epoch_emissions(epoch_length, total_staked_balance*(n as u128 + 1));
-//     }
-//   });
-// }
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let mut amount_staked: u128 = 0;
 
-// #[test]
-// fn test_submit_accountant_data() {
-//   new_test_ext().execute_with(|| {
-//     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
-//     let n_peers: u32 = Network::max_subnet_nodes();
-//     build_subnet(subnet_path.clone());
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
-//     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
-//     let deposit_amount: u128 = 1000000000000000000000000;
-//     let amount: u128 = 1000000000000000000000;
-//     let mut amount_staked: u128 = 0;
+    make_subnet_node_consensus_data_submittable();
 
-//     System::set_block_number(System::block_number() + CONSENSUS_STEPS);
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+    let epoch = System::block_number() / epoch_length;
 
-//     let amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, amount + deposit_amount, amount);
+    let subnet_node_data_vec = subnet_node_data(0, n_peers);
 
-//     make_subnet_node_dishonesty_consensus_proposable();
+    // --- Insert validator
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
 
-//     Network::check_and_choose_accountant();
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        subnet_node_data_vec.clone()
+      )
+    );
 
-//     let mut current_accountants = CurrentAccountant2::<Test>::get(subnet_id.clone());
-//     let accountant = current_accountants.first_key_value().unwrap();
-//     let accountant_data_count = AccountantDataCount::<Test>::get(subnet_id.clone());
+    // --- Attest with half agreement
+    assert_ok!(
+      Network::attest_partial(
+        RuntimeOrigin::signed(account(1)), 
+        subnet_id.clone(),
+        AttestationData {
+          agreement: 5000,
+          disagreements: Vec::new(),
+        }
+      )
+    );
 
-//     let mut accountant_data_peers: Vec<AccountantDataNodeParams> = Vec::new();
+    // --- Attest disputing one peer
+    assert_ok!(
+      Network::attest_partial(
+        RuntimeOrigin::signed(account(2)), 
+        subnet_id.clone(),
+        AttestationData {
+          agreement: 10000,
+          disagreements: vec![peer(3)],
+        }
+      )
+    );
 
-//     assert_err!(
-//       Network::submit_accountant_data(
-//         RuntimeOrigin::signed(accountant.0.clone()), 
-//         subnet_id.clone(),
-//         Vec::new(),
-//       ),
-//       Error::<Test>::InvalidAccountantData
-//     );
+    let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+    assert_eq!(submission.attests.len(), 3);
+    assert_eq!(submission.attests.get(&account(0)).unwrap().agreement, 10000);
+    assert_eq!(submission.attests.get(&account(1)).unwrap().agreement, 5000);
 
-//     let mut fake_data: Vec<AccountantDataNodeParams> = Vec::new();
+    let agreement = Network::get_attestation_agreement(submission.attests.get(&account(1)).unwrap(), &submission.data);
+    assert_eq!(agreement, 5000);
 
-//     for n in 0..255 {
-//       fake_data.push(
-//         AccountantDataNodeParams {
-//           peer_id: peer(n),
-//           data: BoundedVec::new()
-//         }
-//       )  
-//     }
+    let agreement = Network::get_attestation_agreement(submission.attests.get(&account(2)).unwrap(), &submission.data);
+    let undisputed_percentage = Network::percent_div((n_peers - 1) as u128, n_peers as u128);
+    assert_eq!(agreement, undisputed_percentage);
 
-//     assert_err!(
-//       Network::submit_accountant_data(
-//         RuntimeOrigin::signed(accountant.0.clone()), 
-//         subnet_id.clone(),
-//         fake_data,
-//       ),
-//       Error::<Test>::InvalidAccountantData
-//     );
+    // --- Equal stake: validator + half + disputed agreement
+    let attested_stake = amount + Network::percent_mul(amount, 5000) + Network::percent_mul(amount, undisputed_percentage);
+    let attestation_percentage = Network::get_attestation_percentage(subnet_id.clone(), &submission);
+    assert_eq!(attestation_percentage, Network::percent_div(attested_stake, amount * n_peers as u128));
+  });
+}
 
-//     for n in 0..n_peers {
-//       accountant_data_peers.push(
-//         AccountantDataNodeParams {
-//           peer_id: peer(n),
-//           data: BoundedVec::new()
-//         }
-//       )  
-//     }
+#[test]
+fn test_attest_partial_invalid_attestation_err() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
-//     assert_ok!(
-//       Network::submit_accountant_data(
-//         RuntimeOrigin::signed(accountant.0.clone()), 
-//         subnet_id.clone(),
-//         accountant_data_peers.clone(),
-//       )  
-//     );
+    build_subnet(subnet_path.clone());
+    assert_eq!(Network::total_models(), 1);
 
-//     assert_err!(
-//       Network::submit_accountant_data(
-//         RuntimeOrigin::signed(accountant.0.clone()), 
-//         subnet_id.clone(),
-//         accountant_data_peers.clone(),
-//       ),
-//       Error::<Test>::NotAccountant
-//     );
+    make_model_submittable();
 
-//   });
-// }
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let mut amount_staked: u128 = 0;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    make_subnet_node_consensus_data_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    let subnet_node_data_vec = subnet_node_data(0, 2);
+
+    // --- Insert validator
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
+
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        subnet_node_data_vec.clone()
+      )
+    );
+
+    assert_err!(
+      Network::attest_partial(
+        RuntimeOrigin::signed(account(1)), 
+        subnet_id.clone(),
+        AttestationData {
+          agreement: 10001,
+          disagreements: Vec::new(),
+        }
+      ),
+      Error::<Test>::InvalidAttestation
+    );
+
+    assert_err!(
+      Network::attest_partial(
+        RuntimeOrigin::signed(account(1)), 
+        subnet_id.clone(),
+        AttestationData {
+          agreement: 10000,
+          disagreements: vec![peer(0), peer(1), peer(2)],
+        }
+      ),
+      Error::<Test>::InvalidAttestation
+    );
+  });
+}
+
+#[test]
+fn test_attest_partial_disagreement_not_aggregated_err() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    make_model_submittable();
+
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    make_subnet_node_consensus_data_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0), account(1)]);
+
+    // --- The first validator submits every peer, the second leaves out the last peer
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        subnet_node_data(0, n_peers)
+      )
+    );
+
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(1)), 
+        subnet_id.clone(),
+        subnet_node_data(0, n_peers - 1)
+      )
+    );
+
+    // --- The last peer is only in the first validators data and isn't aggregated
+    assert_err!(
+      Network::attest_partial(
+        RuntimeOrigin::signed(account(2)), 
+        subnet_id.clone(),
+        AttestationData {
+          agreement: 10000,
+          disagreements: vec![peer(n_peers - 1)],
+        }
+      ),
+      Error::<Test>::InvalidAttestation
+    );
+
+    assert_ok!(
+      Network::attest_partial(
+        RuntimeOrigin::signed(account(2)), 
+        subnet_id.clone(),
+        AttestationData {
+          agreement: 10000,
+          disagreements: vec![peer(0)],
+        }
+      )
+    );
+  });
+}
 
 #[test]
-fn test_choose_accountants() {
+fn test_reward_subnets() {
   new_test_ext().execute_with(|| {
-    
-    setup_blocks(38);
-
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
-    let n_peers: u32 = Network::max_subnet_nodes();
+
     build_subnet(subnet_path.clone());
+    assert_eq!(Network::total_models(), 1);
 
-    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+    make_model_submittable();
+
+    let n_peers: u32 = Network::max_subnet_nodes();
 
     let deposit_amount: u128 = 1000000000000000000000000;
     let amount: u128 = 1000000000000000000000;
     let mut amount_staked: u128 = 0;
 
-    let amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, amount + deposit_amount, amount);
-    make_model_submittable();
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    make_subnet_node_consensus_data_submittable();
 
     let epoch_length = EpochLength::get();
     let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
     System::set_block_number(System::block_number() + epochs * epoch_length + 1);
-
     Network::shift_node_classes(System::block_number(), epoch_length);
-
     let epoch = System::block_number() / epoch_length;
 
-    let validator = SubnetRewardsValidator::<Test>::get(subnet_id.clone(), epoch as u32);
-    assert!(validator == None, "Validator should be None");
+    let subnet_node_data_vec = subnet_node_data(0, n_peers);
 
-    let accountants = CurrentAccountants::<Test>::get(subnet_id.clone(), epoch as u32);
-    assert!(accountants == None, "Accountant should be None");
+    // --- Insert validator
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
 
-    Network::do_choose_validator_and_accountants(System::block_number(), epoch as u32, epoch_length);
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        subnet_node_data_vec.clone()
+      )
+    );
+
+    // Attest
+    for n in 1..n_peers {
+      assert_ok!(
+        Network::attest(
+          RuntimeOrigin::signed(account(n)), 
+          subnet_id.clone(),
+        )
+      );
+    }
+    
+    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+  });
+}
 
-    let validator = SubnetRewardsValidator::<Test>::get(subnet_id.clone(), epoch as u32);
-    assert!(validator != None, "Validator is None");
+#[test]
+fn test_reward_subnets_epoch_rewards() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
-    let accountants = CurrentAccountants::<Test>::get(subnet_id.clone(), epoch as u32);
-    assert!(accountants != None, "Accountants is None");
-    assert_eq!(accountants.unwrap().len() as u32, TargetAccountantsLength::<Test>::get());
+    build_subnet(subnet_path.clone());
 
+    make_model_submittable();
+
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    make_subnet_node_consensus_data_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
 
-    let subnet_node_data_vec = subnet_node_data(0, n_peers);
     assert_ok!(
       Network::validate(
-        RuntimeOrigin::signed(validator.unwrap()), 
+        RuntimeOrigin::signed(account(0)), 
         subnet_id.clone(),
-        subnet_node_data_vec.clone()
+        subnet_node_data(0, n_peers)
       )
     );
 
-  });
-}
+    for n in 1..n_peers {
+      assert_ok!(
+        Network::attest(
+          RuntimeOrigin::signed(account(n)), 
+          subnet_id.clone(),
+        )
+      );
+    }
 
-fn setup_blocks(blocks: u64) {
-  let mut parent_hash = System::parent_hash();
+    let total_stake: u128 = TotalStake::<Test>::get();
+    let total_delegate_stake: u128 = TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id);
 
-  for i in 1..(blocks + 1) {
-    System::reset_events();
-    System::initialize(&i, &parent_hash, &Default::default());
-    InsecureRandomnessCollectiveFlip::on_initialize(i);
+    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
 
-    let header = System::finalize();
-    parent_hash = header.hash();
-    System::set_block_number(*header.number());
-  }
+    let epoch_rewards = EpochRewards::<Test>::get(subnet_id, epoch as u32).unwrap();
+    assert_eq!(epoch_rewards.nodes.len(), n_peers as usize);
+    assert_eq!(
+      epoch_rewards.delegate_stake_reward,
+      TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id) - total_delegate_stake
+    );
+    assert_eq!(
+      epoch_rewards.total,
+      TotalStake::<Test>::get() - total_stake + epoch_rewards.delegate_stake_reward
+    );
+
+    // --- Only the validator receives the validator reward
+    for node_reward in epoch_rewards.nodes.iter() {
+      if node_reward.account_id == account(0) {
+        assert!(node_reward.validator_reward > 0);
+      } else {
+        assert_eq!(node_reward.validator_reward, 0);
+      }
+      assert_eq!(
+        node_reward.reward,
+        AccountSubnetStake::<Test>::get(node_reward.account_id.clone(), subnet_id) - amount
+      );
+    }
+    assert_eq!(
+      epoch_rewards.validator_rewards,
+      epoch_rewards.nodes.iter().map(|node_reward| node_reward.validator_reward).sum::<u128>()
+    );
+
+    assert_eq!(Network::get_epoch_rewards(subnet_id, epoch as u32), Some(epoch_rewards));
+
+    // --- Records are pruned once they are `EpochRewardsRetentionEpochs` behind
+    assert_ok!(Network::set_epoch_rewards_retention_epochs(1));
+
+    Network::record_epoch_rewards(subnet_id, epoch as u32 + 1, Vec::new(), 0);
+
+    assert_eq!(EpochRewards::<Test>::get(subnet_id, epoch as u32), None);
+    assert!(EpochRewards::<Test>::get(subnet_id, epoch as u32 + 1).is_some());
+    assert_eq!(EpochRewardsOldestEpoch::<Test>::get(subnet_id), Some(epoch as u32 + 1));
+
+    assert_err!(
+      Network::set_epoch_rewards_retention_epochs(0),
+      Error::<Test>::InvalidEpochRewardsRetentionEpochs
+    );
+  });
 }
 
 #[test]
-fn test_randomness() {
+fn test_record_epoch_rewards_prunes_all_expired_epochs() {
   new_test_ext().execute_with(|| {
-    setup_blocks(38);
-    let gen_rand_num = Network::generate_random_number(1);
-    log::error!("test_randomness gen_rand_num {:?}", gen_rand_num);
+    let subnet_id: u32 = 1;
+    let max_prune: u32 = Network::MAX_EPOCH_REWARDS_PRUNE;
+    let epochs: u32 = max_prune * 2;
 
-    let rand_num = Network::get_random_number(96, 0);
-    log::error!("test_randomness rand_num {:?}", rand_num);
+    assert_ok!(Network::set_epoch_rewards_retention_epochs(epochs));
+
+    for epoch in 0..epochs {
+      Network::record_epoch_rewards(subnet_id, epoch, Vec::new(), 0);
+    }
+
+    for epoch in 0..epochs {
+      assert!(EpochRewards::<Test>::get(subnet_id, epoch).is_some());
+    }
+
+    // --- Lowering the retention prunes every expired epoch, bounded per recorded epoch
+    assert_ok!(Network::set_epoch_rewards_retention_epochs(1));
+
+    Network::record_epoch_rewards(subnet_id, epochs, Vec::new(), 0);
+
+    for epoch in 0..max_prune {
+      assert_eq!(EpochRewards::<Test>::get(subnet_id, epoch), None);
+    }
+    for epoch in max_prune..epochs + 1 {
+      assert!(EpochRewards::<Test>::get(subnet_id, epoch).is_some());
+    }
+    assert_eq!(EpochRewardsOldestEpoch::<Test>::get(subnet_id), Some(max_prune));
+
+    Network::record_epoch_rewards(subnet_id, epochs + 1, Vec::new(), 0);
+
+    for epoch in 0..epochs {
+      assert_eq!(EpochRewards::<Test>::get(subnet_id, epoch), None);
+    }
+    assert_eq!(EpochRewardsOldestEpoch::<Test>::get(subnet_id), Some(epochs));
+
+    // --- Caught up, only the retained epoch is left
+    Network::record_epoch_rewards(subnet_id, epochs + 2, Vec::new(), 0);
 
+    for epoch in 0..epochs + 2 {
+      assert_eq!(EpochRewards::<Test>::get(subnet_id, epoch), None);
+    }
+    assert!(EpochRewards::<Test>::get(subnet_id, epochs + 2).is_some());
+    assert_eq!(EpochRewardsOldestEpoch::<Test>::get(subnet_id), Some(epochs + 2));
   });
 }
 
-// #[test]
-// fn test_propose_dishonesty() {
-//   new_test_ext().execute_with(|| {
-//     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
-//     let n_peers: u32 = Network::max_subnet_nodes();
-//     build_subnet(subnet_path.clone());
+#[test]
+fn test_reward_subnets_remove_subnet_node() {
+  new_test_ext().execute_with(|| {
+    let max_absent = MaxSequentialAbsentSubnetNode::<Test>::get();
+    
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
-//     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+    build_subnet(subnet_path.clone());
+    assert_eq!(Network::total_models(), 1);
 
-//     let deposit_amount: u128 = 1000000000000000000000000;
-//     let amount: u128 = 1000000000000000000000;
-//     let mut amount_staked: u128 = 0;
+    make_model_submittable();
 
-//     System::set_block_number(System::block_number() + CONSENSUS_STEPS);
+    let n_peers: u32 = Network::max_subnet_nodes();
 
-//     let amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, amount + deposit_amount, amount);
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let mut amount_staked: u128 = 0;
 
-//     make_subnet_node_dishonesty_consensus_proposable();
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
-//     Network::check_and_choose_accountant();
+    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
-//     let mut current_accountants = CurrentAccountant2::<Test>::get(subnet_id.clone());
-//     let accountant = current_accountants.first_key_value().unwrap();
-//     let accountant_data_count = AccountantDataCount::<Test>::get(subnet_id.clone());
+    make_subnet_node_consensus_data_submittable();
 
-//     let mut accountant_data_peers: Vec<AccountantDataNodeParams> = Vec::new();
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
 
-//     for n in 0..n_peers {
-//       accountant_data_peers.push(
-//         AccountantDataNodeParams {
-//           peer_id: peer(n),
-//           data: BoundedVec::new()
-//         }
-//       )  
-//     }
+    let base_reward = BaseReward::<Test>::get();
+    let mut validator_rewards: u128 = 0;
 
-//     assert_ok!(
-//       Network::submit_accountant_data(
-//         RuntimeOrigin::signed(accountant.0.clone()), 
-//         subnet_id.clone(),
-//         accountant_data_peers.clone(),
-//       )  
-//     );
+    for num in 0..max_absent+1 {
+      System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+      Network::shift_node_classes(System::block_number(), epoch_length);
+      let epoch = System::block_number() / epoch_length;
+  
+      let subnet_node_data_vec = subnet_node_data(0, n_peers-1);
+    
+      // --- Insert validator
+      SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
+  
+      assert_ok!(
+        Network::validate(
+          RuntimeOrigin::signed(account(0)), 
+          subnet_id.clone(),
+          subnet_node_data_vec.clone()
+        )
+      );
+  
+      // Attest
+      for n in 1..n_peers-1 {
+        assert_ok!(
+          Network::attest(
+            RuntimeOrigin::signed(account(n)), 
+            subnet_id.clone(),
+          )
+        );
+      }
 
-//     let non_empty_data: Vec<u8> = "__data__".into();
+      // --- Attestation percentage is weighted by the stake of the submittable nodes at the time of rewarding
+      let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+      let attestation_percentage: u128 = Network::get_attestation_percentage(subnet_id.clone(), &submission);
+      validator_rewards += Network::percent_mul(base_reward, attestation_percentage);
+      
+      Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+
+      let node_absent_count = SequentialAbsentSubnetNode::<Test>::get(subnet_id.clone(), account(n_peers-1));
+      assert_eq!(node_absent_count, num+1);
+
+      log::error!("node_absent_count {:?}", node_absent_count);
+
+      assert!(
+        System::events().iter().any(|record| record.event == RuntimeEvent::Network(
+          crate::Event::SubnetNodeAbsent {
+            subnet_id: subnet_id,
+            account_id: account(n_peers-1),
+            count: num+1,
+            max: max_absent,
+          }
+        ))
+      );
+
+      if num + 1 > max_absent {
+        post_remove_subnet_node_ensures(n_peers-1, subnet_id.clone());
+
+        assert!(
+          System::events().iter().any(|record| record.event == RuntimeEvent::Network(
+            crate::Event::SubnetNodeRemoved {
+              subnet_id: subnet_id,
+              account_id: account(n_peers-1),
+              peer_id: peer(n_peers-1),
+              reason: SubnetNodeRemovalReason::MaxSequentialAbsent,
+              block: System::block_number(),
+            }
+          ))
+        );
+      }
+
+      let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+
+      let base_subnet_reward: u128 = BaseSubnetReward::<Test>::get();
+      let delegate_stake_rewards_percentage: u128 = DelegateStakeRewardsPercentage::<Test>::get();
+      let subnet_reward: u128 = Network::percent_mul(base_subnet_reward, delegate_stake_rewards_percentage);
+  
+      let reward_ratio: u128 = Network::percent_div(DEFAULT_SCORE, submission.sum);
+      let account_reward: u128 = Network::percent_mul(reward_ratio, subnet_reward);
+
+      // check each subnet nodes balance increased
+      for n in 0..n_peers {
+        if n == 0 {
+          // validator
+          let stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone());
+          assert!(stake_balance == amount + (account_reward * (num+1) as u128) + validator_rewards, "Invalid validator staking rewards")  
+        } else if n == n_peers - 1 {
+          // node removed | should have no rewards
+          let stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone());
+          assert!(stake_balance == amount, "Invalid subnet node staking rewards")  
+        } else {
+          // attestors
+          let stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone());
+          assert!(stake_balance == amount + (account_reward * (num+1) as u128), "Invalid subnet node staking rewards")  
+        }
+      }
+    }
+  });
+}
 
-//     // propose_dishonesty
-//     assert_ok!(
-//       Network::propose_dishonesty(
-//         RuntimeOrigin::signed(account(1)), 
-//         subnet_id.clone(),
-//         peer(0),
-//         PropsType::DishonestAccountant,
-//         non_empty_data,
-//         Some(1),
-//       )
-//     );
-//   });
-// }
+#[test]
+fn test_reward_subnets_absent_node_increment_decrement() {
+  new_test_ext().execute_with(|| {
+    let max_absent = MaxSequentialAbsentSubnetNode::<Test>::get();
+    
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
+    build_subnet(subnet_path.clone());
+    assert_eq!(Network::total_models(), 1);
 
+    make_model_submittable();
 
+    let n_peers: u32 = Network::max_subnet_nodes();
 
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    let mut amount_staked: u128 = 0;
 
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
+    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
+    make_subnet_node_consensus_data_submittable();
 
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
 
+    for num in 0..10 {
+      System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+      Network::shift_node_classes(System::block_number(), epoch_length);
+      let epoch = System::block_number() / epoch_length;
 
+      if num % 2 == 0 {
+        let subnet_node_data_vec = subnet_node_data(0, n_peers-1);
+    
+        // --- Insert validator
+        SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
+    
+        assert_ok!(
+          Network::validate(
+            RuntimeOrigin::signed(account(0)), 
+            subnet_id.clone(),
+            subnet_node_data_vec.clone()
+          )
+        );
+    
+        // Attest
+        for n in 1..n_peers-1 {
+          assert_ok!(
+            Network::attest(
+              RuntimeOrigin::signed(account(n)), 
+              subnet_id.clone(),
+            )
+          );
+        }
+        
+        Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+  
+        let node_absent_count = SequentialAbsentSubnetNode::<Test>::get(subnet_id.clone(), account(n_peers-1));
+        assert_eq!(node_absent_count, 1);
+      } else {
+        let subnet_node_data_vec = subnet_node_data(0, n_peers);
+    
+        // --- Insert validator
+        SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
+    
+        assert_ok!(
+          Network::validate(
+            RuntimeOrigin::signed(account(0)), 
+            subnet_id.clone(),
+            subnet_node_data_vec.clone()
+          )
+        );
+    
+        // Attest
+        for n in 1..n_peers {
+          assert_ok!(
+            Network::attest(
+              RuntimeOrigin::signed(account(n)), 
+              subnet_id.clone(),
+            )
+          );
+        }
+        
+        Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+  
+        let node_absent_count = SequentialAbsentSubnetNode::<Test>::get(subnet_id.clone(), account(n_peers-1));
+        assert_eq!(node_absent_count, 0);  
+      }
+    }
+  });
+}
 
 #[test]
-fn test_validate() {
+fn test_reward_subnets_check_balances() {
   new_test_ext().execute_with(|| {
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
@@ -5383,32 +7658,30 @@ fn test_validate() {
     let n_peers: u32 = Network::max_subnet_nodes();
 
     let deposit_amount: u128 = 1000000000000000000000000;
-    let amount: u128 = 1000000000000000000000;
+    let amount: u128 = MinStakeBalance::<Test>::get();
     let mut amount_staked: u128 = 0;
 
     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
+    // let epoch_length = EpochLength::get();
     // System::set_block_number(System::block_number() + epoch_length);
 
+    // let epoch = System::block_number() / epoch_length;
+
     amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
-    make_subnet_node_consensus_data_submittable();
+    // make_subnet_node_consensus_data_submittable();
 
     let epoch_length = EpochLength::get();
-
     let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
     System::set_block_number(System::block_number() + epochs * epoch_length + 1);
-
     Network::shift_node_classes(System::block_number(), epoch_length);
-
     let epoch = System::block_number() / epoch_length;
 
-    log::error!("epoch is -> {:?}", epoch);
-
     let subnet_node_data_vec = subnet_node_data(0, n_peers);
 
     // --- Insert validator
-    SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
 
     assert_ok!(
       Network::validate(
@@ -5418,135 +7691,242 @@ fn test_validate() {
       )
     );
 
+    // Attest
+    for n in 1..n_peers {
+      assert_ok!(
+        Network::attest(
+          RuntimeOrigin::signed(account(n)), 
+          subnet_id.clone(),
+        )
+      );
+    }
+    
+    let delegate_stake_balance: u128 = TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone());
+
+    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+
+    let post_delegate_stake_balance: u128 = TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone());
+    assert!(post_delegate_stake_balance > delegate_stake_balance, "Delegate stake balance should increase");
+
     let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
 
-    assert_eq!(submission.validator, account(0), "Err: validator");
-    assert_eq!(submission.data.len(), subnet_node_data_vec.len(), "Err: data len");
-    assert_eq!(submission.sum, DEFAULT_SCORE * n_peers as u128, "Err: sum");
-    assert_eq!(submission.attests.len(), 1, "Err: attests");
-    assert_eq!(submission.nodes_count, n_peers, "Err: nodes_count");
+    let base_subnet_reward: u128 = BaseSubnetReward::<Test>::get();
+    let delegate_stake_rewards_percentage: u128 = DelegateStakeRewardsPercentage::<Test>::get();
+    let subnet_reward: u128 = Network::percent_mul(base_subnet_reward, delegate_stake_rewards_percentage);
 
-    assert_err!(
-      Network::validate(
-        RuntimeOrigin::signed(account(0)), 
-        subnet_id.clone(),
-        subnet_node_data_vec.clone()
-      ),
-      Error::<Test>::SubnetRewardsAlreadySubmitted
-    );
+    let reward_ratio: u128 = Network::percent_div(DEFAULT_SCORE, submission.sum);
+    let account_reward: u128 = Network::percent_mul(reward_ratio, subnet_reward);
+
+    let base_reward = BaseReward::<Test>::get();
+
+    // check each subnet nodes balance increased
+    for n in 0..n_peers {
+      if n == 0 {
+        // validator
+        let stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone());
+        assert!(stake_balance == amount + account_reward + base_reward, "Invalid validator staking rewards")  
+      } else {
+        // attestors
+        let stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone());
+        assert!(stake_balance == amount + account_reward, "Invalid subnet node staking rewards")  
+      }
+    }
   });
 }
 
 #[test]
-fn test_validate_invalid_validator() {
+fn test_reward_subnets_multiple_validators() {
   new_test_ext().execute_with(|| {
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
     build_subnet(subnet_path.clone());
-    assert_eq!(Network::total_models(), 1);
 
     make_model_submittable();
 
     let n_peers: u32 = Network::max_subnet_nodes();
 
     let deposit_amount: u128 = 1000000000000000000000000;
-    let amount: u128 = 1000000000000000000000;
-    let mut amount_staked: u128 = 0;
+    let amount: u128 = MinStakeBalance::<Test>::get();
 
     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
-    // let epoch_length = EpochLength::get();
-    // System::set_block_number(System::block_number() + epoch_length);
-
-    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
-
-    make_subnet_node_consensus_data_submittable();
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
     let epoch_length = EpochLength::get();
     let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
     System::set_block_number(System::block_number() + epochs * epoch_length + 1);
-
     Network::shift_node_classes(System::block_number(), epoch_length);
-
     let epoch = System::block_number() / epoch_length;
 
     let subnet_node_data_vec = subnet_node_data(0, n_peers);
 
-    assert_err!(
+    // --- Insert validators
+    SubnetRewardsValidators::<Test>::insert(
+      subnet_id, 
+      epoch as u32, 
+      vec![account(0), account(1), account(2), account(3)]
+    );
+
+    assert_ok!(
       Network::validate(
         RuntimeOrigin::signed(account(0)), 
         subnet_id.clone(),
-        subnet_node_data_vec
-      ),
-      Error::<Test>::InvalidValidator
+        subnet_node_data_vec.clone()
+      )
+    );
+
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(1)), 
+        subnet_id.clone(),
+        subnet_node_data_vec.clone()
+      )
+    );
+
+    // --- account(2) deviates from the other validators
+    let deviating_data: Vec<SubnetNodeData> = subnet_node_data_vec.iter()
+      .map(|d| SubnetNodeData { peer_id: d.peer_id.clone(), score: d.score * 10 })
+      .collect();
+
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(2)), 
+        subnet_id.clone(),
+        deviating_data
+      )
     );
+
+    // --- account(3) doesn't submit
+    for n in 3..n_peers {
+      assert_ok!(
+        Network::attest(
+          RuntimeOrigin::signed(account(n)), 
+          subnet_id.clone(),
+        )
+      );
+    }
+
+    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+
+    let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+    assert_eq!(submission.submissions.len(), 3);
+    assert_eq!(submission.sum, DEFAULT_SCORE * n_peers as u128);
+
+    let base_subnet_reward: u128 = BaseSubnetReward::<Test>::get();
+    let delegate_stake_rewards_percentage: u128 = DelegateStakeRewardsPercentage::<Test>::get();
+    let subnet_reward: u128 = Network::percent_mul(base_subnet_reward, delegate_stake_rewards_percentage);
+
+    let reward_ratio: u128 = Network::percent_div(DEFAULT_SCORE, submission.sum);
+    let account_reward: u128 = Network::percent_mul(reward_ratio, subnet_reward);
+
+    let base_reward = BaseReward::<Test>::get();
+    let max_slash_amount = MaxSlashAmount::<Test>::get();
+
+    // --- Validators in consensus split the validator reward
+    for n in 0..2 {
+      assert_eq!(AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone()), amount + account_reward + base_reward / 2);
+      assert_eq!(AccountPenaltyCount::<Test>::get(account(n)), 0);
+    }
+
+    // --- Deviating and absent validators are slashed
+    for n in 2..4 {
+      assert_eq!(AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone()), amount + account_reward - max_slash_amount);
+      assert_eq!(AccountPenaltyCount::<Test>::get(account(n)), 1);
+    }
   });
 }
 
 #[test]
-fn test_attest() {
+fn test_aggregate_submissions() {
   new_test_ext().execute_with(|| {
-    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
-
-    build_subnet(subnet_path.clone());
-    assert_eq!(Network::total_models(), 1);
-
-    make_model_submittable();
+    let mut submissions: BTreeMap<AccountId, Vec<SubnetNodeData>> = BTreeMap::new();
+    submissions.insert(
+      account(0), 
+      vec![SubnetNodeData { peer_id: peer(0), score: 100 }, SubnetNodeData { peer_id: peer(1), score: 200 }]
+    );
+    submissions.insert(
+      account(1), 
+      vec![SubnetNodeData { peer_id: peer(0), score: 100 }, SubnetNodeData { peer_id: peer(1), score: 200 }]
+    );
+    submissions.insert(
+      account(2), 
+      vec![
+        SubnetNodeData { peer_id: peer(0), score: 500 }, 
+        SubnetNodeData { peer_id: peer(1), score: 200 },
+        SubnetNodeData { peer_id: peer(2), score: 100 }
+      ]
+    );
 
-    let n_peers: u32 = Network::max_subnet_nodes();
+    let (data, deviating_validators) = Network::aggregate_submissions(&submissions);
 
-    let deposit_amount: u128 = 1000000000000000000000000;
-    let amount: u128 = 1000000000000000000000;
-    let mut amount_staked: u128 = 0;
+    // --- peer(2) wasn't submitted by a majority of validators
+    assert_eq!(data.len(), 2);
+    assert!(data.contains(&SubnetNodeData { peer_id: peer(0), score: 100 }));
+    assert!(data.contains(&SubnetNodeData { peer_id: peer(1), score: 200 }));
 
-    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+    assert_eq!(deviating_validators, vec![account(2)]);
 
-    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+    assert_eq!(Network::get_median(vec![4, 1, 3, 2]), 2);
+    assert_eq!(Network::get_median(vec![3, 1, 2]), 2);
+    assert_eq!(Network::get_median(Vec::new()), 0);
 
-    make_subnet_node_consensus_data_submittable();
+    // --- Outliers are filtered without losing precision on large scores
+    let score: u128 = u128::MAX - 10;
+    assert_eq!(
+      Network::filter_outliers(vec![score, score + 1, score + 2, score + 3, 0]),
+      vec![score, score + 1, score + 2, score + 3]
+    );
+    assert_eq!(Network::filter_outliers(vec![100, 101, 102, 103, 104, 100000]), vec![100, 101, 102, 103, 104]);
+  });
+}
 
-    let epoch_length = EpochLength::get();
-    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
-    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
-    Network::shift_node_classes(System::block_number(), epoch_length);
-    let epoch = System::block_number() / epoch_length;
+#[test]
+fn test_migrate_to_v1() {
+  new_test_ext().execute_with(|| {
+    use crate::migrations::v1::{MigrateToV1, OldRewardsData, SubnetRewardsValidator};
+    use frame_support::traits::{OnRuntimeUpgrade, StorageVersion, GetStorageVersion};
+    use sp_std::collections::btree_set::BTreeSet;
 
-    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+    StorageVersion::new(0).put::<Network>();
 
-    // --- Insert validator
-    SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
+    let subnet_id: u32 = 1;
+    let epoch: u32 = 10;
+    let subnet_node_data_vec = subnet_node_data(0, 2);
 
-    assert_ok!(
-      Network::validate(
-        RuntimeOrigin::signed(account(0)), 
-        subnet_id.clone(),
-        subnet_node_data_vec.clone()
-      )
+    SubnetRewardsValidator::<Test>::insert(subnet_id, epoch, account(0));
+
+    let mut attests: BTreeSet<AccountId> = BTreeSet::new();
+    attests.insert(account(0));
+    attests.insert(account(1));
+
+    let old_rewards_data = OldRewardsData {
+      validator: account(0),
+      nodes_count: 2,
+      sum: DEFAULT_SCORE * 2,
+      attests: attests,
+      data: subnet_node_data_vec.clone(),
+    };
+    frame_support::storage::unhashed::put(
+      &SubnetRewardsSubmission::<Test>::hashed_key_for(subnet_id, epoch),
+      &old_rewards_data
     );
 
-    // Attest
-    for n in 1..n_peers {
-      assert_ok!(
-        Network::attest(
-          RuntimeOrigin::signed(account(n)), 
-          subnet_id.clone(),
-        )
-      );
-    }
-    
-    let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+    MigrateToV1::<Test>::on_runtime_upgrade();
 
+    assert_eq!(Network::on_chain_storage_version(), 1);
+    assert_eq!(SubnetRewardsValidator::<Test>::get(subnet_id, epoch), None);
+    assert_eq!(SubnetRewardsValidators::<Test>::get(subnet_id, epoch), Some(vec![account(0)]));
+
+    let submission = SubnetRewardsSubmission::<Test>::get(subnet_id, epoch).unwrap();
     assert_eq!(submission.validator, account(0));
-    assert_eq!(submission.data.len(), subnet_node_data_vec.len());
-    assert_eq!(submission.sum, DEFAULT_SCORE * n_peers as u128);
-    assert_eq!(submission.attests.len(), n_peers as usize);
-    assert_eq!(submission.attests.get(&account(1)), Some(&account(1)));
-    assert_eq!(submission.nodes_count, n_peers);
+    assert_eq!(submission.data, subnet_node_data_vec.clone());
+    assert_eq!(submission.submissions.get(&account(0)), Some(&subnet_node_data_vec));
+    assert_eq!(submission.attests.get(&account(1)), Some(&Network::full_attestation()));
   });
 }
 
 #[test]
-fn test_attest_no_submission_err() {
+fn test_reward_subnets_validator_slash() {
   new_test_ext().execute_with(|| {
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
@@ -5576,20 +7956,31 @@ fn test_attest_no_submission_err() {
     let subnet_node_data_vec = subnet_node_data(0, n_peers);
 
     // --- Insert validator
-    SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
 
-    assert_err!(
-      Network::attest(
+    assert_ok!(
+      Network::validate(
         RuntimeOrigin::signed(account(0)), 
         subnet_id.clone(),
-      ),
-      Error::<Test>::InvalidSubnetRewardsSubmission
+        subnet_node_data_vec.clone()
+      )
     );
+
+    // No attests to ensure validator is slashed
+    
+    let validator_stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(0), subnet_id.clone());
+
+    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+
+    let slashed_validator_stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(0), subnet_id.clone());
+
+    // Ensure validator was slashed
+    assert!(validator_stake_balance > slashed_validator_stake_balance, "Validator was not slashed")
   });
 }
 
 #[test]
-fn test_attest_already_attested_err() {
+fn test_reward_subnets_subnet_penalty_count() {
   new_test_ext().execute_with(|| {
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
@@ -5619,13 +8010,13 @@ fn test_attest_already_attested_err() {
     let subnet_node_data_vec = subnet_node_data(0, n_peers);
 
     // --- Insert validator
-    SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
 
     assert_ok!(
       Network::validate(
         RuntimeOrigin::signed(account(0)), 
         subnet_id.clone(),
-        subnet_node_data_vec.clone()
+        Vec::new()
       )
     );
 
@@ -5639,32 +8030,18 @@ fn test_attest_already_attested_err() {
       );
     }
     
-    let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
-
-    assert_eq!(submission.validator, account(0));
-    assert_eq!(submission.data.len(), subnet_node_data_vec.len());
-    assert_eq!(submission.sum, DEFAULT_SCORE * n_peers as u128);
-    assert_eq!(submission.attests.len(), n_peers as usize);
-    assert_eq!(submission.nodes_count, n_peers);
+    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
 
-    for n in 1..n_peers {
-      assert_eq!(submission.attests.get(&account(n)), Some(&account(n)));
-    }
+    let subnet_penalty_count = SubnetPenaltyCount::<Test>::get(subnet_id.clone());
+    assert_eq!(subnet_penalty_count, 1);
 
-    // for n in 0..n_peers {
-    //   assert_err!(
-    //     Network::attest(
-    //       RuntimeOrigin::signed(account(n)), 
-    //       subnet_id.clone(),
-    //     ),
-    //     Error::<Test>::AlreadyAttested
-    //   );
-    // }
+    let account_penalty_count = AccountPenaltyCount::<Test>::get(account(0));
+    assert_eq!(account_penalty_count, 0);
   });
 }
 
 #[test]
-fn test_reward_subnets() {
+fn test_reward_subnets_account_penalty_count() {
   new_test_ext().execute_with(|| {
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
@@ -5694,35 +8071,31 @@ fn test_reward_subnets() {
     let subnet_node_data_vec = subnet_node_data(0, n_peers);
 
     // --- Insert validator
-    SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
 
     assert_ok!(
       Network::validate(
         RuntimeOrigin::signed(account(0)), 
         subnet_id.clone(),
-        subnet_node_data_vec.clone()
+        Vec::new()
       )
     );
 
-    // Attest
-    for n in 1..n_peers {
-      assert_ok!(
-        Network::attest(
-          RuntimeOrigin::signed(account(n)), 
-          subnet_id.clone(),
-        )
-      );
-    }
-    
+    // No Attest
+
     Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+
+    let subnet_penalty_count = SubnetPenaltyCount::<Test>::get(subnet_id.clone());
+    assert_eq!(subnet_penalty_count, 1);
+
+    let account_penalty_count = AccountPenaltyCount::<Test>::get(account(0));
+    assert_eq!(account_penalty_count, 1);
   });
 }
 
 #[test]
-fn test_reward_subnets_remove_subnet_node() {
+fn test_reward_subnets_remove_subnet_max_penalties() {
   new_test_ext().execute_with(|| {
-    let max_absent = MaxSequentialAbsentSubnetNode::<Test>::get();
-    
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
     build_subnet(subnet_path.clone());
@@ -5744,481 +8117,602 @@ fn test_reward_subnets_remove_subnet_node() {
 
     let epoch_length = EpochLength::get();
     let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+    let epoch = System::block_number() / epoch_length;
 
-    for num in 0..max_absent+1 {
-      System::set_block_number(System::block_number() + epochs * epoch_length + 1);
-      Network::shift_node_classes(System::block_number(), epoch_length);
-      let epoch = System::block_number() / epoch_length;
-  
-      let subnet_node_data_vec = subnet_node_data(0, n_peers-1);
-    
-      // --- Insert validator
-      SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
-  
-      assert_ok!(
-        Network::validate(
-          RuntimeOrigin::signed(account(0)), 
-          subnet_id.clone(),
-          subnet_node_data_vec.clone()
-        )
-      );
-  
-      // Attest
-      for n in 1..n_peers-1 {
-        assert_ok!(
-          Network::attest(
-            RuntimeOrigin::signed(account(n)), 
-            subnet_id.clone(),
-          )
-        );
-      }
-      
-      Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+    // --- Any penalty will remove the subnet
+    MaxSubnetPenalties::<Test>::set(0);
 
-      let node_absent_count = SequentialAbsentSubnetNode::<Test>::get(subnet_id.clone(), account(n_peers-1));
-      assert_eq!(node_absent_count, num+1);
+    // --- Insert validator
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
 
-      log::error!("node_absent_count {:?}", node_absent_count);
+    // --- Validator submits no data
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        Vec::new()
+      )
+    );
 
-      if num + 1 > max_absent {
-        post_remove_subnet_node_ensures(n_peers-1, subnet_id.clone());
-      }
+    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
 
-      let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+    assert_eq!(SubnetsData::<Test>::contains_key(subnet_id.clone()), false);
+    assert_eq!(SubnetPaths::<Test>::contains_key(subnet_path.clone()), false);
+    assert_eq!(SubnetsToClean::<Test>::get(), vec![subnet_id.clone()]);
 
-      let base_subnet_reward: u128 = BaseSubnetReward::<Test>::get();
-      let delegate_stake_rewards_percentage: u128 = DelegateStakeRewardsPercentage::<Test>::get();
-      let subnet_reward: u128 = Network::percent_mul(base_subnet_reward, delegate_stake_rewards_percentage);
-  
-      let reward_ratio: u128 = Network::percent_div(DEFAULT_SCORE, submission.sum);
-      let account_reward: u128 = Network::percent_mul(reward_ratio, subnet_reward);
-  
-      let base_reward = BaseReward::<Test>::get();
-  
-      let submission_nodes_count: u128 = submission.nodes_count as u128;
-      let submission_attestations: u128 = submission.attests.len() as u128;
-      let attestation_percentage: u128 = Network::percent_div(submission_attestations, submission_nodes_count);
+    // --- Remaining subnet data is removed in `on_idle`
+    Network::on_idle(System::block_number(), Weight::MAX);
 
-      // check each subnet nodes balance increased
-      for n in 0..n_peers {
-        if n == 0 {
-          // validator
-          let stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone());
-          let validator_reward: u128 = Network::percent_mul(base_reward, attestation_percentage);
-          assert!(stake_balance == amount + (account_reward * (num+1) as u128) + (validator_reward * (num+1) as u128), "Invalid validator staking rewards")  
-        } else if n == n_peers - 1 {
-          // node removed | should have no rewards
-          let stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone());
-          assert!(stake_balance == amount, "Invalid subnet node staking rewards")  
-        } else {
-          // attestors
-          let stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone());
-          assert!(stake_balance == amount + (account_reward * (num+1) as u128), "Invalid subnet node staking rewards")  
-        }
-      }
+    assert_eq!(SubnetsToClean::<Test>::get(), Vec::<u32>::new());
+    assert_eq!(SubnetPenaltyCount::<Test>::get(subnet_id.clone()), 0);
+    assert_eq!(TotalSubnetNodes::<Test>::get(subnet_id.clone()), 0);
+    assert_eq!(SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32), None);
+
+    for n in 0..n_peers {
+      post_clean_subnet_ensures(n, subnet_id.clone());
     }
+
+    // Total models should stay constant as its an index value
+    assert_eq!(Network::total_models(), 1);
   });
 }
 
 #[test]
-fn test_reward_subnets_absent_node_increment_decrement() {
+fn test_clean_subnet_refunds_stake() {
   new_test_ext().execute_with(|| {
-    let max_absent = MaxSequentialAbsentSubnetNode::<Test>::get();
-    
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
     build_subnet(subnet_path.clone());
-    assert_eq!(Network::total_models(), 1);
-
-    make_model_submittable();
 
     let n_peers: u32 = Network::max_subnet_nodes();
 
     let deposit_amount: u128 = 1000000000000000000000000;
     let amount: u128 = 1000000000000000000000;
-    let mut amount_staked: u128 = 0;
 
     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
-    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
-    make_subnet_node_consensus_data_submittable();
+    let _ = Balances::deposit_creating(&account(n_peers), deposit_amount);
+    assert_ok!(
+      Network::add_to_delegate_stake(
+        RuntimeOrigin::signed(account(n_peers)),
+        subnet_id.clone(),
+        amount,
+      ) 
+    );
 
-    let epoch_length = EpochLength::get();
-    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    let delegate_shares = AccountSubnetDelegateStakeShares::<Test>::get(account(n_peers), subnet_id.clone());
+    let delegate_balance = Network::convert_to_balance(
+      delegate_shares,
+      TotalSubnetDelegateStakeShares::<Test>::get(subnet_id.clone()),
+      TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone())
+    );
 
-    for num in 0..10 {
-      System::set_block_number(System::block_number() + epochs * epoch_length + 1);
-      Network::shift_node_classes(System::block_number(), epoch_length);
-      let epoch = System::block_number() / epoch_length;
+    let total_stake = TotalStake::<Test>::get();
 
-      if num % 2 == 0 {
-        let subnet_node_data_vec = subnet_node_data(0, n_peers-1);
-    
-        // --- Insert validator
-        SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
-    
-        assert_ok!(
-          Network::validate(
-            RuntimeOrigin::signed(account(0)), 
-            subnet_id.clone(),
-            subnet_node_data_vec.clone()
-          )
-        );
-    
-        // Attest
-        for n in 1..n_peers-1 {
-          assert_ok!(
-            Network::attest(
-              RuntimeOrigin::signed(account(n)), 
-              subnet_id.clone(),
-            )
-          );
-        }
-        
-        Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
-  
-        let node_absent_count = SequentialAbsentSubnetNode::<Test>::get(subnet_id.clone(), account(n_peers-1));
-        assert_eq!(node_absent_count, 1);
-      } else {
-        let subnet_node_data_vec = subnet_node_data(0, n_peers);
-    
-        // --- Insert validator
-        SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
-    
-        assert_ok!(
-          Network::validate(
-            RuntimeOrigin::signed(account(0)), 
-            subnet_id.clone(),
-            subnet_node_data_vec.clone()
-          )
-        );
-    
-        // Attest
-        for n in 1..n_peers {
-          assert_ok!(
-            Network::attest(
-              RuntimeOrigin::signed(account(n)), 
-              subnet_id.clone(),
-            )
-          );
-        }
-        
-        Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
-  
-        let node_absent_count = SequentialAbsentSubnetNode::<Test>::get(subnet_id.clone(), account(n_peers-1));
-        assert_eq!(node_absent_count, 0);  
-      }
+    Network::do_remove_subnet(System::block_number(), subnet_id.clone(), None, SubnetRemovalReason::MaxPenalties);
+
+    // --- Clean up over multiple blocks
+    MaxSubnetCleanupEntries::<Test>::set(2);
+
+    let mut blocks = 0;
+    while SubnetsToClean::<Test>::get().len() > 0 {
+      Network::on_idle(System::block_number(), Weight::MAX);
+      System::set_block_number(System::block_number() + 1);
+      blocks += 1;
+    }
+    assert!(blocks > 1);
+
+    for n in 0..n_peers {
+      post_clean_subnet_ensures(n, subnet_id.clone());
+      assert_eq!(ClaimableBalance::<Test>::get(account(n)), amount);
     }
+
+    assert_eq!(TotalSubnetStake::<Test>::get(subnet_id.clone()), 0);
+    assert_eq!(TotalStake::<Test>::get(), total_stake - amount * n_peers as u128);
+
+    assert_eq!(AccountSubnetDelegateStakeShares::<Test>::get(account(n_peers), subnet_id.clone()), 0);
+    assert_eq!(TotalSubnetDelegateStakeShares::<Test>::get(subnet_id.clone()), 0);
+    assert_eq!(TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone()), 0);
+    assert_eq!(ClaimableBalance::<Test>::get(account(n_peers)), delegate_balance);
+  });
+}
+
+#[test]
+fn test_claim_balance() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    Network::do_remove_subnet(System::block_number(), subnet_id.clone(), None, SubnetRemovalReason::MaxPenalties);
+    Network::on_idle(System::block_number(), Weight::MAX);
+
+    let balance = Balances::free_balance(&account(0));
+
+    assert_ok!(
+      Network::claim_balance(
+        RuntimeOrigin::signed(account(0)),
+      )
+    );
+
+    assert_eq!(Balances::free_balance(&account(0)), balance + amount);
+    assert_eq!(ClaimableBalance::<Test>::get(account(0)), 0);
+
+    assert_err!(
+      Network::claim_balance(
+        RuntimeOrigin::signed(account(0)),
+      ),
+      Error::<Test>::NoClaimableBalance
+    );
   });
 }
 
 #[test]
-fn test_reward_subnets_check_balances() {
+fn test_shift_node_classes() {
   new_test_ext().execute_with(|| {
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Idle, 2);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Included, 4);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Submittable, 6);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Accountant, 8);
+
     build_subnet(subnet_path.clone());
     assert_eq!(Network::total_models(), 1);
 
-    make_model_submittable();
-
     let n_peers: u32 = Network::max_subnet_nodes();
 
     let deposit_amount: u128 = 1000000000000000000000000;
-    let amount: u128 = MinStakeBalance::<Test>::get();
+    let amount: u128 = 1000000000000000000000;
     let mut amount_staked: u128 = 0;
 
     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
-    // let epoch_length = EpochLength::get();
-    // System::set_block_number(System::block_number() + epoch_length);
-
-    // let epoch = System::block_number() / epoch_length;
+    System::set_block_number(System::block_number() + CONSENSUS_STEPS);
 
     amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
-    // make_subnet_node_consensus_data_submittable();
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Idle);
+    assert_eq!(node_set.len(), n_peers as usize);
 
     let epoch_length = EpochLength::get();
-    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
-    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
-    Network::shift_node_classes(System::block_number(), epoch_length);
-    let epoch = System::block_number() / epoch_length;
 
-    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+    let last_class_id = SubnetNodeClass::iter().last().unwrap();
 
-    // --- Insert validator
-    SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
+    let starting_block = System::block_number();
 
-    assert_ok!(
-      Network::validate(
-        RuntimeOrigin::signed(account(0)), 
-        subnet_id.clone(),
-        subnet_node_data_vec.clone()
-      )
-    );
+    for class_id in SubnetNodeClass::iter() {
+      if class_id == last_class_id {
+        continue;
+      }
+      log::error!("test class_id {:?}", class_id);
 
-    // Attest
-    for n in 1..n_peers {
-      assert_ok!(
-        Network::attest(
-          RuntimeOrigin::signed(account(n)), 
-          subnet_id.clone(),
-        )
-      );
+
+      let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), class_id);
+      assert_eq!(node_set.len(), n_peers as usize);
+
+      let epochs = SubnetNodeClassEpochs::<Test>::get(class_id.clone());
+      System::set_block_number(starting_block + epochs * epoch_length + 1);
+
+      Network::shift_node_classes(System::block_number(), epoch_length);
     }
-    
-    let delegate_stake_balance: u128 = TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone());
+  })
+}
 
-    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+#[test]
+fn test_shift_node_classes_promotion_buckets() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
-    let post_delegate_stake_balance: u128 = TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id.clone());
-    assert!(post_delegate_stake_balance > delegate_stake_balance, "Delegate stake balance should increase");
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Idle, 2);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Included, 4);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Submittable, 6);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Accountant, 8);
 
-    let submission = SubnetRewardsSubmission::<Test>::get(subnet_id.clone(), epoch as u32).unwrap();
+    build_subnet(subnet_path.clone());
 
-    let base_subnet_reward: u128 = BaseSubnetReward::<Test>::get();
-    let delegate_stake_rewards_percentage: u128 = DelegateStakeRewardsPercentage::<Test>::get();
-    let subnet_reward: u128 = Network::percent_mul(base_subnet_reward, delegate_stake_rewards_percentage);
+    let n_peers: u32 = Network::max_subnet_nodes();
 
-    let reward_ratio: u128 = Network::percent_div(DEFAULT_SCORE, submission.sum);
-    let account_reward: u128 = Network::percent_mul(reward_ratio, subnet_reward);
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
 
-    let base_reward = BaseReward::<Test>::get();
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
-    // check each subnet nodes balance increased
-    for n in 0..n_peers {
-      if n == 0 {
-        // validator
-        let stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone());
-        assert!(stake_balance == amount + account_reward + base_reward, "Invalid validator staking rewards")  
-      } else {
-        // attestors
-        let stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(n), subnet_id.clone());
-        assert!(stake_balance == amount + account_reward, "Invalid subnet node staking rewards")  
-      }
-    }
-  });
+    System::set_block_number(System::block_number() + CONSENSUS_STEPS);
+
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    let epoch_length = EpochLength::get();
+    let starting_block = System::block_number();
+    let idle_epoch = starting_block / epoch_length + 2;
+
+    assert_eq!(SubnetNodeClassCount::<Test>::get(subnet_id, SubnetNodeClass::Idle), n_peers);
+    assert_eq!(SubnetNodeClassPromotions::<Test>::get(subnet_id, idle_epoch).len(), n_peers as usize);
+
+    let classification = SubnetNodesClassification::<Test>::get(subnet_id, account(0)).unwrap();
+    assert_eq!(classification.class, SubnetNodeClass::Idle);
+    assert_eq!(classification.classified, starting_block);
+    assert_eq!(classification.promotion_epoch, Some(idle_epoch));
+
+    // --- Removed subnet nodes are skipped when their epoch is shifted
+    Network::do_remove_subnet_node(
+      System::block_number(),
+      subnet_id,
+      account(0),
+      SubnetNodeRemovalReason::SelfRemoved,
+    );
+    assert_eq!(SubnetNodeClassCount::<Test>::get(subnet_id, SubnetNodeClass::Idle), n_peers - 1);
+
+    // --- Epochs before the scheduled epoch don't shift any subnet nodes
+    System::set_block_number(starting_block + epoch_length);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+    assert_eq!(Network::get_classified_subnet_nodes_count(subnet_id, SubnetNodeClass::Included), 0);
+
+    System::set_block_number(starting_block + 2 * epoch_length);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+
+    assert_eq!(SubnetNodeClassShiftEpoch::<Test>::get(subnet_id), Some(idle_epoch));
+    assert_eq!(SubnetNodeClassPromotions::<Test>::get(subnet_id, idle_epoch).len(), 0);
+    assert_eq!(SubnetNodeClassCount::<Test>::get(subnet_id, SubnetNodeClass::Idle), 0);
+    assert_eq!(SubnetNodeClassCount::<Test>::get(subnet_id, SubnetNodeClass::Included), n_peers - 1);
+    assert_eq!(Network::get_classified_subnet_nodes_count(subnet_id, SubnetNodeClass::Idle), n_peers - 1);
+    assert_eq!(SubnetNodesClassification::<Test>::get(subnet_id, account(0)), None);
+
+    // --- Included subnet nodes are scheduled from the classified block
+    let classification = SubnetNodesClassification::<Test>::get(subnet_id, account(1)).unwrap();
+    assert_eq!(classification.class, SubnetNodeClass::Included);
+    assert_eq!(classification.promotion_epoch, Some(starting_block / epoch_length + 4));
+    assert_eq!(
+      SubnetNodeClassPromotions::<Test>::get(subnet_id, starting_block / epoch_length + 4).len(), 
+      (n_peers - 1) as usize
+    );
+  })
 }
 
 #[test]
-fn test_reward_subnets_validator_slash() {
+fn test_shift_node_classes_subnet_override() {
   new_test_ext().execute_with(|| {
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
-    build_subnet(subnet_path.clone());
-    assert_eq!(Network::total_models(), 1);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Idle, 2);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Included, 4);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Submittable, 6);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Accountant, 8);
 
-    make_model_submittable();
+    build_subnet(subnet_path.clone());
 
     let n_peers: u32 = Network::max_subnet_nodes();
 
     let deposit_amount: u128 = 1000000000000000000000000;
     let amount: u128 = 1000000000000000000000;
-    let mut amount_staked: u128 = 0;
 
     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
-    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+    assert_err!(
+      Network::set_subnet_node_class_epochs_override(
+        subnet_id + 1, 
+        Vec::new()
+      ),
+      Error::<Test>::SubnetNotExist
+    );
 
-    make_subnet_node_consensus_data_submittable();
+    // --- Classes must be set once and ordered
+    assert_err!(
+      Network::set_subnet_node_class_epochs_override(
+        subnet_id, 
+        vec![
+          (SubnetNodeClass::Idle, 1), 
+          (SubnetNodeClass::Included, 1), 
+          (SubnetNodeClass::Submittable, 3), 
+          (SubnetNodeClass::Accountant, 4)
+        ]
+      ),
+      Error::<Test>::InvalidSubnetNodeClassEpochs
+    );
+
+    assert_err!(
+      Network::set_subnet_node_class_epochs_override(
+        subnet_id, 
+        vec![
+          (SubnetNodeClass::Idle, 1), 
+          (SubnetNodeClass::Idle, 2), 
+          (SubnetNodeClass::Submittable, 3), 
+          (SubnetNodeClass::Accountant, 4)
+        ]
+      ),
+      Error::<Test>::InvalidSubnetNodeClassEpochs
+    );
+
+    assert_ok!(
+      Network::set_subnet_node_class_epochs_override(
+        subnet_id, 
+        vec![
+          (SubnetNodeClass::Accountant, 4),
+          (SubnetNodeClass::Idle, 1), 
+          (SubnetNodeClass::Included, 2), 
+          (SubnetNodeClass::Submittable, 3), 
+        ]
+      )
+    );
+
+    assert_eq!(Network::get_subnet_node_class_epochs(subnet_id, SubnetNodeClass::Idle), 1);
+    assert_eq!(Network::get_subnet_node_class_epochs(subnet_id, SubnetNodeClass::Accountant), 4);
+
+    System::set_block_number(System::block_number() + CONSENSUS_STEPS);
+
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
     let epoch_length = EpochLength::get();
-    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
-    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+
+    let starting_block = System::block_number();
+
+    // --- The subnets Idle epochs are used in place of the networks
+    System::set_block_number(starting_block + epoch_length + 1);
+
     Network::shift_node_classes(System::block_number(), epoch_length);
-    let epoch = System::block_number() / epoch_length;
 
-    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Included);
+    assert_eq!(node_set.len(), n_peers as usize);
 
-    // --- Insert validator
-    SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
+    // --- Removing the override falls back to the networks epochs
+    assert_ok!(Network::set_subnet_node_class_epochs_override(subnet_id, Vec::new()));
+    assert_eq!(Network::get_subnet_node_class_epochs(subnet_id, SubnetNodeClass::Included), 4);
 
-    assert_ok!(
-      Network::validate(
-        RuntimeOrigin::signed(account(0)), 
-        subnet_id.clone(),
-        subnet_node_data_vec.clone()
-      )
-    );
+    System::set_block_number(starting_block + 2 * epoch_length + 1);
 
-    // No attests to ensure validator is slashed
-    
-    let validator_stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(0), subnet_id.clone());
+    Network::shift_node_classes(System::block_number(), epoch_length);
 
-    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Submittable);
+    assert_eq!(node_set.len(), 0);
+  })
+}
 
-    let slashed_validator_stake_balance: u128 = AccountSubnetStake::<Test>::get(&account(0), subnet_id.clone());
+#[test]
+fn test_set_subnet_node_class_epochs() {
+  new_test_ext().execute_with(|| {
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Idle, 2);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Included, 4);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Submittable, 6);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Accountant, 8);
 
-    // Ensure validator was slashed
-    assert!(validator_stake_balance > slashed_validator_stake_balance, "Validator was not slashed")
-  });
+    assert_ok!(Network::set_subnet_node_class_epochs(SubnetNodeClass::Included, 5));
+    assert_eq!(SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Included), 5);
+
+    assert_err!(
+      Network::set_subnet_node_class_epochs(SubnetNodeClass::Included, 6),
+      Error::<Test>::InvalidSubnetNodeClassEpochs
+    );
+
+    assert_err!(
+      Network::set_subnet_node_class_epochs(SubnetNodeClass::Accountant, 1),
+      Error::<Test>::InvalidSubnetNodeClassEpochs
+    );
+
+    // --- Activation proposals are checked against the same ordering
+    let add_subnet_data = PreSubnetData {
+      path: "petals-team/StableBeluga2".into(),
+      memory_mb: 50000,
+      class_epochs: vec![
+        (SubnetNodeClass::Idle, 4), 
+        (SubnetNodeClass::Included, 2), 
+        (SubnetNodeClass::Submittable, 6), 
+        (SubnetNodeClass::Accountant, 8)
+      ],
+    };
+    assert_err!(
+      Network::activate_subnet(
+        account(0),
+        account(0),
+        add_subnet_data,
+      ),
+      Error::<Test>::InvalidSubnetNodeClassEpochs
+    );
+  })
 }
 
 #[test]
-fn test_reward_subnets_subnet_penalty_count() {
+fn test_demote_subnet_node() {
   new_test_ext().execute_with(|| {
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
-    build_subnet(subnet_path.clone());
-    assert_eq!(Network::total_models(), 1);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Idle, 2);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Included, 4);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Submittable, 6);
+    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Accountant, 8);
 
-    make_model_submittable();
+    build_subnet(subnet_path.clone());
 
     let n_peers: u32 = Network::max_subnet_nodes();
 
     let deposit_amount: u128 = 1000000000000000000000000;
     let amount: u128 = 1000000000000000000000;
-    let mut amount_staked: u128 = 0;
 
     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
-    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+    System::set_block_number(System::block_number() + CONSENSUS_STEPS);
 
-    make_subnet_node_consensus_data_submittable();
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
     let epoch_length = EpochLength::get();
-    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
-    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+
+    let starting_block = System::block_number();
+
+    System::set_block_number(starting_block + 4 * epoch_length + 1);
+
     Network::shift_node_classes(System::block_number(), epoch_length);
-    let epoch = System::block_number() / epoch_length;
 
-    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Submittable);
+    assert_eq!(node_set.len(), n_peers as usize);
 
-    // --- Insert validator
-    SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
+    let demotion_block = System::block_number();
 
-    assert_ok!(
-      Network::validate(
-        RuntimeOrigin::signed(account(0)), 
-        subnet_id.clone(),
-        Vec::new()
+    Network::demote_subnet_node(subnet_id, &account(0), SubnetNodeDemotionReason::SequentialAbsent);
+
+    System::assert_last_event(
+      RuntimeEvent::Network(
+        crate::Event::SubnetNodeDemoted {
+          subnet_id: subnet_id,
+          account_id: account(0),
+          class: SubnetNodeClass::Included,
+          reason: SubnetNodeDemotionReason::SequentialAbsent,
+          block: demotion_block,
+        }
       )
     );
 
-    // Attest
-    for n in 1..n_peers {
-      assert_ok!(
-        Network::attest(
-          RuntimeOrigin::signed(account(n)), 
-          subnet_id.clone(),
-        )
-      );
-    }
-    
-    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Submittable);
+    assert_eq!(node_set.len(), (n_peers - 1) as usize);
+    assert!(!node_set.contains_key(&account(0)));
+
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Included);
+    assert!(node_set.contains_key(&account(0)));
+
+    let (class, next_class, next_class_block) = Network::get_subnet_node_class_info(
+      subnet_id, 
+      &account(0), 
+      demotion_block, 
+      epoch_length
+    );
+    assert_eq!(class, Some(SubnetNodeClass::Included));
+    assert_eq!(next_class, Some(SubnetNodeClass::Submittable));
+    assert_eq!(next_class_block, Some(demotion_block - (demotion_block % epoch_length) + 2 * epoch_length));
+
+    // --- The subnet node must serve the Included class epochs again
+    System::set_block_number(demotion_block + epoch_length);
+
+    Network::shift_node_classes(System::block_number(), epoch_length);
+
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Submittable);
+    assert!(!node_set.contains_key(&account(0)));
+
+    System::set_block_number(demotion_block + 2 * epoch_length);
+
+    Network::shift_node_classes(System::block_number(), epoch_length);
+
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Submittable);
+    assert!(node_set.contains_key(&account(0)));
 
-    let subnet_penalty_count = SubnetPenaltyCount::<Test>::get(subnet_id.clone());
-    assert_eq!(subnet_penalty_count, 1);
+    // --- Subnet nodes are demoted one class at a time down to Idle
+    Network::demote_subnet_node(subnet_id, &account(1), SubnetNodeDemotionReason::AccountPenalties);
+    Network::demote_subnet_node(subnet_id, &account(1), SubnetNodeDemotionReason::AccountPenalties);
+    Network::demote_subnet_node(subnet_id, &account(1), SubnetNodeDemotionReason::AccountPenalties);
 
-    let account_penalty_count = AccountPenaltyCount::<Test>::get(account(0));
-    assert_eq!(account_penalty_count, 0);
-  });
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Idle);
+    assert!(node_set.contains_key(&account(1)));
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Included);
+    assert!(!node_set.contains_key(&account(1)));
+  })
 }
 
 #[test]
-fn test_reward_subnets_account_penalty_count() {
+fn test_declassify_subnet_node_on_max_account_penalties() {
   new_test_ext().execute_with(|| {
     let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
 
     build_subnet(subnet_path.clone());
-    assert_eq!(Network::total_models(), 1);
-
-    make_model_submittable();
 
     let n_peers: u32 = Network::max_subnet_nodes();
 
     let deposit_amount: u128 = 1000000000000000000000000;
     let amount: u128 = 1000000000000000000000;
-    let mut amount_staked: u128 = 0;
 
     let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
 
-    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
-
-    make_subnet_node_consensus_data_submittable();
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
 
     let epoch_length = EpochLength::get();
     let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
     System::set_block_number(System::block_number() + epochs * epoch_length + 1);
     Network::shift_node_classes(System::block_number(), epoch_length);
-    let epoch = System::block_number() / epoch_length;
 
-    let subnet_node_data_vec = subnet_node_data(0, n_peers);
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Submittable);
+    assert!(node_set.contains_key(&account(0)));
 
-    // --- Insert validator
-    SubnetRewardsValidator::<Test>::insert(subnet_id, epoch as u32, account(0));
+    // --- Reaching the max penalties keeps the subnet node classified
+    let max_penalties = MaxAccountPenaltyCount::<Test>::get();
+    AccountPenaltyCount::<Test>::insert(account(0), max_penalties - 1);
 
-    assert_ok!(
-      Network::validate(
-        RuntimeOrigin::signed(account(0)), 
-        subnet_id.clone(),
-        Vec::new()
-      )
-    );
+    Network::slash_validator(subnet_id, account(0), 0, ValidatorSlashReason::MissedSubmission);
 
-    // No Attest
+    assert_eq!(AccountPenaltyCount::<Test>::get(account(0)), max_penalties);
+    assert!(SubnetNodesClassification::<Test>::get(subnet_id, account(0)).is_some());
 
-    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+    // --- Exceeding the max penalties declassifies the subnet node without waiting on a promotion
+    Network::slash_validator(subnet_id, account(0), 0, ValidatorSlashReason::MissedSubmission);
 
-    let subnet_penalty_count = SubnetPenaltyCount::<Test>::get(subnet_id.clone());
-    assert_eq!(subnet_penalty_count, 1);
+    assert_eq!(AccountPenaltyCount::<Test>::get(account(0)), max_penalties + 1);
+    assert_eq!(SubnetNodesClassification::<Test>::get(subnet_id, account(0)), None);
 
-    let account_penalty_count = AccountPenaltyCount::<Test>::get(account(0));
-    assert_eq!(account_penalty_count, 1);
-  });
+    let node_set = Network::get_classified_subnet_nodes(subnet_id.clone(), SubnetNodeClass::Submittable);
+    assert!(!node_set.contains_key(&account(0)));
+    assert!(node_set.contains_key(&account(1)));
+  })
 }
 
 #[test]
-fn test_shift_node_classes() {
+fn test_set_node_class_demotion_thresholds() {
   new_test_ext().execute_with(|| {
-    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
-
-    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Idle, 2);
-    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Included, 4);
-    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Submittable, 6);
-    SubnetNodeClassEpochs::<Test>::insert(SubnetNodeClass::Accountant, 8);
-
-    build_subnet(subnet_path.clone());
-    assert_eq!(Network::total_models(), 1);
-
-    let n_peers: u32 = Network::max_subnet_nodes();
-
-    let deposit_amount: u128 = 1000000000000000000000000;
-    let amount: u128 = 1000000000000000000000;
-    let mut amount_staked: u128 = 0;
-
-    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
-
-    System::set_block_number(System::block_number() + CONSENSUS_STEPS);
+    let max_absent = MaxSequentialAbsentSubnetNode::<Test>::get();
+    let max_penalties = MaxAccountPenaltyCount::<Test>::get();
 
-    amount_staked = build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+    assert_ok!(Network::set_node_class_demotion_absent_threshold(max_absent));
+    assert_eq!(NodeClassDemotionAbsentThreshold::<Test>::get(), max_absent);
 
-    let node_set = SubnetNodesClasses::<Test>::get(subnet_id.clone(), SubnetNodeClass::Idle);
-    assert_eq!(node_set.len(), n_peers as usize);
+    assert_err!(
+      Network::set_node_class_demotion_absent_threshold(0),
+      Error::<Test>::InvalidNodeClassDemotionAbsentThreshold
+    );
 
-    let epoch_length = EpochLength::get();
+    assert_err!(
+      Network::set_node_class_demotion_absent_threshold(max_absent + 1),
+      Error::<Test>::InvalidNodeClassDemotionAbsentThreshold
+    );
 
-    let last_class_id = SubnetNodeClass::iter().last().unwrap();
+    assert_ok!(Network::set_node_class_demotion_penalty_threshold(max_penalties));
+    assert_eq!(NodeClassDemotionPenaltyThreshold::<Test>::get(), max_penalties);
 
-    let starting_block = System::block_number();
+    assert_err!(
+      Network::set_node_class_demotion_penalty_threshold(max_penalties + 1),
+      Error::<Test>::InvalidNodeClassDemotionPenaltyThreshold
+    );
 
-    for class_id in SubnetNodeClass::iter() {
-      if class_id == last_class_id {
-        continue;
-      }
-      log::error!("test class_id {:?}", class_id);
+    assert!(!Network::is_node_class_demotion_threshold(0, 2));
+    assert!(!Network::is_node_class_demotion_threshold(1, 2));
+    assert!(Network::is_node_class_demotion_threshold(2, 2));
+    assert!(!Network::is_node_class_demotion_threshold(3, 2));
+    assert!(Network::is_node_class_demotion_threshold(4, 2));
+  })
+}
 
+#[test]
+fn test_set_base_subnet_reward_and_slash_percentage() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Network::set_base_subnet_reward(1e+18 as u128));
+    assert_eq!(BaseSubnetReward::<Test>::get(), 1e+18 as u128);
 
-      let node_set = SubnetNodesClasses::<Test>::get(subnet_id.clone(), class_id);
-      assert_eq!(node_set.len(), n_peers as usize);
+    assert_err!(
+      Network::set_base_subnet_reward(0),
+      Error::<Test>::InvalidBaseSubnetReward
+    );
 
-      let epochs = SubnetNodeClassEpochs::<Test>::get(class_id.clone());
-      System::set_block_number(starting_block + epochs * epoch_length + 1);
+    assert_ok!(Network::set_slash_percentage(PERCENTAGE_FACTOR));
+    assert_eq!(SlashPercentage::<Test>::get(), PERCENTAGE_FACTOR);
 
-      Network::shift_node_classes(System::block_number(), epoch_length);
-    }
+    assert_err!(
+      Network::set_slash_percentage(PERCENTAGE_FACTOR + 1),
+      Error::<Test>::InvalidSlashPercentage
+    );
   })
 }
 
@@ -6360,6 +8854,255 @@ fn test_get_subnet_nodes_included() {
   })
 }
 
+#[test]
+fn test_get_account_info() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+    build_subnet_nodes(subnet_id.clone(), 0, 1, deposit_amount, amount);
+
+    assert_ok!(
+      Network::add_to_delegate_stake(
+        RuntimeOrigin::signed(account(0)),
+        subnet_id.clone(),
+        amount,
+      ) 
+    );
+
+    let account_info = Network::get_account_info(account(0));
+    assert_eq!(account_info.account_subnets, vec![subnet_id]);
+    assert_eq!(account_info.total_stake, amount);
+    assert_eq!(account_info.penalty_count, 0);
+    assert_eq!(account_info.subnets.len(), 1);
+
+    let subnet_info = account_info.subnets[0].clone();
+    assert_eq!(subnet_info.subnet_id, subnet_id);
+    assert_eq!(subnet_info.stake, amount);
+    assert_eq!(subnet_info.delegate_stake_shares, amount - 1000);
+    assert_eq!(subnet_info.delegate_stake_balance, amount - 1000);
+    assert_eq!(subnet_info.class, Some(SubnetNodeClass::Idle));
+    assert_eq!(subnet_info.next_class, Some(SubnetNodeClass::Included));
+
+    let epoch_length = EpochLength::get();
+    let initialized = SubnetNodesData::<Test>::get(subnet_id, account(0)).initialized;
+    let next_class_block = Network::get_eligible_epoch_block(
+      epoch_length,
+      initialized,
+      SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Idle)
+    );
+    assert_eq!(subnet_info.next_class_block, Some(next_class_block));
+
+    // --- Subnet node is in the next class at the next class block
+    System::set_block_number(next_class_block);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+
+    let subnet_info = Network::get_account_info(account(0)).subnets[0].clone();
+    assert_eq!(subnet_info.class, Some(SubnetNodeClass::Included));
+    assert_eq!(subnet_info.next_class, Some(SubnetNodeClass::Submittable));
+
+    // --- Delegate stakers without a subnet node have no class
+    let _ = Balances::deposit_creating(&account(1), deposit_amount);
+    assert_ok!(
+      Network::add_to_delegate_stake(
+        RuntimeOrigin::signed(account(1)),
+        subnet_id.clone(),
+        amount,
+      ) 
+    );
+
+    let account_info = Network::get_account_info(account(1));
+    assert_eq!(account_info.account_subnets, Vec::<u32>::new());
+    assert_eq!(account_info.subnets.len(), 1);
+    assert_eq!(account_info.subnets[0].class, None);
+    assert_eq!(account_info.subnets[0].next_class_block, None);
+  });
+}
+
+#[test]
+fn test_get_subnet_epoch_info() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    let deposit_amount: u128 = 10000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, 2, deposit_amount, amount);
+
+    let epoch_length = EpochLength::get();
+    let epoch: u32 = 5;
+    let start_block: u64 = epoch as u64 * epoch_length;
+
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch, vec![account(0)]);
+    let mut accountants: BTreeMap<AccountIdOf<Test>, bool> = BTreeMap::new();
+    accountants.insert(account(1), false);
+    CurrentAccountants::<Test>::insert(subnet_id, epoch, accountants);
+
+    // --- Roles are not assigned on the first block of the epoch
+    System::set_block_number(start_block);
+
+    let epoch_info = Network::get_subnet_epoch_info(subnet_id, account(0));
+    assert_eq!(epoch_info.block, start_block);
+    assert_eq!(epoch_info.in_consensus_steps, true);
+    assert_eq!(epoch_info.can_remove_or_update_subnet_node, false);
+    assert_eq!(epoch_info.current_roles.assigned, false);
+
+    let remove_or_update_end_block: u64 = start_block + Network::percent_mul(
+      epoch_length as u128,
+      RemoveSubnetNodeEpochPercentage::<Test>::get()
+    ) as u64;
+
+    assert_eq!(
+      epoch_info.current_epoch,
+      EpochSchedule {
+        epoch: epoch,
+        start_block: start_block,
+        end_block: start_block + epoch_length - 1,
+        roles_assigned_block: start_block + 1,
+        consensus_steps_end_block: start_block + CONSENSUS_STEPS - 1,
+        remove_or_update_start_block: start_block + CONSENSUS_STEPS,
+        remove_or_update_end_block: remove_or_update_end_block,
+        submit_consensus_start_block: remove_or_update_end_block + 1,
+        consensus_deadline_block: start_block + epoch_length - 1,
+      }
+    );
+    assert_eq!(epoch_info.next_epoch.epoch, epoch + 1);
+    assert_eq!(epoch_info.next_epoch.start_block, start_block + epoch_length);
+
+    // --- Roles are assigned once validators and accountants are chosen
+    System::set_block_number(start_block + CONSENSUS_STEPS);
+
+    let epoch_info = Network::get_subnet_epoch_info(subnet_id, account(0));
+    assert_eq!(epoch_info.in_consensus_steps, false);
+    assert_eq!(epoch_info.can_remove_or_update_subnet_node, true);
+    assert_eq!(epoch_info.can_submit_consensus, false);
+    assert_eq!(
+      epoch_info.current_roles,
+      EpochRoles {
+        epoch: epoch,
+        assigned: true,
+        is_validator: true,
+        is_accountant: false,
+      }
+    );
+    assert_eq!(epoch_info.next_roles.assigned, false);
+
+    let epoch_info = Network::get_subnet_epoch_info(subnet_id, account(1));
+    assert_eq!(epoch_info.current_roles.is_validator, false);
+    assert_eq!(epoch_info.current_roles.is_accountant, true);
+
+    System::set_block_number(remove_or_update_end_block + 1);
+
+    let epoch_info = Network::get_subnet_epoch_info(subnet_id, account(1));
+    assert_eq!(epoch_info.can_remove_or_update_subnet_node, false);
+    assert_eq!(epoch_info.can_submit_consensus, true);
+  });
+}
+
+#[test]
+fn test_get_reward_projection() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+
+    build_subnet(subnet_path.clone());
+
+    make_model_submittable();
+
+    let n_peers: u32 = Network::max_subnet_nodes();
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = 1000000000000000000000;
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    build_subnet_nodes(subnet_id.clone(), 0, n_peers, deposit_amount, amount);
+
+    make_subnet_node_consensus_data_submittable();
+
+    let epoch_length = EpochLength::get();
+    let epochs = SubnetNodeClassEpochs::<Test>::get(SubnetNodeClass::Accountant);
+    System::set_block_number(System::block_number() + epochs * epoch_length + 1);
+    Network::shift_node_classes(System::block_number(), epoch_length);
+    let epoch = System::block_number() / epoch_length;
+
+    SubnetRewardsValidators::<Test>::insert(subnet_id, epoch as u32, vec![account(0)]);
+
+    assert_ok!(
+      Network::validate(
+        RuntimeOrigin::signed(account(0)), 
+        subnet_id.clone(),
+        subnet_node_data(0, n_peers)
+      )
+    );
+
+    for n in 1..n_peers {
+      assert_ok!(
+        Network::attest(
+          RuntimeOrigin::signed(account(n)), 
+          subnet_id.clone(),
+        )
+      );
+    }
+
+    // --- Project on the following epoch so the submission is the previous epochs data
+    System::set_block_number(System::block_number() + epoch_length);
+
+    let submission = SubnetRewardsSubmission::<Test>::get(subnet_id, epoch as u32);
+
+    let projection = Network::get_reward_projection(subnet_id, account(1), amount, amount);
+    assert_eq!(projection.epoch, epoch as u32);
+    assert_eq!(projection.stake, amount + amount);
+    assert_eq!(projection.delegate_stake_balance, amount);
+    assert_eq!(projection.epochs_per_year, YEAR as u64 / epoch_length);
+    assert_eq!(projection.epoch_secs, epoch_length * SECS_PER_BLOCK);
+    assert_eq!(projection.year_reward, projection.epoch_reward * projection.epochs_per_year as u128);
+    assert!(projection.epoch_reward > 0);
+    // --- Compounding every epoch yields at least the annualized rewards
+    assert!(projection.apy >= Network::percent_div(projection.year_reward, amount + amount + amount));
+    assert_eq!(
+      projection.stake_vault_subsidy,
+      BlockStakeVaultSubsidy::get() * (YEAR as u128 / 365) * 365
+    );
+
+    // --- The projection doesn't change storage
+    assert_eq!(AccountSubnetStake::<Test>::get(account(1), subnet_id), amount);
+    assert_eq!(AccountSubnetDelegateStakeShares::<Test>::get(account(1), subnet_id), 0);
+    assert_eq!(TotalSubnetDelegateStakeBalance::<Test>::get(subnet_id), 0);
+    assert_eq!(SubnetRewardsSubmission::<Test>::get(subnet_id, epoch as u32), submission);
+
+    // --- Only the validator is projected the validator reward
+    assert_eq!(projection.validator_reward, 0);
+
+    // --- Projected stake rewards match the rewards of the epoch
+    let projection = Network::get_reward_projection(subnet_id, account(1), 0, 0);
+    assert!(projection.stake_reward > 0);
+
+    let validator_projection = Network::get_reward_projection(subnet_id, account(0), 0, 0);
+    assert!(validator_projection.validator_reward > 0);
+    assert_eq!(validator_projection.stake_reward, projection.stake_reward + validator_projection.validator_reward);
+
+    Network::reward_subnets(System::block_number(), epoch as u32, epoch_length);
+
+    assert_eq!(
+      AccountSubnetStake::<Test>::get(account(1), subnet_id),
+      amount + projection.stake_reward
+    );
+    assert_eq!(
+      AccountSubnetStake::<Test>::get(account(0), subnet_id),
+      amount + validator_projection.stake_reward
+    );
+  });
+}
+
 #[test]
 fn test_propose() {
 	new_test_ext().execute_with(|| {
@@ -6812,3 +9555,108 @@ fn test_proposal_voting() {
     );
   })
 }
+
+
+#[test]
+fn test_vote_activated_bootstrap_subnet_nodes() {
+  new_test_ext().execute_with(|| {
+    let subnet_path: Vec<u8> = "petals-team/StableBeluga2".into();
+    let cost = Network::get_model_initialization_cost(0);
+    let _ = Balances::deposit_creating(&account(0), cost+1000);
+
+    let deposit_amount: u128 = 1000000000000000000000000;
+    let amount: u128 = get_min_stake_balance();
+    let n_peers: u32 = MinSubnetNodes::<Test>::get();
+
+    let mut subnet_nodes: Vec<BootstrapSubnetNode<AccountIdOf<Test>>> = Vec::new();
+    for n in 0..n_peers {
+      let _ = Balances::deposit_creating(&account(n), deposit_amount);
+      subnet_nodes.push(BootstrapSubnetNode {
+        account_id: account(n),
+        peer_id: peer(n),
+        stake: amount,
+      });
+    }
+
+    let vote_subnet_data = VoteSubnetData {
+      data: PreSubnetData {
+        path: subnet_path.clone(),
+        memory_mb: 50000,
+        class_epochs: Vec::new(),
+      },
+      active: true,
+    };
+
+    let total_subnets = TotalSubnets::<Test>::get();
+    let balances: Vec<u128> = (0..n_peers).map(|n| Balances::free_balance(&account(n))).collect();
+
+    // --- A failed activation doesn't stake any of the nodes
+    let max_subnets = MaxSubnets::<Test>::get();
+    MaxSubnets::<Test>::set(0);
+
+    assert_err!(
+      <Network as SubnetVote<AccountIdOf<Test>>>::vote_activated(
+        account(0),
+        subnet_path.clone(),
+        account(0),
+        vote_subnet_data.clone(),
+        subnet_nodes.clone(),
+      ),
+      Error::<Test>::MaxSubnets
+    );
+
+    assert_eq!(SubnetPaths::<Test>::get(subnet_path.clone()), None);
+    assert_eq!(TotalSubnets::<Test>::get(), total_subnets);
+    for n in 0..n_peers {
+      assert_eq!(Balances::free_balance(&account(n)), balances[n as usize]);
+    }
+
+    MaxSubnets::<Test>::set(max_subnets);
+
+    // --- A node without the balance to stake is skipped
+    System::set_block_number(System::block_number() + 1);
+
+    let mut bootstrap_subnet_nodes = subnet_nodes.clone();
+    bootstrap_subnet_nodes.push(BootstrapSubnetNode {
+      account_id: account(n_peers),
+      peer_id: peer(n_peers),
+      stake: amount,
+    });
+
+    assert_ok!(
+      <Network as SubnetVote<AccountIdOf<Test>>>::vote_activated(
+        account(0),
+        subnet_path.clone(),
+        account(0),
+        vote_subnet_data.clone(),
+        bootstrap_subnet_nodes,
+      )
+    );
+
+    let subnet_id = SubnetPaths::<Test>::get(subnet_path.clone()).unwrap();
+
+    System::assert_has_event(
+      RuntimeEvent::Network(
+        crate::Event::BootstrapSubnetNodeSkipped {
+          subnet_id: subnet_id,
+          account_id: account(n_peers),
+          peer_id: peer(n_peers),
+          error: Error::<Test>::NotEnoughBalanceToStake.into(),
+        }
+      )
+    );
+    assert_eq!(SubnetNodesData::<Test>::contains_key(subnet_id, account(n_peers)), false);
+    assert_eq!(TotalSubnetNodes::<Test>::get(subnet_id), n_peers);
+    assert_eq!(TotalSubnetStake::<Test>::get(subnet_id), amount * n_peers as u128);
+
+    let node_set = Network::get_classified_subnet_nodes(subnet_id, SubnetNodeClass::Idle);
+    assert_eq!(node_set.len(), n_peers as usize);
+
+    for n in 0..n_peers {
+      let subnet_node_data = SubnetNodesData::<Test>::get(subnet_id, account(n));
+      assert_eq!(subnet_node_data.peer_id, peer(n));
+      assert_eq!(SubnetNodeAccount::<Test>::get(subnet_id, peer(n)), account(n));
+      assert_eq!(AccountSubnetStake::<Test>::get(account(n), subnet_id), amount);
+    }
+  })
+}
\ No newline at end of file
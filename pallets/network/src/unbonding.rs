@@ -0,0 +1,151 @@
+// Copyright (C) Hypertensor.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Removed stake and delegate stake balances are held in the unbonding ledger until they mature
+// Subnet node stake remains slashable while unbonding, delegate stake is not
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+  /// Add a removed balance to the accounts unbonding ledger on the subnet
+  // Balances mature after `StakeUnbondingEpochs`
+  pub fn do_add_unbonding(
+    account_id: &T::AccountId,
+    subnet_id: u32,
+    amount: u128,
+    kind: UnbondingKind,
+  ) -> DispatchResult {
+    let block: u64 = Self::get_current_block_as_u64();
+    let epoch: u64 = block / T::EpochLength::get();
+    let unbonding_epoch: u64 = epoch + StakeUnbondingEpochs::<T>::get();
+
+    Unbonding::<T>::try_mutate(account_id, subnet_id, |unbondings: &mut BTreeMap<u64, UnbondingBalance>| -> DispatchResult {
+      // --- Balances maturing on the same epoch are merged into one entry
+      ensure!(
+        unbondings.contains_key(&unbonding_epoch) || (unbondings.len() as u32) < MaxUnbondings::<T>::get(),
+        Error::<T>::MaxUnbondingsReached
+      );
+
+      let unbonding = unbondings.entry(unbonding_epoch).or_default();
+      match kind {
+        UnbondingKind::Stake => unbonding.stake = unbonding.stake.saturating_add(amount),
+        UnbondingKind::DelegateStake => unbonding.delegate_stake = unbonding.delegate_stake.saturating_add(amount),
+      }
+
+      Ok(())
+    })
+  }
+
+  pub fn do_claim_unbonded(
+    account_id: T::AccountId,
+    subnet_id: u32,
+  ) -> DispatchResult {
+    let block: u64 = Self::get_current_block_as_u64();
+    let epoch: u64 = block / T::EpochLength::get();
+
+    let mut unbondings: BTreeMap<u64, UnbondingBalance> = Unbonding::<T>::get(&account_id, subnet_id);
+
+    // --- Split off the unbondings that haven't matured yet
+    let remaining_unbondings: BTreeMap<u64, UnbondingBalance> = unbondings.split_off(&(epoch + 1));
+
+    let unbonded_balance: u128 = unbondings
+      .values()
+      .fold(0, |acc, unbonding| acc.saturating_add(unbonding.stake).saturating_add(unbonding.delegate_stake));
+
+    ensure!(
+      unbonded_balance > 0,
+      Error::<T>::NoUnbondedBalance
+    );
+
+    // --- Ensure that we can convert this u128 to a balance.
+    let unbonded_balance_as_currency = Self::u128_to_balance(unbonded_balance);
+    ensure!(
+      unbonded_balance_as_currency.is_some(),
+      Error::<T>::CouldNotConvertToBalance
+    );
+
+    if remaining_unbondings.is_empty() {
+      Unbonding::<T>::remove(&account_id, subnet_id);
+    } else {
+      Unbonding::<T>::insert(&account_id, subnet_id, remaining_unbondings);
+    }
+
+    Self::add_balance_to_coldkey_account(&account_id, unbonded_balance_as_currency.unwrap());
+
+    Self::deposit_event(Event::UnbondedClaimed(subnet_id, account_id, unbonded_balance));
+
+    Ok(())
+  }
+
+  /// Slash up to `amount` from the accounts unbonding subnet node stake on the subnet
+  // Only unbondings that haven't matured are slashed, the latest first
+  // Returns the amount slashed
+  pub fn slash_unbonding(
+    account_id: &T::AccountId,
+    subnet_id: u32,
+    amount: u128,
+  ) -> u128 {
+    if amount == 0 {
+      return 0
+    }
+
+    let block: u64 = Self::get_current_block_as_u64();
+    let epoch: u64 = block / T::EpochLength::get();
+
+    let mut unbondings: BTreeMap<u64, UnbondingBalance> = Unbonding::<T>::get(account_id, subnet_id);
+    let mut remaining_slash_amount: u128 = amount;
+
+    for (_, unbonding) in unbondings.range_mut(epoch + 1..).rev() {
+      let slash_amount: u128 = unbonding.stake.min(remaining_slash_amount);
+      unbonding.stake -= slash_amount;
+      remaining_slash_amount -= slash_amount;
+      if remaining_slash_amount == 0 {
+        break
+      }
+    }
+
+    unbondings.retain(|_, unbonding| unbonding.stake > 0 || unbonding.delegate_stake > 0);
+
+    if unbondings.is_empty() {
+      Unbonding::<T>::remove(account_id, subnet_id);
+    } else {
+      Unbonding::<T>::insert(account_id, subnet_id, unbondings);
+    }
+
+    amount - remaining_slash_amount
+  }
+
+  pub fn get_unbonding_balance(
+    account_id: &T::AccountId,
+    subnet_id: u32,
+  ) -> u128 {
+    Unbonding::<T>::get(account_id, subnet_id)
+      .values()
+      .fold(0, |acc, unbonding| acc.saturating_add(unbonding.stake).saturating_add(unbonding.delegate_stake))
+  }
+
+  /// Subnet node stake unbonding on the subnet that hasn't matured yet
+  pub fn get_slashable_unbonding_balance(
+    account_id: &T::AccountId,
+    subnet_id: u32,
+  ) -> u128 {
+    let block: u64 = Self::get_current_block_as_u64();
+    let epoch: u64 = block / T::EpochLength::get();
+
+    Unbonding::<T>::get(account_id, subnet_id)
+      .range(epoch + 1..)
+      .fold(0, |acc, (_, unbonding)| acc.saturating_add(unbonding.stake))
+  }
+}
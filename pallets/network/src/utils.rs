@@ -86,8 +86,8 @@ impl<T: Config> Pallet<T> {
   }
 
   // https://stackoverflow.com/a/56883420
-  // interquartile filter
-  fn filter_outliers(values: Vec<u128>) -> Vec<u128> {
+  // interquartile filter, using integer arithmetic only so every node computes the same values
+  pub fn filter_outliers(values: Vec<u128>) -> Vec<u128> {
     if values.len() == 4 {
       let mut final_values: Vec<u128> = Vec::new();
       let mut values: Vec<u128> = values;
@@ -106,18 +106,20 @@ impl<T: Config> Pallet<T> {
     let mut values: Vec<u128> = values;
     values.sort();
 
-    let q1 = Self::get_quantile(values.clone(), 48.0);
-    let q3 = Self::get_quantile(values.clone(), 52.0);
+    let q1 = Self::get_quantile(&values, 25);
+    let q3 = Self::get_quantile(&values, 75);
 
+    // --- 1.5 * iqr
     let iqr = q3 - q1;
-    let max_value = q3 + iqr * 1.5;
-    let min_value = q1 - iqr * 1.5;
+    let fence = iqr.saturating_add(iqr / 2);
+    let max_value = q3.saturating_add(fence);
+    let min_value = q1.saturating_sub(fence);
 
     let values_iter: scale_info::prelude::vec::IntoIter<u128> = values.into_iter();
 
     for value in values_iter {
       // push middle of curve values only
-      if value as f64 >= min_value && value as f64 <= max_value {
+      if value >= min_value && value <= max_value {
         final_values.push(value);
       }
     }
@@ -125,21 +127,41 @@ impl<T: Config> Pallet<T> {
     return final_values
   }
 
-  fn get_quantile(array: Vec<u128>, quantile: f64) -> f64 {
-    // Get the index the quantile is at.
-    let index = quantile / 100.0 * (array.len() as f64 - 1.0);
+  // `quantile` is a percentage of 100 of the sorted `array`, values between two indexes are
+  // interpolated and rounded down
+  fn get_quantile(array: &Vec<u128>, quantile: u128) -> u128 {
+    // Get the index the quantile is at in hundredths.
+    let index = quantile * (array.len() as u128 - 1);
+    let lower_index = (index / 100) as usize;
+    let remainder = index % 100;
 
-    // Check if it has decimal places.
-    if index as f64 % 1.0 == 0.0 {
-      return array[index as usize] as f64;
-    } else {
-      // Get the lower index.
-      let lower_index = index.floor() as f64;
-      // Get the remaining.
-      let remainder = index - lower_index;
-      // Add the remaining to the lowerindex value.
-      return array[lower_index as usize] as f64 + remainder * (array[lower_index as usize + 1] as f64 - array[lower_index as usize] as f64) as f64;
+    if remainder == 0 {
+      return array[lower_index];
     }
+
+    // Add the remaining to the lower index value without overflowing.
+    let lower = array[lower_index];
+    let delta = array[lower_index + 1] - lower;
+    lower + delta / 100 * remainder + delta % 100 * remainder / 100
+  }
+
+  pub fn get_median(values: Vec<u128>) -> u128 {
+    let len = values.len();
+    if len == 0 {
+      return 0
+    }
+
+    let mut values: Vec<u128> = values;
+    values.sort();
+
+    if len % 2 == 1 {
+      return values[len / 2]
+    }
+
+    // --- Average the 2 middle values without overflowing
+    let lower: u128 = values[len / 2 - 1];
+    let upper: u128 = values[len / 2];
+    lower / 2 + upper / 2 + (lower % 2 + upper % 2) / 2
   }
 
   fn get_average(array: Vec<u128>) -> u128 {
@@ -386,14 +408,19 @@ impl<T: Config> Pallet<T> {
   pub fn do_remove_account_subnet_nodes(block: u64, account_id: T::AccountId) {
     let model_ids: Vec<u32> = AccountSubnets::<T>::get(account_id.clone());
     for subnet_id in model_ids.iter() {
-      Self::do_remove_subnet_node(block, *subnet_id, account_id.clone());
+      Self::do_remove_subnet_node(block, *subnet_id, account_id.clone(), SubnetNodeRemovalReason::MaxAccountPenalties);
     }
   }
 
   /// Remove subnet peer from subnet
   // to-do: Add slashing to subnet peers stake balance
   // note: We don't reset AccountPenaltyCount
-  pub fn do_remove_subnet_node(block: u64, subnet_id: u32, account_id: T::AccountId) {
+  pub fn do_remove_subnet_node(
+    block: u64,
+    subnet_id: u32,
+    account_id: T::AccountId,
+    reason: SubnetNodeRemovalReason,
+  ) {
     // Take and remove SubnetNodesData account_id as key
     // `take()` returns and removes data
     if let Ok(subnet_node) = SubnetNodesData::<T>::try_get(subnet_id, account_id.clone()) {
@@ -420,23 +447,51 @@ impl<T: Config> Pallet<T> {
       AccountSubnets::<T>::insert(account_id.clone(), account_model_ids);
 
       // Remove from classifications
-      for class_id in SubnetNodeClass::iter() {
-        let mut node_sets: BTreeMap<T::AccountId, u64> = SubnetNodesClasses::<T>::get(subnet_id, class_id);
-        node_sets.retain(|k, _| *k != account_id.clone());
-        SubnetNodesClasses::<T>::insert(subnet_id, class_id, node_sets);
-      }
+      Self::declassify_subnet_node(subnet_id, &account_id);
 
       Self::deposit_event(
         Event::SubnetNodeRemoved { 
           subnet_id: subnet_id, 
           account_id: account_id.clone(), 
           peer_id: peer_id,
+          reason: reason,
           block: block
         }
       );
     }
   }
 
+  /// Remove a subnet from the network
+  // The remaining subnet data is removed in bounded batches in `on_idle`, see `subnet_cleanup.rs`
+  pub fn do_remove_subnet(
+    block: u64, 
+    subnet_id: u32, 
+    account_id: Option<T::AccountId>, 
+    reason: SubnetRemovalReason
+  ) {
+    let subnet = match SubnetsData::<T>::try_get(subnet_id) {
+      Ok(subnet) => subnet,
+      Err(()) => return,
+    };
+    let subnet_path: Vec<u8> = subnet.path;
+
+    // --- Remove unique path and subnet data
+    // We don't subtract TotalSubnets since it's used for ids
+    SubnetPaths::<T>::remove(subnet_path.clone());
+    SubnetsData::<T>::remove(subnet_id);
+
+    // --- Queue the subnet for cleanup
+    SubnetsToClean::<T>::append(subnet_id);
+
+    Self::deposit_event(Event::SubnetRemoved { 
+      account: account_id, 
+      subnet_id: subnet_id, 
+      subnet_path: subnet_path,
+      reason: reason,
+      block: block
+    });
+  }
+
   // pub fn do_add_subnet_node(
   //   block: u64, 
   //   subnet_id: u32, 
@@ -624,92 +679,255 @@ impl<T: Config> Pallet<T> {
     return subnet_node_account_exists
   }
 
-  /// Shift up subnet nodes to new classifications
-  // This is used to know the len() of each class of subnet nodes instead of iterating through each time
-  pub fn shift_node_classes(block: u64, epoch_length: u64) {
-    for (subnet_id, _) in SubnetsData::<T>::iter() {
-      let class_ids = SubnetNodeClass::iter();
-      let last_class_id = class_ids.clone().last().unwrap();
-      for mut class_id in class_ids {
-        // Can't increase user class after last so skip
-        if class_id == last_class_id {
-          continue;
-        }
+  /// Get the epochs a subnet node must be in a class on a subnet
+  // Uses the subnets override if set, otherwise the global `SubnetNodeClassEpochs`
+  pub fn get_subnet_node_class_epochs(subnet_id: u32, class_id: SubnetNodeClass) -> u64 {
+    match SubnetNodeClassEpochsOverride::<T>::get(subnet_id, class_id) {
+      Some(epochs) => epochs,
+      None => SubnetNodeClassEpochs::<T>::get(class_id),
+    }
+  }
 
-        // If there are none in the class, then skip
-        // let node_sets: BTreeMap<T::AccountId, u64> = SubnetNodesClasses::<T>::get(
-        //   subnet_id, 
-        //   class_id.clone()
-        // )
-        // .ok_or(continue)
-        // .unwrap();
-        let node_sets: BTreeMap<T::AccountId, u64> = SubnetNodesClasses::<T>::get(
-          subnet_id, 
-          class_id.clone()
-        );
+  /// Check a class schedule sets each class once and increases from Idle to Accountant
+  pub fn is_valid_subnet_node_class_epochs(class_epochs: &Vec<(SubnetNodeClass, u64)>) -> bool {
+    if class_epochs.len() != SubnetNodeClass::iter().count() {
+      return false
+    }
 
-        // If initialized but empty, then skip
-        if node_sets.is_empty() {
-          continue;
-        }
-        
-        // --- Get next class to shift into
-        let class_index = class_id.index();
-
-        // --- Safe unwrap from `continue` from last
-        let next_class_id: SubnetNodeClass = SubnetNodeClass::from_repr(class_index + 1).unwrap();
-
-        // --- Copy the node sets for mutation
-        let mut node_sets_copy: BTreeMap<T::AccountId, u64> = node_sets.clone();
-        
-        // --- Get next node sets for mutation or initialize new BTreeMap
-        let mut next_node_sets: BTreeMap<T::AccountId, u64> = match SubnetNodesClasses::<T>::try_get(subnet_id, next_class_id) {
-          Ok(next_node_sets) => next_node_sets,
-          Err(_) => BTreeMap::new(),
+    let mut ordered: Vec<Option<u64>> = vec![None; class_epochs.len()];
+    for (class_id, epochs) in class_epochs.iter() {
+      let index: usize = class_id.index();
+      if ordered[index].is_some() {
+        return false
+      }
+      ordered[index] = Some(*epochs);
+    }
+
+    Self::is_strictly_increasing(&ordered.into_iter().flatten().collect::<Vec<u64>>())
+  }
+
+  pub fn is_strictly_increasing(values: &Vec<u64>) -> bool {
+    values.windows(2).all(|pair| pair[0] < pair[1])
+  }
+
+  /// Returns the subnet nodes in a class mapped to the block they were classified
+  // Classes are cumulative so this includes the subnet nodes in higher classes
+  pub fn get_classified_subnet_nodes(subnet_id: u32, class_id: SubnetNodeClass) -> BTreeMap<T::AccountId, u64> {
+    SubnetNodesClassification::<T>::iter_prefix(subnet_id)
+      .filter(|(_, classification)| classification.class.index() >= class_id.index())
+      .map(|(account_id, classification)| (account_id, classification.classified))
+      .collect()
+  }
+
+  /// Returns the count of subnet nodes in a class including the subnet nodes in higher classes
+  pub fn get_classified_subnet_nodes_count(subnet_id: u32, class_id: SubnetNodeClass) -> u32 {
+    SubnetNodeClass::iter()
+      .filter(|id| id.index() >= class_id.index())
+      .fold(0, |acc, id| acc.saturating_add(SubnetNodeClassCount::<T>::get(subnet_id, id)))
+  }
+
+  /// Returns true if the subnet node is in a class or a higher class
+  pub fn is_subnet_node_class(subnet_id: u32, account_id: &T::AccountId, class_id: SubnetNodeClass) -> bool {
+    match SubnetNodesClassification::<T>::get(subnet_id, account_id) {
+      Some(classification) => classification.class.index() >= class_id.index(),
+      None => false,
+    }
+  }
+
+  /// Classify a subnet node and schedule its shift into the next class
+  // The class epochs are counted from the `classified` block
+  pub fn classify_subnet_node(
+    subnet_id: u32, 
+    account_id: &T::AccountId, 
+    class_id: SubnetNodeClass, 
+    classified: u64
+  ) {
+    let epoch_length: u64 = T::EpochLength::get();
+
+    if let Some(classification) = SubnetNodesClassification::<T>::get(subnet_id, account_id) {
+      SubnetNodeClassCount::<T>::mutate(subnet_id, classification.class, |n: &mut u32| n.saturating_dec());
+    }
+
+    // --- Schedule the shift into the next class, no earlier than the next epoch not yet shifted
+    let promotion_epoch: Option<u64> = match SubnetNodeClass::from_repr(class_id.index() + 1) {
+      Some(_) => {
+        let eligible_block: u64 = Self::get_eligible_epoch_block(
+          epoch_length, 
+          classified, 
+          Self::get_subnet_node_class_epochs(subnet_id, class_id)
+        );
+        let next_shift_epoch: u64 = match SubnetNodeClassShiftEpoch::<T>::get(subnet_id) {
+          Some(shift_epoch) => shift_epoch + 1,
+          None => 0,
         };
+        Some((eligible_block / epoch_length).max(next_shift_epoch))
+      },
+      None => None,
+    };
+
+    if let Some(epoch) = promotion_epoch {
+      SubnetNodeClassPromotions::<T>::append(subnet_id, epoch, account_id.clone());
+    }
+
+    SubnetNodeClassCount::<T>::mutate(subnet_id, class_id, |n: &mut u32| n.saturating_inc());
+
+    SubnetNodesClassification::<T>::insert(
+      subnet_id, 
+      account_id, 
+      SubnetNodeClassification {
+        class: class_id,
+        classified: classified,
+        promotion_epoch: promotion_epoch,
+      }
+    );
+  }
+
+  /// Remove a subnet node from all classes
+  // Its scheduled shift is skipped once its classification no longer exists
+  pub fn declassify_subnet_node(subnet_id: u32, account_id: &T::AccountId) {
+    if let Some(classification) = SubnetNodesClassification::<T>::take(subnet_id, account_id) {
+      SubnetNodeClassCount::<T>::mutate(subnet_id, classification.class, |n: &mut u32| n.saturating_dec());
+    }
+  }
+
+  /// Remove an account from the classes of all of its subnets once its penalties exceed `MaxAccountPenaltyCount`
+  // Called wherever `AccountPenaltyCount` is increased so the account stops serving its class right away
+  pub fn declassify_ineligible_account(account_id: &T::AccountId) {
+    if Self::is_account_eligible(account_id.clone()) {
+      return
+    }
+
+    for subnet_id in AccountSubnets::<T>::get(account_id) {
+      Self::declassify_subnet_node(subnet_id, account_id);
+    }
+  }
 
-        // --- Get epochs required to be in class from the initialization block
-        let epochs = SubnetNodeClassEpochs::<T>::get(class_id.clone());
-
-        for node_set in node_sets.iter() {
-          let account_eligible: bool = Self::is_account_eligible(node_set.0.clone());
-
-          if !account_eligible {
-            next_node_sets.remove(&node_set.0.clone());
-            node_sets_copy.remove(&node_set.0.clone());
-            continue;
-          }
-
-          if let Ok(subnet_node_data) = SubnetNodesData::<T>::try_get(subnet_id, node_set.0.clone()) {
-            let initialized: u64 = subnet_node_data.initialized;
-            if Self::is_epoch_block_eligible(
-              block, 
-              epoch_length, 
-              epochs, 
-              initialized
-            ) {
-              // --- Insert to the next classification, will only insert if doesn't already exist
-              next_node_sets.insert(node_set.0.clone(), *node_set.1);
-            }  
-          } else {
-            // Remove the account from classification if they don't exist anymore
-            node_sets_copy.remove(&node_set.0.clone());
-          }
+  /// Shift up subnet nodes due to be shifted into their next class
+  // Only the subnet nodes scheduled for the epochs since the last shift are read
+  //
+  // Returns the count of subnets and the most subnet nodes shifted in a subnet for weighing the epoch
+  pub fn shift_node_classes(block: u64, epoch_length: u64) -> (u32, u32) {
+    let epoch: u64 = block / epoch_length;
+
+    let mut subnets: u32 = 0;
+    let mut max_promotions: u32 = 0;
+
+    for subnet_id in SubnetsData::<T>::iter_keys() {
+      subnets += 1;
+      let mut promotions: u32 = 0;
+
+      // --- Shift any epochs missed since the last shift
+      let from_epoch: u64 = match SubnetNodeClassShiftEpoch::<T>::get(subnet_id) {
+        Some(shift_epoch) => shift_epoch + 1,
+        None => epoch,
+      };
+
+      for shift_epoch in from_epoch..=epoch {
+        SubnetNodeClassShiftEpoch::<T>::insert(subnet_id, shift_epoch);
+
+        let account_ids: Vec<T::AccountId> = SubnetNodeClassPromotions::<T>::take(subnet_id, shift_epoch);
+        promotions = promotions.saturating_add(account_ids.len() as u32);
+        for account_id in account_ids.iter() {
+          Self::promote_subnet_node(subnet_id, account_id, shift_epoch);
         }
-        // --- Update classifications
-        SubnetNodesClasses::<T>::insert(subnet_id, class_id, node_sets_copy);
-        SubnetNodesClasses::<T>::insert(subnet_id, next_class_id, next_node_sets);
       }
+
+      max_promotions = max_promotions.max(promotions);
+    }
+
+    (subnets, max_promotions)
+  }
+
+  /// Shift a subnet node into the class above its current class
+  pub fn promote_subnet_node(subnet_id: u32, account_id: &T::AccountId, epoch: u64) {
+    let classification: SubnetNodeClassification = match SubnetNodesClassification::<T>::get(subnet_id, account_id) {
+      Some(classification) => classification,
+      None => return,
+    };
+
+    // --- Skip if reclassified since being scheduled
+    if classification.promotion_epoch != Some(epoch) {
+      return
+    }
+
+    // --- Remove the account from classification if no longer eligible
+    if !Self::is_account_eligible(account_id.clone()) {
+      Self::declassify_subnet_node(subnet_id, account_id);
+      return
     }
+
+    let next_class_id: SubnetNodeClass = match SubnetNodeClass::from_repr(classification.class.index() + 1) {
+      Some(next_class_id) => next_class_id,
+      None => return,
+    };
+
+    Self::classify_subnet_node(subnet_id, account_id, next_class_id, classification.classified);
+  }
+
+  /// Returns true if a count has reached a multiple of a demotion threshold
+  pub fn is_node_class_demotion_threshold(count: u32, threshold: u32) -> bool {
+    threshold > 0 && count > 0 && count % threshold == 0
+  }
+
+  /// Demote a subnet node to the class below its current class
+  // The subnet node is classified again on the current block and must serve
+  // the class epochs of its demoted class again before being shifted up
+  pub fn demote_subnet_node(subnet_id: u32, account_id: &T::AccountId, reason: SubnetNodeDemotionReason) {
+    let block: u64 = Self::get_current_block_as_u64();
+    let epoch_length: u64 = T::EpochLength::get();
+
+    let class_id: SubnetNodeClass = match SubnetNodesClassification::<T>::get(subnet_id, account_id) {
+      Some(classification) => classification.class,
+      None => return,
+    };
+
+    // --- Idle is the lowest class
+    let demoted_class_id: SubnetNodeClass = match class_id.index().checked_sub(1) {
+      Some(index) => SubnetNodeClass::from_repr(index).unwrap(),
+      None => return,
+    };
+
+    // --- Class epochs are counted from the classified block so the epochs of the class
+    //     below the demoted class are subtracted to restart the demoted class on this block
+    let classified: u64 = match demoted_class_id.index().checked_sub(1) {
+      Some(index) => block.saturating_sub(
+        Self::get_subnet_node_class_epochs(subnet_id, SubnetNodeClass::from_repr(index).unwrap()) * epoch_length
+      ),
+      None => block,
+    };
+
+    Self::classify_subnet_node(subnet_id, account_id, demoted_class_id, classified);
+
+    Self::deposit_event(
+      Event::SubnetNodeDemoted {
+        subnet_id: subnet_id,
+        account_id: account_id.clone(),
+        class: demoted_class_id,
+        reason: reason,
+        block: block,
+      }
+    );
   }
 
-  pub fn do_choose_validator_and_accountants(block: u64, epoch: u32, epoch_length: u64) {
+  /// Choose the validators and accountants of each subnet for an epoch
+  // Returns the count of subnets and the most subnet nodes in a subnet for weighing the epoch
+  pub fn do_choose_validator_and_accountants(block: u64, epoch: u32, epoch_length: u64) -> (u32, u32) {
     let min_required_model_consensus_submit_epochs = MinRequiredSubnetConsensusSubmitEpochs::<T>::get();
     let target_accountants_len: u32 = TargetAccountantsLength::<T>::get();
+    let validators_per_epoch: u32 = ValidatorsPerEpoch::<T>::get();
+
+    let mut subnets: u32 = 0;
+    let mut max_subnet_nodes: u32 = 0;
 
     for (subnet_id, data) in SubnetsData::<T>::iter() {
+      subnets += 1;
+      max_subnet_nodes = max_subnet_nodes.max(TotalSubnetNodes::<T>::get(subnet_id));
+
       let min_subnet_nodes = data.min_nodes;
 
+      // --- Penalize entropy commitments that were never revealed
+      Self::penalize_unrevealed_entropy(subnet_id, epoch as u64);
+
       // --- Ensure model is able to submit consensus
       if block < Self::get_eligible_epoch_block(
         epoch_length, 
@@ -719,21 +937,35 @@ impl<T: Config> Pallet<T> {
         continue
       }
 
-      Self::choose_validator(
-        block,
+      // --- Shuffle all eligible nodes at once so no node can be chosen for more than one role
+      let mut account_ids: Vec<T::AccountId> = Self::get_classified_subnet_nodes(subnet_id, SubnetNodeClass::Submittable)
+        .into_keys()
+        .collect();
+
+      let seed: [u8; 32] = T::ElectionRandomness::random_seed(subnet_id, epoch);
+
+      Self::shuffle(&mut account_ids, seed);
+
+      let validators: Vec<T::AccountId> = Self::choose_validators(
+        &account_ids,
+        seed,
         subnet_id,
         min_subnet_nodes,
+        validators_per_epoch,
         epoch,
       );
 
       Self::choose_accountants(
-        block,
+        &account_ids,
+        &validators,
         epoch,
         subnet_id,
         min_subnet_nodes,
         target_accountants_len,
       );
     }
+
+    (subnets, max_subnet_nodes)
   }
 
   pub fn eth_into_gwei(
@@ -36,6 +36,9 @@ pub trait WeightInfo {
 	fn remove_subnet_node() -> Weight;
 	fn add_to_stake() -> Weight;
 	fn remove_stake() -> Weight;
+	fn reward_subnets(s: u32, n: u32, r: u32, d: u32, ) -> Weight;
+	fn shift_node_classes(s: u32, p: u32, ) -> Weight;
+	fn do_choose_validator_and_accountants(s: u32, n: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_network` using the Substrate node and recommended hardware.
@@ -73,8 +76,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Network::TotalStake` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Network::SubnetAccount` (r:1 w:1)
 	/// Proof: `Network::SubnetAccount` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `Network::SubnetNodesClasses` (r:1 w:1)
-	/// Proof: `Network::SubnetNodesClasses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodesClassification` (r:1 w:1)
+	/// Proof: `Network::SubnetNodesClassification` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassEpochsOverride` (r:1 w:0)
+	/// Proof: `Network::SubnetNodeClassEpochsOverride` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassEpochs` (r:1 w:0)
+	/// Proof: `Network::SubnetNodeClassEpochs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassShiftEpoch` (r:1 w:0)
+	/// Proof: `Network::SubnetNodeClassShiftEpoch` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassCount` (r:1 w:1)
+	/// Proof: `Network::SubnetNodeClassCount` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassPromotions` (r:0 w:1)
+	/// Proof: `Network::SubnetNodeClassPromotions` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Network::AccountSubnets` (r:1 w:1)
 	/// Proof: `Network::AccountSubnets` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn add_subnet_node() -> Weight {
@@ -83,8 +96,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		//  Estimated: `3991`
 		// Minimum execution time: 103_861_000 picoseconds.
 		Weight::from_parts(108_124_000, 3991)
-			.saturating_add(T::DbWeight::get().reads(18_u64))
-			.saturating_add(T::DbWeight::get().writes(11_u64))
+			.saturating_add(T::DbWeight::get().reads(22_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
 	}
 	/// Storage: `Network::SubnetsData` (r:1 w:0)
 	/// Proof: `Network::SubnetsData` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -117,8 +130,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Network::TotalSubnetNodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Network::AccountSubnets` (r:1 w:1)
 	/// Proof: `Network::AccountSubnets` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `Network::SubnetNodesClasses` (r:4 w:4)
-	/// Proof: `Network::SubnetNodesClasses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodesClassification` (r:1 w:1)
+	/// Proof: `Network::SubnetNodesClassification` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassCount` (r:1 w:1)
+	/// Proof: `Network::SubnetNodeClassCount` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Network::SubnetNodeAccount` (r:0 w:1)
 	/// Proof: `Network::SubnetNodeAccount` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn remove_subnet_node() -> Weight {
@@ -127,8 +142,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		//  Estimated: `11795`
 		// Minimum execution time: 70_596_000 picoseconds.
 		Weight::from_parts(74_846_000, 11795)
-			.saturating_add(T::DbWeight::get().reads(10_u64))
-			.saturating_add(T::DbWeight::get().writes(9_u64))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 	}
 	/// Storage: `Network::SubnetsData` (r:1 w:0)
 	/// Proof: `Network::SubnetsData` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -188,6 +203,65 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(10_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
+	/// Not benchmarked: constants scaled to the previous fixed epoch weight at 32 subnets with 96 subnet nodes each.
+	/// Replace with the `reward_subnets` and `reward_subnets_penalized` benchmark results, keeping the larger per
+	/// subnet node weight of the two multiplied by `s * n`.
+	/// `s` is the count of subnets and `n` the most subnet nodes in a subnet, every subnet is weighed as having `n`
+	/// subnet nodes that are either rewarded or penalized and demoted. `r` is the count of subnet nodes removed for
+	/// sequential absence and `d` the count of subnets removed for surpassing `MaxSubnetPenalties`.
+	fn reward_subnets(s: u32, n: u32, r: u32, d: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 1638)
+			.saturating_add(Weight::from_parts(500_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(62_500_000, 0).saturating_mul(s.saturating_mul(n).into()))
+			.saturating_add(Weight::from_parts(40_000_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(30_000_000, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((14_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(s.saturating_mul(n).into())))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(s.saturating_mul(n).into())))
+			.saturating_add(T::DbWeight::get().writes((7_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(0, 7_200).saturating_mul(s.saturating_mul(n).into()))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(d.into()))
+	}
+	/// Not benchmarked: constants scaled to the previous fixed epoch weight at 32 subnets with 96 promotions each.
+	/// Replace with the `shift_node_classes` benchmark results, keeping the per subnet node weight multiplied by `s`.
+	/// `s` is the count of subnets and `p` the most subnet nodes shifted into their next class in a subnet, every
+	/// subnet is weighed as shifting `p` subnet nodes so the per subnet node weight is multiplied by `s * p`.
+	fn shift_node_classes(s: u32, p: u32, ) -> Weight {
+		Weight::from_parts(8_000_000, 1638)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(s.saturating_mul(p).into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(s.saturating_mul(p).into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(s.saturating_mul(p).into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(s.saturating_mul(p).into()))
+	}
+	/// Not benchmarked: constants scaled to the previous fixed epoch weight at 32 subnets with 96 subnet nodes each.
+	/// Replace with the `do_choose_validator_and_accountants` benchmark results, keeping the per subnet node weight multiplied by `s`.
+	/// `s` is the count of subnets and `n` the most subnet nodes in a subnet, every subnet is weighed as having `n`
+	/// subnet nodes so the per subnet node weight is multiplied by `s * n`.
+	fn do_choose_validator_and_accountants(s: u32, n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 1638)
+			.saturating_add(Weight::from_parts(400_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(45_625_000, 0).saturating_mul(s.saturating_mul(n).into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(s.saturating_mul(n).into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(s.saturating_mul(n).into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(0, 7_100).saturating_mul(s.saturating_mul(n).into()))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -224,8 +298,18 @@ impl WeightInfo for () {
 	/// Proof: `Network::TotalStake` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Network::SubnetAccount` (r:1 w:1)
 	/// Proof: `Network::SubnetAccount` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `Network::SubnetNodesClasses` (r:1 w:1)
-	/// Proof: `Network::SubnetNodesClasses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodesClassification` (r:1 w:1)
+	/// Proof: `Network::SubnetNodesClassification` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassEpochsOverride` (r:1 w:0)
+	/// Proof: `Network::SubnetNodeClassEpochsOverride` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassEpochs` (r:1 w:0)
+	/// Proof: `Network::SubnetNodeClassEpochs` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassShiftEpoch` (r:1 w:0)
+	/// Proof: `Network::SubnetNodeClassShiftEpoch` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassCount` (r:1 w:1)
+	/// Proof: `Network::SubnetNodeClassCount` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassPromotions` (r:0 w:1)
+	/// Proof: `Network::SubnetNodeClassPromotions` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Network::AccountSubnets` (r:1 w:1)
 	/// Proof: `Network::AccountSubnets` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn add_subnet_node() -> Weight {
@@ -234,8 +318,8 @@ impl WeightInfo for () {
 		//  Estimated: `3991`
 		// Minimum execution time: 103_861_000 picoseconds.
 		Weight::from_parts(108_124_000, 3991)
-			.saturating_add(RocksDbWeight::get().reads(18_u64))
-			.saturating_add(RocksDbWeight::get().writes(11_u64))
+			.saturating_add(RocksDbWeight::get().reads(22_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
 	}
 	/// Storage: `Network::SubnetsData` (r:1 w:0)
 	/// Proof: `Network::SubnetsData` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -268,8 +352,10 @@ impl WeightInfo for () {
 	/// Proof: `Network::TotalSubnetNodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Network::AccountSubnets` (r:1 w:1)
 	/// Proof: `Network::AccountSubnets` (`max_values`: None, `max_size`: None, mode: `Measured`)
-	/// Storage: `Network::SubnetNodesClasses` (r:4 w:4)
-	/// Proof: `Network::SubnetNodesClasses` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodesClassification` (r:1 w:1)
+	/// Proof: `Network::SubnetNodesClassification` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetNodeClassCount` (r:1 w:1)
+	/// Proof: `Network::SubnetNodeClassCount` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `Network::SubnetNodeAccount` (r:0 w:1)
 	/// Proof: `Network::SubnetNodeAccount` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn remove_subnet_node() -> Weight {
@@ -278,8 +364,8 @@ impl WeightInfo for () {
 		//  Estimated: `11795`
 		// Minimum execution time: 70_596_000 picoseconds.
 		Weight::from_parts(74_846_000, 11795)
-			.saturating_add(RocksDbWeight::get().reads(10_u64))
-			.saturating_add(RocksDbWeight::get().writes(9_u64))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	/// Storage: `Network::SubnetsData` (r:1 w:0)
 	/// Proof: `Network::SubnetsData` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -339,4 +425,63 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(10_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
+	/// Not benchmarked: constants scaled to the previous fixed epoch weight at 32 subnets with 96 subnet nodes each.
+	/// Replace with the `reward_subnets` and `reward_subnets_penalized` benchmark results, keeping the larger per
+	/// subnet node weight of the two multiplied by `s * n`.
+	/// `s` is the count of subnets and `n` the most subnet nodes in a subnet, every subnet is weighed as having `n`
+	/// subnet nodes that are either rewarded or penalized and demoted. `r` is the count of subnet nodes removed for
+	/// sequential absence and `d` the count of subnets removed for surpassing `MaxSubnetPenalties`.
+	fn reward_subnets(s: u32, n: u32, r: u32, d: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 1638)
+			.saturating_add(Weight::from_parts(500_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(62_500_000, 0).saturating_mul(s.saturating_mul(n).into()))
+			.saturating_add(Weight::from_parts(40_000_000, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(30_000_000, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((14_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(s.saturating_mul(n).into())))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(s.saturating_mul(n).into())))
+			.saturating_add(RocksDbWeight::get().writes((7_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(d.into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(0, 7_200).saturating_mul(s.saturating_mul(n).into()))
+			.saturating_add(Weight::from_parts(0, 2_700).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(d.into()))
+	}
+	/// Not benchmarked: constants scaled to the previous fixed epoch weight at 32 subnets with 96 promotions each.
+	/// Replace with the `shift_node_classes` benchmark results, keeping the per subnet node weight multiplied by `s`.
+	/// `s` is the count of subnets and `p` the most subnet nodes shifted into their next class in a subnet, every
+	/// subnet is weighed as shifting `p` subnet nodes so the per subnet node weight is multiplied by `s * p`.
+	fn shift_node_classes(s: u32, p: u32, ) -> Weight {
+		Weight::from_parts(8_000_000, 1638)
+			.saturating_add(Weight::from_parts(6_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(25_000_000, 0).saturating_mul(s.saturating_mul(p).into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(s.saturating_mul(p).into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(s.saturating_mul(p).into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(0, 2_600).saturating_mul(s.saturating_mul(p).into()))
+	}
+	/// Not benchmarked: constants scaled to the previous fixed epoch weight at 32 subnets with 96 subnet nodes each.
+	/// Replace with the `do_choose_validator_and_accountants` benchmark results, keeping the per subnet node weight multiplied by `s`.
+	/// `s` is the count of subnets and `n` the most subnet nodes in a subnet, every subnet is weighed as having `n`
+	/// subnet nodes so the per subnet node weight is multiplied by `s * n`.
+	fn do_choose_validator_and_accountants(s: u32, n: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 1638)
+			.saturating_add(Weight::from_parts(400_000_000, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(45_625_000, 0).saturating_mul(s.saturating_mul(n).into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(s.saturating_mul(n).into())))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(s.saturating_mul(n).into())))
+			.saturating_add(Weight::from_parts(0, 2_500).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(0, 7_100).saturating_mul(s.saturating_mul(n).into()))
+	}
 }
\ No newline at end of file
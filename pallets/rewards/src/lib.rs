@@ -193,4 +193,17 @@ impl<T: Config> Pallet<T> {
 
     block_subsidy
   }
+
+  /// Portion of the block subsidy paid into the network pallets stake vault
+  fn get_stake_vault_subsidy(block_number: BlockNumberFor<T>) -> BalanceOf<T> {
+    let subsidy: BalanceOf<T> = Self::get_block_subsidy(block_number);
+    let validator_percent = Perbill::from_rational(ValidatorRewardPercent::<T>::get(), 100 as u32);
+    subsidy - validator_percent * subsidy
+  }
+}
+
+impl<T: Config> pallet_network::StakeVaultSubsidy for Pallet<T> {
+  fn stake_vault_subsidy(block: u64) -> u128 {
+    Self::get_stake_vault_subsidy(block.saturated_into::<BlockNumberFor<T>>()).saturated_into::<u128>()
+  }
 }
\ No newline at end of file
@@ -62,4 +62,51 @@ impl<T: Config> Pallet<T> {
     Ok(())
   }
 
+  /// Cancel an approved proposal before it is enacted
+  pub fn cancel_enactment(
+    proposal_index: PropIndex,
+  ) -> DispatchResult {
+    ensure!(
+      Proposals::<T>::contains_key(proposal_index),
+      Error::<T>::ProposalInvalid
+    );
+
+    let proposal = Proposals::<T>::get(proposal_index);
+
+    ensure!(
+      proposal.proposal_status == PropsStatus::Approved,
+      Error::<T>::ProposalNotApproved
+    );
+
+    EnactmentQueue::<T>::mutate(
+      proposal.enactment_block,
+      |proposal_indexes: &mut Vec<PropIndex>| proposal_indexes.retain(|index| *index != proposal_index)
+    );
+
+    // --- Free the reserves kept for enactment
+    Self::unreserve_subnet_nodes(proposal_index);
+
+    if let Some(proposer_stake_as_balance) = Self::u128_to_balance(proposal.proposer_stake) {
+      T::Currency::unreserve(
+        &proposal.proposer,
+        proposer_stake_as_balance,
+      );
+    }
+
+    // --- Active proposals were already decreased on approval
+    Proposals::<T>::mutate(
+      proposal_index,
+      |params: &mut PropsParams<T::AccountId>| {
+        params.proposal_status = PropsStatus::Cancelled;
+        params.proposer_stake = 0;
+      },
+    );
+
+    Self::set_props_path_status(proposal.path, PropsStatus::Cancelled);
+
+    Self::deposit_event(Event::EnactmentCancelled(proposal_index));
+
+    Ok(())
+  }
+
 }
\ No newline at end of file
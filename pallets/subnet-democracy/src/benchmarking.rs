@@ -30,7 +30,8 @@ use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use crate::Pallet as SubnetVoting;
 use crate::{
   SubnetNode, PropsType, SubnetVote, VotesBalance, ReservableCurrency, PropCount, VoteType,
-  Votes, ActiveProposals, Proposals, PropsStatus, Quorum, PreSubnetData
+  Votes, ActiveProposals, Proposals, PropsStatus, Quorum, PreSubnetData, Conviction, VoterVotes,
+  EnactmentResults, JoinedSubnetNodes
 };
 use frame_support::dispatch::Vec;
 use scale_info::prelude::{vec, format};
@@ -62,6 +63,7 @@ fn default_add_subnet_data() -> PreSubnetData {
   let subnet_data = PreSubnetData {
     path: DEFAULT_MODEL_PATH.into(),
 		memory_mb: 50000,
+		class_epochs: Vec::new(),
   };
   subnet_data
 }
@@ -166,7 +168,7 @@ fn post_activate_cancel_ensures<T: Config>(proposal_index: u32, proposer: T::Acc
 
 fn post_cast_vote_ensures<T: Config>(proposal_index: u32, voter: u32) {
   assert_err!(
-    SubnetVoting::<T>::unreserve(
+    SubnetVoting::<T>::unlock(
       RawOrigin::Signed(funded_account::<T>("voter", voter)).into(),
       proposal_index, 
     ),
@@ -189,6 +191,17 @@ fn build_propose_activate<T: Config>(path: Vec<u8>, start: u32, end: u32, deposi
   0
 }
 
+fn build_join_proposal<T: Config>(proposal_index: u32, start: u32, end: u32) {
+  for n in start..end {
+    assert_ok!(
+      SubnetVoting::<T>::join_proposal(
+        RawOrigin::Signed(funded_account::<T>("voter", n)).into(),
+        proposal_index,
+      )
+    );
+  }
+}
+
 fn build_cast_vote<T: Config>(proposal_index: u32, start: u32, end: u32, vote: VoteType) {
   for n in start..end {
     let voter = funded_account::<T>("voter", n);
@@ -199,6 +212,7 @@ fn build_cast_vote<T: Config>(proposal_index: u32, start: u32, end: u32, vote: V
         proposal_index,
         DEFAUT_VOTE_AMOUNT.try_into().ok().expect("REASON"),
         vote.clone(),
+        Conviction::None,
       )
     );
   }
@@ -229,7 +243,7 @@ benchmarks! {
 		let voter = funded_account::<T>("voter", 0);
     let subnet_nodes = build_subnet_nodes::<T>(0, min_subnet_nodes, min_stake);
     let proposal_index = build_propose_activate::<T>(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
-	}: cast_vote(RawOrigin::Signed(voter.clone()), proposal_index, DEFAUT_VOTE_AMOUNT.try_into().ok().expect("REASON"), VoteType::Yay)
+	}: cast_vote(RawOrigin::Signed(voter.clone()), proposal_index, DEFAUT_VOTE_AMOUNT.try_into().ok().expect("REASON"), VoteType::Yay, Conviction::Locked6x)
 	verify {
     assert_eq!(1, 1);
     post_cast_vote_ensures::<T>(proposal_index, 0)
@@ -267,7 +281,7 @@ benchmarks! {
     assert_eq!(1, 1);
   }
 
-  unreserve {
+  unlock {
     let prop_count = PropCount::<T>::get();
     let min_stake = T::SubnetVote::get_min_stake_balance();
     let min_subnet_nodes: u32 = T::SubnetVote::get_min_subnet_nodes(1000);
@@ -282,7 +296,8 @@ benchmarks! {
         RawOrigin::Signed(voter.clone()).into(), 
         proposal_index, 
         DEFAUT_VOTE_AMOUNT.try_into().ok().expect("REASON"), 
-        VoteType::Yay      
+        VoteType::Yay,
+        Conviction::None,
       )
     );
 
@@ -297,11 +312,71 @@ benchmarks! {
       )
     );
   
-	}: unreserve(RawOrigin::Signed(voter.clone()), proposal_index)
+	}: unlock(RawOrigin::Signed(voter.clone()), proposal_index)
 	verify {
     assert_eq!(1, 1);
   }
 
+  enact_proposal {
+    let min_subnet_nodes: u32 = T::SubnetVote::get_min_subnet_nodes(1000);
+		let voter = funded_account::<T>("voter", 0);
+    let proposal_index = build_propose_activate::<T>(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+    build_join_proposal::<T>(proposal_index, 0, min_subnet_nodes);
+    build_cast_vote::<T>(proposal_index, 0, min_subnet_nodes, VoteType::Yay);
+
+    let proposal = Proposals::<T>::get(proposal_index);
+
+    let current_block_number = get_current_block_as_u64::<T>();
+    frame_system::Pallet::<T>::set_block_number(u64_to_block::<T>(current_block_number + proposal.max_block + 1));
+
+    assert_ok!(
+      SubnetVoting::<T>::execute(
+        RawOrigin::Signed(voter.clone()).into(),
+        proposal_index
+      )
+    );
+
+    let enactment_block = Proposals::<T>::get(proposal_index).enactment_block;
+    frame_system::Pallet::<T>::set_block_number(u64_to_block::<T>(enactment_block));
+	}: {
+    <SubnetVoting<T> as OnInitialize<BlockNumberFor<T>>>::on_initialize(u64_to_block::<T>(enactment_block));
+  }
+	verify {
+    assert_eq!(EnactmentResults::<T>::get(proposal_index), Some(Ok(())));
+  }
+
+  remove_vote {
+    let min_subnet_nodes: u32 = T::SubnetVote::get_min_subnet_nodes(1000);
+		let voter = funded_account::<T>("voter", 0);
+    let proposal_index = build_propose_activate::<T>(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+    build_cast_vote::<T>(proposal_index, 0, 1, VoteType::Yay);
+	}: remove_vote(RawOrigin::Signed(voter.clone()), proposal_index)
+	verify {
+    assert_eq!(VoterVotes::<T>::contains_key(proposal_index, &voter), false);
+    assert_eq!(Votes::<T>::get(proposal_index).yay, 0);
+  }
+
+  change_vote {
+    let min_subnet_nodes: u32 = T::SubnetVote::get_min_subnet_nodes(1000);
+		let voter = funded_account::<T>("voter", 0);
+    let proposal_index = build_propose_activate::<T>(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+    build_cast_vote::<T>(proposal_index, 0, 1, VoteType::Yay);
+	}: change_vote(RawOrigin::Signed(voter.clone()), proposal_index, VoteType::Nay)
+	verify {
+    let votes = Votes::<T>::get(proposal_index);
+    assert_eq!(votes.yay, 0);
+    assert_ne!(votes.nay, 0);
+  }
+
+  join_proposal {
+    let min_subnet_nodes: u32 = T::SubnetVote::get_min_subnet_nodes(1000);
+		let subnet_node = funded_account::<T>("voter", 0);
+    let proposal_index = build_propose_activate::<T>(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+	}: join_proposal(RawOrigin::Signed(subnet_node.clone()), proposal_index)
+	verify {
+    assert!(JoinedSubnetNodes::<T>::contains_key(proposal_index, &subnet_node));
+  }
+
   impl_benchmark_test_suite!(
 		SubnetVoting,
 		crate::mock::new_test_ext(),
@@ -36,13 +36,17 @@ use frame_support::{
   pallet_prelude::DispatchResult,
   ensure,
   dispatch::Vec,
+  sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet},
   traits::{Currency, LockableCurrency, ReservableCurrency, WithdrawReasons, LockIdentifier},
 };
 use sp_runtime::{
   traits::Zero,
-  Saturating, Perbill, Percent
+  Saturating, Perbill, Percent, DispatchError
+};
+use pallet_network::{
+  SubnetVote, PreSubnetData, VoteSubnetData, BootstrapSubnetNode, SubnetNodeClass,
+  AdminInterface as NetworkAdminInterface, PERCENTAGE_FACTOR,
 };
-use pallet_network::{SubnetVote, PreSubnetData, VoteSubnetData};
 
 #[cfg(test)]
 mod mock;
@@ -59,6 +63,7 @@ pub use weights::WeightInfo;
 mod types;
 mod admin;
 mod utils;
+pub mod migrations;
 
 pub use types::PropIndex;
 
@@ -98,13 +103,32 @@ pub mod pallet {
     #[pallet::constant]
 		type VotingPeriod: Get<BlockNumberFor<Self>>;
 
+    /// Blocks after an approved proposal is tallied that it is enacted at
     #[pallet::constant]
 		type EnactmentPeriod: Get<BlockNumberFor<Self>>;
 
+    /// Blocks a vote stays locked after voting ends per lock period of its conviction
+    #[pallet::constant]
+		type VoteLockingPeriod: Get<BlockNumberFor<Self>>;
+
+    /// Maximum base subnet reward a parameter change proposal can set
+    #[pallet::constant]
+		type MaxBaseSubnetReward: Get<u128>;
+
+    /// Maximum minimum stake balance a parameter change proposal can set
+    #[pallet::constant]
+		type MaxMinStakeBalance: Get<u128>;
+
     type SubnetVote: SubnetVote<Self::AccountId>; 
 
-    // type Currency: Currency<Self::AccountId> + LockableCurrency<Self::AccountId, Moment = BlockNumberFor<Self>> + Send + Sync;
-    type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId> + Send + Sync;
+    /// Applies the network parameters of enacted parameter change proposals
+    type NetworkAdminInterface: NetworkAdminInterface;
+
+    type Currency: Currency<Self::AccountId> 
+      + ReservableCurrency<Self::AccountId> 
+      + LockableCurrency<Self::AccountId, Moment = BlockNumberFor<Self>> 
+      + Send 
+      + Sync;
 
     type WeightInfo: WeightInfo;
   }
@@ -167,6 +191,26 @@ pub mod pallet {
     InvalidQuorum,
     InvalidNodeVotePremium,
     InvalidPeerId,
+    /// Subnet node class epochs must set each class once and increase from Idle to Accountant
+    InvalidSubnetNodeClassEpochs,
+    /// Subnet nodes must have unique account IDs and peer IDs
+    DuplicateSubnetNode,
+    /// Vote balance is still locked by its conviction
+    VoteLocked,
+    /// Voter has no votes on the proposal
+    VoteNotExist,
+    /// Votes are already all in the direction voted
+    VoteUnchanged,
+    /// Proposal is not approved and waiting for enactment
+    ProposalNotApproved,
+    /// Parameter change proposals can't have subnet data or subnet nodes
+    InvalidParameterChange,
+    /// Account isn't one of the proposals subnet nodes
+    SubnetNodeNotProposed,
+    /// Subnet node already joined the proposal
+    SubnetNodeAlreadyJoined,
+    /// Network parameter value is outside of the bounds allowed by proposals
+    NetworkParameterOutOfBounds,
   }
 
   /// `pallet-rewards` events
@@ -180,6 +224,10 @@ pub mod pallet {
     SetNodeVotePremium(u128),
     SetQuorum(u128),
     SetMajority(u128),
+    ProposalApproved(PropIndex, u64),
+    ProposalEnacted(PropIndex, DispatchResult),
+    EnactmentCancelled(PropIndex),
+    SubnetNodeJoined(PropIndex, T::AccountId),
   }
 
 	#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
@@ -199,12 +247,14 @@ pub mod pallet {
 	pub struct PropsParams<AccountId> {
     pub proposer: AccountId,
     pub proposer_stake: u128, // Activate: Non refundable, Deactivate: Refundable
+    pub subnet_node_stake: u128, // Activate: Reserved from each joined subnet node until concluded
     pub proposal_status: PropsStatus,
     pub proposal_type: PropsType,
     pub path: Vec<u8>,
     pub subnet_data: PreSubnetData,
 		pub subnet_nodes: Vec<SubnetNode<AccountId>>,
     pub max_block: u64, // block ending voting
+    pub enactment_block: u64, // block an approved proposal is enacted at
 	}
 
   // #[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
@@ -250,12 +300,14 @@ pub mod pallet {
 		return PropsParams {
       proposer: T::AccountId::decode(&mut TrailingZeroInput::zeroes()).unwrap(),
       proposer_stake: 0,
+      subnet_node_stake: 0,
       proposal_status: PropsStatus::None,
       proposal_type: PropsType::None,
 			path: Vec::new(),
       subnet_data: PreSubnetData::default(),
 			subnet_nodes: Vec::new(),
       max_block: 0,
+      enactment_block: 0,
     };
 	}
   #[pallet::type_value]
@@ -322,11 +374,103 @@ pub mod pallet {
     Abstain,
   }
 
+  /// Multiplies the votes of a balance in exchange for locking it for longer after voting ends
+  #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+  pub enum Conviction {
+    /// 0.1x votes, unlocked once voting ends
+    None,
+    /// 1x votes, locked for 1 lock period
+    Locked1x,
+    /// 2x votes, locked for 2 lock periods
+    Locked2x,
+    /// 3x votes, locked for 4 lock periods
+    Locked3x,
+    /// 4x votes, locked for 8 lock periods
+    Locked4x,
+    /// 5x votes, locked for 16 lock periods
+    Locked5x,
+    /// 6x votes, locked for 32 lock periods
+    Locked6x,
+  }
+
+  impl Default for Conviction {
+    fn default() -> Self {
+      Conviction::None
+    }
+  }
+
+  impl Conviction {
+    /// The number of `VoteLockingPeriod`s the voted balance stays locked after voting ends
+    pub fn lock_periods(self) -> u64 {
+      match self {
+        Conviction::None => 0,
+        Conviction::Locked1x => 1,
+        Conviction::Locked2x => 2,
+        Conviction::Locked3x => 4,
+        Conviction::Locked4x => 8,
+        Conviction::Locked5x => 16,
+        Conviction::Locked6x => 32,
+      }
+    }
+
+    /// The votes a balance is worth at this conviction
+    pub fn votes(self, balance: u128) -> u128 {
+      match self {
+        Conviction::None => balance / 10,
+        Conviction::Locked1x => balance,
+        Conviction::Locked2x => balance.saturating_mul(2),
+        Conviction::Locked3x => balance.saturating_mul(3),
+        Conviction::Locked4x => balance.saturating_mul(4),
+        Conviction::Locked5x => balance.saturating_mul(5),
+        Conviction::Locked6x => balance.saturating_mul(6),
+      }
+    }
+  }
+
   #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
   pub enum PropsType {
     None,
     Activate,
     Deactivate,
+    ParameterChange(NetworkParameter),
+  }
+
+  /// Network parameters a `PropsType::ParameterChange` proposal can set
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, scale_info::TypeInfo)]
+  pub enum NetworkParameter {
+    MaxSubnets(u32),
+    MinSubnetNodes(u32),
+    MaxSubnetNodes(u32),
+    MinStakeBalance(u128),
+    BaseSubnetReward(u128),
+    SlashPercentage(u128),
+    SubnetRemovalBounty(u128),
+    StakeUnbondingEpochs(u64),
+    ValidatorsPerEpoch(u32),
+    SubnetNodeClassEpochs(SubnetNodeClass, u64),
+    NodeClassDemotionAbsentThreshold(u32),
+    NodeClassDemotionPenaltyThreshold(u32),
+  }
+
+  impl NetworkParameter {
+    /// Whether the value is within the bounds a proposal can set the parameter to
+    // The network pallet also validates the value against the network state on enactment
+    pub fn is_within_bounds<T: Config>(&self) -> bool {
+      match *self {
+        NetworkParameter::MaxSubnets(value) => value > 0 && value <= 100,
+        NetworkParameter::MinSubnetNodes(value) => value >= 9 && value <= 1000,
+        NetworkParameter::MaxSubnetNodes(value) => value >= 9 && value <= 1000,
+        NetworkParameter::MinStakeBalance(value) => value > 0 && value <= T::MaxMinStakeBalance::get(),
+        NetworkParameter::BaseSubnetReward(value) => value > 0 && value <= T::MaxBaseSubnetReward::get(),
+        NetworkParameter::SlashPercentage(value) => value <= PERCENTAGE_FACTOR, // 100.00%
+        NetworkParameter::SubnetRemovalBounty(_) => true,
+        NetworkParameter::StakeUnbondingEpochs(value) => value > 0 && value <= 100,
+        NetworkParameter::ValidatorsPerEpoch(value) => value > 0 && value <= 32,
+        NetworkParameter::SubnetNodeClassEpochs(_, value) => value <= 1000,
+        NetworkParameter::NodeClassDemotionAbsentThreshold(value) => value > 0,
+        NetworkParameter::NodeClassDemotionPenaltyThreshold(value) => value > 0,
+      }
+    }
   }
 
   impl Default for PropsType {
@@ -341,7 +485,7 @@ pub mod pallet {
     None,
     /// Voting in progress or not yet executed
     Active,
-    /// Voting succeeded and executed
+    /// Voting succeeded and enacted
     Succeeded,
     /// Not enough votes within voting period accomplished
     Defeated,
@@ -349,6 +493,10 @@ pub mod pallet {
     Cancelled,
     /// Voting period passed, thus expiring proposal
     Expired,
+    /// Voting succeeded and waiting for enactment
+    Approved,
+    /// Voting succeeded but the enactment failed, see `EnactmentResults` for the error
+    Failed,
   }
 
   impl Default for PropsStatus {
@@ -399,6 +547,57 @@ pub mod pallet {
     ValueQuery,
   >;
 
+  /// Vote power of each voter on a proposal by direction
+  // Used to take a voters votes out of `Votes` when they change or remove their vote
+  #[pallet::storage]
+  #[pallet::getter(fn voter_votes)]
+  pub type VoterVotes<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    PropIndex,
+    Identity,
+    T::AccountId,
+    VotesParams,
+    ValueQuery,
+    DefaultVotes<T>,
+  >;
+
+  /// Proposals to tally at each block, the block after their voting ends
+  #[pallet::storage]
+  pub type TallyQueue<T> = StorageMap<_, Identity, u64, Vec<PropIndex>, ValueQuery>;
+
+  /// Approved proposals to enact at each block
+  #[pallet::storage]
+  pub type EnactmentQueue<T> = StorageMap<_, Identity, u64, Vec<PropIndex>, ValueQuery>;
+
+  /// Result of enacting each approved proposal
+  #[pallet::storage]
+  #[pallet::getter(fn enactment_results)]
+  pub type EnactmentResults<T> = StorageMap<_, Blake2_128Concat, PropIndex, DispatchResult>;
+
+  /// Stake reserved by each subnet node that joined an activate proposal
+  // Only the joined subnet nodes are registered into the subnet on activation
+  #[pallet::storage]
+  pub type JoinedSubnetNodes<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    PropIndex,
+    Identity,
+    T::AccountId,
+    BalanceOf<T>,
+  >;
+
+  /// Block each of an accounts proposal votes can be unlocked at
+  // The account is locked by the largest `VotesBalance` of these proposals
+  #[pallet::storage]
+  pub type VoteLocks<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    BTreeMap<PropIndex, u64>,
+    ValueQuery,
+  >;
+
   // #[pallet::storage]
   // pub type ActivateVotes<T: Config> =
   //   StorageMap<_, Blake2_128Concat, PropIndex, ActivateVotesParams, ValueQuery, DefaultActivateVotes<T>>;
@@ -434,7 +633,10 @@ pub mod pallet {
   #[pallet::storage]
   pub type NodeVotePremium<T> = StorageValue<_, u128, ValueQuery>;
 
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
   #[pallet::pallet]
+  #[pallet::storage_version(STORAGE_VERSION)]
   #[pallet::without_storage_info]
   pub struct Pallet<T>(_);
 
@@ -448,11 +650,18 @@ pub mod pallet {
     ///  - The subnet isn't already proposed to be activated via PropsStatus::Active
     ///  - The proposer doesn't have the funds to initiate the subnet
     ///  - The subnet_nodes entered are below or above the min and max requirements
-    ///  - The subnet_nodes don't have the minimum required stake balance available
+    ///  - The subnet_nodes contain a duplicate account or peer ID
+    ///
+    /// Each subnet node opts in with `join_proposal`, only joined subnet nodes are registered
+    /// when the subnet is activated
     ///
 		/// May only be call to deactivate a subnet if 
     ///  - The subnet already does exist within the network pallet
     ///  - The subnet isn't already proposed to be deactivated via PropsStatus::Active
+    ///
+		/// May only be call to change a network parameter if 
+    ///  - The subnet_data is empty and no subnet_nodes are entered
+    ///  - The parameter value is within the bounds allowed by proposals
     ///
     /// The PreSubnetData is used to dictate the subnets rewards and node requirements.
     /// Memory must be accurate to usage of the model for servers/
@@ -484,6 +693,7 @@ pub mod pallet {
       let proposal_index = PropCount::<T>::get();
 
       let mut proposer_stake: u128 = 0;
+      let mut subnet_node_stake: u128 = 0;
       let proposer_balance = T::Currency::free_balance(&account_id);
 
       if proposal_type == PropsType::Activate {
@@ -510,10 +720,12 @@ pub mod pallet {
         );
 
         // --- Proposal prelims
+        subnet_node_stake = T::SubnetVote::get_min_stake_balance();
         Self::try_propose_activate(
           account_id.clone(), 
           subnet_data.clone(), 
-          subnet_nodes.clone()
+          subnet_nodes.clone(),
+          subnet_node_stake,
         ).map_err(|e| e)?;
       } else if proposal_type == PropsType::Deactivate {
         // --- Ensure zero subnet peers are submitted on deactivation proposals
@@ -522,43 +734,50 @@ pub mod pallet {
           Error::<T>::SubnetNodesLengthInvalid
         );
 
-        proposer_stake = T::MinProposalStake::get();
-        let proposer_stake_as_balance = Self::u128_to_balance(proposer_stake);
+        proposer_stake = Self::try_reserve_proposer_stake(&account_id, proposer_balance)?;
 
+        // --- Proposal prelims
+        Self::try_propose_deactivate(account_id.clone(), subnet_data.clone().path)
+          .map_err(|e| e)?;
+      } else if let PropsType::ParameterChange(parameter) = &proposal_type {
+        // --- Parameter changes aren't tied to a subnet
         ensure!(
-          proposer_balance >= proposer_stake_as_balance.unwrap(),
-          Error::<T>::NotEnoughSubnetInitializationBalance
+          subnet_data == PreSubnetData::default() && subnet_nodes.len() == 0,
+          Error::<T>::InvalidParameterChange
         );
-    
-        // --- Reserve balance to be used once succeeded, otherwise it is freed on defeat
-        // The final initialization fee may be more or less than the current initialization cost results
-        T::Currency::reserve(
-          &account_id,
-          proposer_stake_as_balance.unwrap(),
+
+        ensure!(
+          parameter.is_within_bounds::<T>(),
+          Error::<T>::NetworkParameterOutOfBounds
         );
 
-        // --- Proposal prelims
-        Self::try_propose_deactivate(account_id.clone(), subnet_data.clone().path)
-          .map_err(|e| e)?;
+        proposer_stake = Self::try_reserve_proposer_stake(&account_id, proposer_balance)?;
       }
 
+      let max_block: u64 = Self::convert_block_as_u64(<frame_system::Pallet<T>>::block_number() + T::VotingPeriod::get());
+
       // --- Save proposal
       Proposals::<T>::insert(
         proposal_index,
         PropsParams {
           proposer: account_id.clone(),
           proposer_stake: proposer_stake,
+          subnet_node_stake: subnet_node_stake,
           proposal_status: PropsStatus::Active,
           proposal_type: proposal_type,
           path: subnet_data.clone().path,
           subnet_data: subnet_data.clone(),
           subnet_nodes: subnet_nodes.clone(),
-          max_block: Self::convert_block_as_u64(<frame_system::Pallet<T>>::block_number() + T::VotingPeriod::get()),
+          max_block: max_block,
+          enactment_block: 0,
         },
       );
+
+      // --- Tally once voting ends
+      TallyQueue::<T>::append(max_block.saturating_add(1), proposal_index);
   
       // --- Set path to current proposal status to active
-      PropsPathStatus::<T>::insert(subnet_data.path, PropsStatus::Active);
+      Self::set_props_path_status(subnet_data.path, PropsStatus::Active);
 
       // --- Increase proposals count
       PropCount::<T>::put(proposal_index + 1);
@@ -574,7 +793,8 @@ pub mod pallet {
 		/// May only vote if
     ///  - Voter has enough balance
     ///
-    /// Vote is based on balance and balance is staked until execution or defeat.
+    /// Vote is based on balance multiplied by the conviction. The balance is locked until the
+    /// conviction's lock periods pass after voting ends and can back votes on other proposals.
     #[pallet::call_index(1)]
     // #[pallet::weight(0)]
     #[pallet::weight(T::WeightInfo::cast_vote())]
@@ -583,6 +803,7 @@ pub mod pallet {
       proposal_index: PropIndex,
       vote_amount: BalanceOf<T>,
       vote: VoteType,
+      conviction: Conviction,
     ) -> DispatchResult {
 			let account_id: T::AccountId = ensure_signed(origin)?;
 
@@ -593,16 +814,20 @@ pub mod pallet {
   
       let proposal = Proposals::<T>::get(proposal_index);
 
-      Self::try_cast_vote(account_id, proposal_index, proposal, vote_amount, vote)
+      Self::try_cast_vote(account_id, proposal_index, proposal, vote_amount, vote, conviction)
     }
 
     /// Execute completion of proposal 
     ///
     /// Voting must have completed
     ///
-    /// Enactment period must not have completed
+    /// Proposals are tallied automatically the block after voting ends, this tallies a proposal
+    /// that wasn't queued for a tally
+    ///
+    /// Approved proposals are queued and enacted automatically once the enactment period passes.
+    /// Governance can cancel them until then.
     ///
-    /// Vote is based on balance and balance is staked until execution or defeat.
+    /// Voted balances stay locked until their conviction lock periods pass, see `unlock`.
     ///
     /// Anyone can call this
     //
@@ -614,7 +839,7 @@ pub mod pallet {
       origin: OriginFor<T>, 
       proposal_index: PropIndex,
     ) -> DispatchResult {
-			let account_id: T::AccountId = ensure_signed(origin)?;
+			ensure_signed(origin)?;
 
       // --- Ensure proposal exists
       ensure!(
@@ -645,50 +870,7 @@ pub mod pallet {
       );
 
       // --- We made it past the voting period, we cannot fail from here
-
-      if proposal.proposal_type == PropsType::Activate {
-        ActivateProposals::<T>::mutate(|n: &mut u32| n.saturating_dec());
-      } else {
-        DeactivateProposals::<T>::mutate(|n: &mut u32| n.saturating_dec());
-      }
-      
-
-      // --- If enactment period has passed, expire the proposal
-      // Don't revert here to allow expired paths to be reproposed
-      if block > max_block + Self::convert_block_as_u64(T::EnactmentPeriod::get())  {
-        Self::try_expire(proposal_index, proposal.path.clone())
-          .map_err(|e| e)?;
-        return Ok(())
-      }
-
-      // --- Get status of proposal
-      let votes = Votes::<T>::get(proposal_index);
-
-      let quorum_reached = Self::quorum_reached(votes.clone());
-      let vote_succeeded = Self::vote_succeeded(votes.clone());
-
-      let proposer_stake_as_balance = Self::u128_to_balance(proposal.proposer_stake);
-      // --- Unreserve here to pay for initialization fee or give back to proposer
-      T::Currency::unreserve(
-        &proposal.proposer,
-        proposer_stake_as_balance.unwrap(),
-      );
-
-      // --- If quorum and vote YAYS aren greater than vote NAYS, then pass, else, defeat
-      if quorum_reached && vote_succeeded {
-        // Self::try_succeed(account_id, proposal.proposer, proposal_index, proposal.proposal_type, proposal.subnet_data.clone())
-        //   .map_err(|e| e)?;
-        Self::try_succeed(account_id, proposal_index, proposal)
-          .map_err(|e| e)?;
-      } else if quorum_reached && !vote_succeeded {
-        Self::try_defeat(proposal_index, proposal.path.clone())
-          .map_err(|e| e)?;
-      } else {
-        Self::try_expire(proposal_index, proposal.path.clone())
-          .map_err(|e| e)?;
-      }
-
-      Ok(())
+      Self::try_tally(proposal_index, proposal)
     }
 
     /// Cancel a proposal
@@ -710,7 +892,7 @@ pub mod pallet {
       );
   
       let proposal = Proposals::<T>::get(proposal_index);
-      let proposer = proposal.proposer;
+      let proposer = proposal.proposer.clone();
       ensure!(
         proposer == account_id,
         Error::<T>::NotProposer
@@ -731,16 +913,19 @@ pub mod pallet {
         Error::<T>::VoteComplete
       );
 
+      Self::unreserve_subnet_nodes(proposal_index);
+
       Self::try_cancel(proposal_index, proposal.path)
     }
 
-    /// Unreserve vote stake
+    /// Remove the vote lock of a proposal
     ///
-    /// Proposal must be not Active
+    /// Proposal must be not Active and the votes conviction lock periods must have passed
+    /// unless the proposal was cancelled
     #[pallet::call_index(4)]
     // #[pallet::weight(0)]
-    #[pallet::weight(T::WeightInfo::unreserve())]
-    pub fn unreserve(
+    #[pallet::weight(T::WeightInfo::unlock())]
+    pub fn unlock(
       origin: OriginFor<T>, 
       proposal_index: PropIndex,
     ) -> DispatchResult {
@@ -767,6 +952,15 @@ pub mod pallet {
         Error::<T>::VotesBalanceInvalid
       );
 
+      // --- Ensure the conviction lock has expired, cancelled proposals unlock immediately
+      if proposal.proposal_status != PropsStatus::Cancelled {
+        let unlock_block: u64 = VoteLocks::<T>::get(&account_id).get(&proposal_index).copied().unwrap_or(0);
+        ensure!(
+          Self::get_current_block_as_u64() >= unlock_block,
+          Error::<T>::VoteLocked
+        );
+      }
+
       // --- Get balance and remove from storage
       let balance = VotesBalance::<T>::take(proposal_index, &account_id);
 
@@ -775,21 +969,226 @@ pub mod pallet {
         Error::<T>::VoteBalanceZero
       );
 
-      let reserved = T::Currency::reserved_balance(
-        &account_id,
+      VoterVotes::<T>::remove(proposal_index, &account_id);
+
+      Self::remove_vote_lock(&account_id, proposal_index);
+  
+      Ok(())
+    }
+
+    /// Remove a vote from a proposal
+    ///
+    /// Voting must be open
+    ///
+    /// The voters votes are taken out of the proposal votes and the vote balance is unlocked
+    #[pallet::call_index(5)]
+    #[pallet::weight(T::WeightInfo::remove_vote())]
+    pub fn remove_vote(
+      origin: OriginFor<T>, 
+      proposal_index: PropIndex,
+    ) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+      ensure!(
+        Proposals::<T>::contains_key(proposal_index),
+        Error::<T>::ProposalInvalid
       );
 
-      T::Currency::unreserve(
-        &account_id,
-        balance,
+      let proposal = Proposals::<T>::get(proposal_index);
+
+      ensure!(
+        Self::is_voting_open(proposal),
+        Error::<T>::VotingNotOpen
       );
-  
+
+      ensure!(
+        VoterVotes::<T>::contains_key(proposal_index, &account_id),
+        Error::<T>::VoteNotExist
+      );
+
+      // --- Take the voters votes out of the proposal votes
+      let voter_votes: VotesParams = VoterVotes::<T>::take(proposal_index, &account_id);
+
+      Votes::<T>::mutate(
+        proposal_index,
+        |params: &mut VotesParams| {
+          params.yay = params.yay.saturating_sub(voter_votes.yay);
+          params.nay = params.nay.saturating_sub(voter_votes.nay);
+          params.abstain = params.abstain.saturating_sub(voter_votes.abstain);
+        }
+      );
+
+      // --- Unlock the vote balance, removed votes never count towards a conclusion
+      VotesBalance::<T>::remove(proposal_index, &account_id);
+
+      Self::remove_vote_lock(&account_id, proposal_index);
+
+      Ok(())
+    }
+
+    /// Change the direction of a vote on a proposal
+    ///
+    /// Voting must be open
+    ///
+    /// All of the voters votes are moved to the new direction, the vote balance and lock are unchanged
+    #[pallet::call_index(6)]
+    #[pallet::weight(T::WeightInfo::change_vote())]
+    pub fn change_vote(
+      origin: OriginFor<T>, 
+      proposal_index: PropIndex,
+      vote: VoteType,
+    ) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+      ensure!(
+        Proposals::<T>::contains_key(proposal_index),
+        Error::<T>::ProposalInvalid
+      );
+
+      let proposal = Proposals::<T>::get(proposal_index);
+
+      ensure!(
+        Self::is_voting_open(proposal),
+        Error::<T>::VotingNotOpen
+      );
+
+      ensure!(
+        VoterVotes::<T>::contains_key(proposal_index, &account_id),
+        Error::<T>::VoteNotExist
+      );
+
+      let voter_votes: VotesParams = VoterVotes::<T>::get(proposal_index, &account_id);
+
+      let vote_power: u128 = voter_votes.yay
+        .saturating_add(voter_votes.nay)
+        .saturating_add(voter_votes.abstain);
+
+      let mut new_voter_votes: VotesParams = DefaultVotes::<T>::get();
+      Self::add_vote_power(&mut new_voter_votes, &vote, vote_power);
+
+      ensure!(
+        new_voter_votes != voter_votes,
+        Error::<T>::VoteUnchanged
+      );
+
+      // --- Move the voters votes from their previous directions to the new direction
+      Votes::<T>::mutate(
+        proposal_index,
+        |params: &mut VotesParams| {
+          params.yay = params.yay.saturating_sub(voter_votes.yay);
+          params.nay = params.nay.saturating_sub(voter_votes.nay);
+          params.abstain = params.abstain.saturating_sub(voter_votes.abstain);
+          Self::add_vote_power(params, &vote, vote_power);
+        }
+      );
+
+      VoterVotes::<T>::insert(proposal_index, &account_id, new_voter_votes);
+
+      Ok(())
+    }
+
+    /// Join an activate proposal as one of its subnet nodes
+    ///
+    /// Voting must be open
+    ///
+    /// The proposals subnet node stake is reserved from the subnet node and staked when the
+    /// subnet is activated, otherwise it is freed on conclusion
+    #[pallet::call_index(7)]
+    #[pallet::weight(T::WeightInfo::join_proposal())]
+    pub fn join_proposal(
+      origin: OriginFor<T>, 
+      proposal_index: PropIndex,
+    ) -> DispatchResult {
+			let account_id: T::AccountId = ensure_signed(origin)?;
+
+      ensure!(
+        Proposals::<T>::contains_key(proposal_index),
+        Error::<T>::ProposalInvalid
+      );
+
+      let proposal = Proposals::<T>::get(proposal_index);
+
+      ensure!(
+        proposal.proposal_type == PropsType::Activate,
+        Error::<T>::PropsTypeInvalid
+      );
+
+      ensure!(
+        Self::is_voting_open(proposal.clone()),
+        Error::<T>::VotingNotOpen
+      );
+
+      ensure!(
+        proposal.subnet_nodes.iter().any(|node| node.account_id == account_id),
+        Error::<T>::SubnetNodeNotProposed
+      );
+
+      ensure!(
+        !JoinedSubnetNodes::<T>::contains_key(proposal_index, &account_id),
+        Error::<T>::SubnetNodeAlreadyJoined
+      );
+
+      let subnet_node_stake_as_balance = Self::u128_to_balance(proposal.subnet_node_stake);
+
+      ensure!(
+        subnet_node_stake_as_balance.is_some(),
+        Error::<T>::CouldNotConvertToBalance
+      );
+
+      // --- The reserved stake cannot take the account below the existential deposit
+      let required_balance = subnet_node_stake_as_balance.unwrap().saturating_add(T::Currency::minimum_balance());
+
+      ensure!(
+        T::Currency::free_balance(&account_id) >= required_balance,
+        Error::<T>::NotEnoughMinStakeBalance
+      );
+
+      // --- Escrow the subnet nodes stake until the proposal concludes
+      T::Currency::reserve(
+        &account_id,
+        subnet_node_stake_as_balance.unwrap(),
+      ).map_err(|_| Error::<T>::NotEnoughMinStakeBalance)?;
+
+      JoinedSubnetNodes::<T>::insert(proposal_index, &account_id, subnet_node_stake_as_balance.unwrap());
+
+      Self::deposit_event(Event::SubnetNodeJoined(proposal_index, account_id));
+
       Ok(())
     }
   }
 
   #[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+      let block: u64 = Self::convert_block_as_u64(block_number);
+
+      // --- Tally the proposals whose voting ended last block
+      let tally_indexes: Vec<PropIndex> = TallyQueue::<T>::take(block);
+
+      for proposal_index in tally_indexes.iter() {
+        let proposal = Proposals::<T>::get(*proposal_index);
+
+        // --- Cancelled proposals are left in the queue, only tally active proposals
+        if proposal.proposal_status == PropsStatus::Active {
+          let _ = Self::try_tally(*proposal_index, proposal);
+        }
+      }
+
+      // --- Enact the proposals approved an enactment period ago
+      let proposal_indexes: Vec<PropIndex> = EnactmentQueue::<T>::take(block);
+
+      for proposal_index in proposal_indexes.iter() {
+        Self::try_enact(*proposal_index);
+      }
+
+      T::WeightInfo::execute()
+        .saturating_mul(tally_indexes.len() as u64)
+        .saturating_add(
+          T::WeightInfo::enact_proposal().saturating_mul(proposal_indexes.len() as u64)
+        )
+        .saturating_add(T::DbWeight::get().reads_writes(2, 2))
+    }
+
     fn offchain_worker(block_number: BlockNumberFor<T>) {
     }
   }
@@ -797,7 +1196,12 @@ pub mod pallet {
 
 // impl<T: Config + pallet::Config> Pallet<T> {
 impl<T: Config> Pallet<T> {
-  fn try_propose_activate(account_id: T::AccountId, subnet_data: PreSubnetData, mut subnet_nodes: Vec<SubnetNode<T::AccountId>>) -> DispatchResult {
+  fn try_propose_activate(
+    account_id: T::AccountId, 
+    subnet_data: PreSubnetData, 
+    subnet_nodes: Vec<SubnetNode<T::AccountId>>,
+    subnet_node_stake: u128,
+  ) -> DispatchResult {
     // --- Ensure path doesn't already exist in Network or SubnetVoting
     // If it doesn't already exist, then it has either been not proposed or deactivated
     ensure!(
@@ -808,10 +1212,10 @@ impl<T: Config> Pallet<T> {
     // --- Ensure proposal on subnet path not already in progress
     let proposal_status = PropsPathStatus::<T>::get(subnet_data.clone().path);
 
-    // --- Ensure not active
+    // --- Ensure not active or waiting for enactment
     // A proposal can only be active if the subnet is not already initialized into the blockchain
     ensure!(
-      proposal_status != PropsStatus::Active,
+      proposal_status != PropsStatus::Active && proposal_status != PropsStatus::Approved,
       Error::<T>::ProposalInvalid
     );
 
@@ -820,16 +1224,11 @@ impl<T: Config> Pallet<T> {
       Error::<T>::MaxActivateProposals
     );
 
-    // Remove duplicates based on peer_id and account_id
-    subnet_nodes.dedup_by(|a, b| a.peer_id == b.peer_id && a.account_id == b.account_id);
-
-    // --- Ensure PeerId's are validated
-    for node in subnet_nodes.iter() {
-      let valid = Self::validate_peer_id(node.clone().peer_id);
-      ensure!(valid, Error::<T>::InvalidPeerId);
-    }
-
-    // --- Ensure account has enough balance to pay cost of subnet initialization
+    // --- Ensure the subnets node class epochs are ordered if overriding the networks
+    ensure!(
+      T::SubnetVote::is_valid_subnet_node_class_epochs(subnet_data.clone().class_epochs),
+      Error::<T>::InvalidSubnetNodeClassEpochs
+    );
 
     // --- Ensure minimum peers required are already met before going forward
     let subnet_nodes_len: u32 = subnet_nodes.len() as u32;
@@ -841,23 +1240,12 @@ impl<T: Config> Pallet<T> {
       Error::<T>::SubnetNodesLengthInvalid
     );
 
-    // --- Ensure peers have the minimum required stake balance
-    let min_stake: u128 = T::SubnetVote::get_min_stake_balance();
-    let min_stake_as_balance = Self::u128_to_balance(min_stake);
-
     ensure!(
-      min_stake_as_balance.is_some(),
+      Self::u128_to_balance(subnet_node_stake).is_some(),
       Error::<T>::CouldNotConvertToBalance
     );
 
-    for peer in subnet_nodes.clone() {
-      let peer_balance = T::Currency::free_balance(&peer.account_id);
-
-      ensure!(
-        peer_balance >= min_stake_as_balance.unwrap(),
-        Error::<T>::NotEnoughMinStakeBalance
-      );
-    }
+    Self::validate_peers(subnet_nodes.clone())?;
 
     ActivateProposals::<T>::mutate(|n: &mut u32| *n += 1);
 
@@ -889,7 +1277,7 @@ impl<T: Config> Pallet<T> {
     let proposal_status = PropsPathStatus::<T>::get(path.clone());
 
     ensure!(
-      proposal_status != PropsStatus::Active,
+      proposal_status != PropsStatus::Active && proposal_status != PropsStatus::Approved,
       Error::<T>::ProposalInvalid
     );
 
@@ -904,9 +1292,10 @@ impl<T: Config> Pallet<T> {
     proposal: PropsParams<T::AccountId>,
     vote_amount: BalanceOf<T>,
     vote: VoteType,
+    conviction: Conviction,
   ) -> DispatchResult {
     ensure!(
-      Self::is_voting_open(proposal),
+      Self::is_voting_open(proposal.clone()),
       Error::<T>::VotingNotOpen
     );
 
@@ -920,100 +1309,269 @@ impl<T: Config> Pallet<T> {
     );
 
     // --- Ensure enough balance to vote based on vote_amount
+    // Locked balance is shared between proposals so only this proposals votes are counted
+    let proposal_vote_balance = VotesBalance::<T>::get(proposal_index, &account_id).saturating_add(vote_amount);
     ensure!(
-      balance >= vote_amount,
+      balance >= proposal_vote_balance,
       Error::<T>::NotEnoughBalanceToVote
     );
 
     // --- Get vote power
-    let vote_power: u128 = Self::get_voting_power(account_id.clone(), vote_amount);
+    let vote_power: u128 = Self::get_voting_power(account_id.clone(), vote_amount, conviction);
 
-    // --- Reserve voting balance of voter
-    T::Currency::reserve(
+    // --- Increase accounts locked voting balance in relation to proposal index
+    VotesBalance::<T>::insert(proposal_index, account_id.clone(), proposal_vote_balance);
+
+    // --- Lock until the conviction lock periods pass after voting ends
+    let lock_blocks: u64 = Self::convert_block_as_u64(T::VoteLockingPeriod::get()).saturating_mul(conviction.lock_periods());
+    let unlock_block: u64 = proposal.max_block.saturating_add(lock_blocks);
+
+    let mut vote_locks: BTreeMap<PropIndex, u64> = VoteLocks::<T>::get(&account_id);
+    let proposal_unlock_block = vote_locks.entry(proposal_index).or_insert(0);
+    *proposal_unlock_block = (*proposal_unlock_block).max(unlock_block);
+
+    Self::set_vote_lock(&account_id, &vote_locks);
+
+    VoteLocks::<T>::insert(&account_id, vote_locks);
+
+    // --- Save vote
+    Votes::<T>::mutate(
+      proposal_index,
+      |params: &mut VotesParams| Self::add_vote_power(params, &vote, vote_power)
+    );
+
+    // --- Save voters votes so they can be changed or removed while voting is open
+    VoterVotes::<T>::mutate(
+      proposal_index,
       &account_id,
-      vote_amount,
+      |params: &mut VotesParams| Self::add_vote_power(params, &vote, vote_power)
     );
 
-    // --- Increase accounts reserved voting balance in relation to proposal index
-    // VotesBalance::<T>::insert(proposal_index.clone(), account_id.clone(), vote_amount);
+    Ok(())
+  }
 
-    VotesBalance::<T>::mutate(proposal_index.clone(), account_id.clone(), |n| *n += vote_amount);
-    // VotesBalance::<T>::mutate(proposal_index.clone(), account_id.clone(), |n| n.saturating_add(vote_amount));
+  fn add_vote_power(params: &mut VotesParams, vote: &VoteType, vote_power: u128) {
+    match vote {
+      VoteType::Yay => params.yay = params.yay.saturating_add(vote_power),
+      VoteType::Nay => params.nay = params.nay.saturating_add(vote_power),
+      VoteType::Abstain => params.abstain = params.abstain.saturating_add(vote_power),
+    }
+  }
 
-    // --- Save vote
-    if vote == VoteType::Yay {
-      Votes::<T>::mutate(
-        proposal_index.clone(),
-        |params: &mut VotesParams| {
-          params.yay += vote_power;
-        }
+  /// Ensure the subnet nodes are unique and have valid peer IDs
+  fn validate_peers(subnet_nodes: Vec<SubnetNode<T::AccountId>>) -> DispatchResult {
+    let mut account_ids: BTreeSet<T::AccountId> = BTreeSet::new();
+    let mut peer_ids: BTreeSet<PeerId> = BTreeSet::new();
+
+    for node in subnet_nodes.iter() {
+      ensure!(
+        account_ids.insert(node.account_id.clone()) && peer_ids.insert(node.peer_id.clone()),
+        Error::<T>::DuplicateSubnetNode
+      );
+
+      ensure!(
+        Self::validate_peer_id(node.peer_id.clone()),
+        Error::<T>::InvalidPeerId
       );
-    } else if vote == VoteType::Nay {
-      Votes::<T>::mutate(
-        proposal_index.clone(),
-        |params: &mut VotesParams| {
-          params.nay += vote_power;
-        }
-      );  
-    } else {
-      Votes::<T>::mutate(
-        proposal_index.clone(),
-        |params: &mut VotesParams| {
-          params.abstain += vote_power;
-        }
-      );  
     }
 
     Ok(())
   }
 
-  fn validate_peers(subnet_nodes: Vec<SubnetNode<T::AccountId>>) -> DispatchResult {
-    Ok(())
+  /// Free the stake reserved by each subnet node that joined an activate proposal
+  fn unreserve_subnet_nodes(proposal_index: PropIndex) -> BTreeSet<T::AccountId> {
+    JoinedSubnetNodes::<T>::drain_prefix(proposal_index)
+      .map(|(account_id, stake)| {
+        T::Currency::unreserve(&account_id, stake);
+        account_id
+      })
+      .collect()
   }
 
-  fn get_voting_power(account_id: T::AccountId, balance: BalanceOf<T>) -> u128 {
+  fn get_voting_power(account_id: T::AccountId, balance: BalanceOf<T>, conviction: Conviction) -> u128 {
+    let votes: u128 = conviction.votes(Self::balance_to_u128(balance));
+
     let is_submittable_subnet_node_account: bool = T::SubnetVote::is_submittable_subnet_node_account(account_id);
 
     if is_submittable_subnet_node_account {
       let peer_vote_premium = Perbill::from_rational(NodeVotePremium::<T>::get(), 100 as u128);
-      let voting_power = balance.saturating_add(peer_vote_premium * balance);
-   
-      return Self::balance_to_u128(voting_power)
+      return votes.saturating_add(peer_vote_premium * votes)
     }
 
-    Self::balance_to_u128(balance)
+    votes
   }
 
-  fn try_succeed(
-    activator: T::AccountId,
-    proposal_index: PropIndex, 
-    proposal: PropsParams<T::AccountId>, 
-  ) -> DispatchResult {
+  /// Remove a proposal from the accounts vote locks and lock the remaining vote balance
+  fn remove_vote_lock(account_id: &T::AccountId, proposal_index: PropIndex) {
+    let mut vote_locks: BTreeMap<PropIndex, u64> = VoteLocks::<T>::get(account_id);
+    vote_locks.remove(&proposal_index);
+
+    Self::set_vote_lock(account_id, &vote_locks);
+
+    if vote_locks.is_empty() {
+      VoteLocks::<T>::remove(account_id);
+    } else {
+      VoteLocks::<T>::insert(account_id, vote_locks);
+    }
+  }
+
+  /// Lock the largest vote balance of the accounts proposal votes
+  // Votes on concurrent proposals share the same locked balance
+  fn set_vote_lock(account_id: &T::AccountId, vote_locks: &BTreeMap<PropIndex, u64>) {
+    let lock_balance: BalanceOf<T> = vote_locks
+      .keys()
+      .map(|proposal_index| VotesBalance::<T>::get(proposal_index, account_id))
+      .max()
+      .unwrap_or(Zero::zero());
+
+    if lock_balance.is_zero() {
+      T::Currency::remove_lock(MODEL_VOTING_ID, account_id);
+    } else {
+      T::Currency::set_lock(
+        MODEL_VOTING_ID,
+        account_id,
+        lock_balance,
+        WithdrawReasons::except(WithdrawReasons::RESERVE),
+      );
+    }
+  }
+
+  /// Conclude the vote of a proposal whose voting has ended
+  fn try_tally(proposal_index: PropIndex, proposal: PropsParams<T::AccountId>) -> DispatchResult {
+    if proposal.proposal_type == PropsType::Activate {
+      ActivateProposals::<T>::mutate(|n: &mut u32| n.saturating_dec());
+    } else if proposal.proposal_type == PropsType::Deactivate {
+      DeactivateProposals::<T>::mutate(|n: &mut u32| n.saturating_dec());
+    }
+
+    // --- Get status of proposal
+    let votes = Votes::<T>::get(proposal_index);
+
+    let quorum_reached = Self::quorum_reached(votes.clone());
+    let vote_succeeded = Self::vote_succeeded(votes.clone());
+
+    // --- If quorum and vote YAYS aren greater than vote NAYS, then pass, else, defeat
+    if quorum_reached && vote_succeeded {
+      // --- Reserves are kept until the proposal is enacted
+      return Self::try_approve(proposal_index, proposal)
+    }
+
+    // --- Free the subnet nodes stake
+    Self::unreserve_subnet_nodes(proposal_index);
+
+    // --- Give back to proposer
+    if let Some(proposer_stake_as_balance) = Self::u128_to_balance(proposal.proposer_stake) {
+      T::Currency::unreserve(
+        &proposal.proposer,
+        proposer_stake_as_balance,
+      );
+    }
+
+    // --- Don't revert here to allow expired paths to be reproposed
+    if quorum_reached {
+      Self::try_defeat(proposal_index, proposal.path.clone())
+    } else {
+      Self::try_expire(proposal_index, proposal.path.clone())
+    }
+  }
+
+  /// Queue a proposal that passed its vote to be enacted once the enactment period passes
+  fn try_approve(proposal_index: PropIndex, proposal: PropsParams<T::AccountId>) -> DispatchResult {
+    // --- Enact on a later block, `on_initialize` of the current block has already run
+    let enactment_period: u64 = Self::convert_block_as_u64(T::EnactmentPeriod::get()).max(1);
+    let enactment_block: u64 = Self::get_current_block_as_u64().saturating_add(enactment_period);
+
     Proposals::<T>::mutate(
       proposal_index,
       |params: &mut PropsParams<T::AccountId>| {
-        params.proposal_status = PropsStatus::Succeeded;
+        params.proposal_status = PropsStatus::Approved;
+        params.enactment_block = enactment_block;
       },
     );
 
-    PropsPathStatus::<T>::insert(proposal.clone().subnet_data.path, PropsStatus::Succeeded);
+    Self::set_props_path_status(proposal.path, PropsStatus::Approved);
 
     ActiveProposals::<T>::mutate(|n: &mut u32| n.saturating_dec());
 
-    if proposal.proposal_type == PropsType::Activate {
-      Self::try_activate_model(activator, proposal.clone().proposer, proposal.clone().subnet_data);
-    } else {
-      Self::try_deactivate_model(activator, proposal.clone().proposer, proposal.clone().subnet_data);
+    EnactmentQueue::<T>::append(enactment_block, proposal_index);
+
+    Self::deposit_event(Event::ProposalApproved(proposal_index, enactment_block));
+
+    Ok(())
+  }
+
+  /// Enact an approved proposal and record the result
+  fn try_enact(proposal_index: PropIndex) {
+    let mut proposal = Proposals::<T>::get(proposal_index);
+
+    // --- Cancelled proposals are removed from the queue, only enact approved proposals
+    if proposal.proposal_status != PropsStatus::Approved {
+      return
+    }
+
+    // --- Free the subnet nodes stake, it is staked by the network pallet on activation
+    // Only the subnet nodes that joined are registered
+    let joined_subnet_nodes: BTreeSet<T::AccountId> = Self::unreserve_subnet_nodes(proposal_index);
+    proposal.subnet_nodes.retain(|node| joined_subnet_nodes.contains(&node.account_id));
+
+    // --- Unreserve here to pay for initialization fee or give back to proposer
+    if let Some(proposer_stake_as_balance) = Self::u128_to_balance(proposal.proposer_stake) {
+      T::Currency::unreserve(
+        &proposal.proposer,
+        proposer_stake_as_balance,
+      );
     }
 
-    // --- Proposal stake unservered in the `execute`, update to reflect no reserves 
+    let result: DispatchResult = Self::try_succeed(proposal_index, proposal);
+
+    EnactmentResults::<T>::insert(proposal_index, result);
+
+    Self::deposit_event(Event::ProposalEnacted(proposal_index, result));
+  }
+
+  /// Enact a proposal and set its status from the result
+  fn try_succeed(
+    proposal_index: PropIndex, 
+    proposal: PropsParams<T::AccountId>, 
+  ) -> DispatchResult {
+    // --- Enactment has no caller, the proposer is the activator
+    // Enactment runs outside of an extrinsic so a failure must revert its own changes
+    let result: DispatchResult = frame_support::storage::with_storage_layer(|| {
+      match proposal.proposal_type.clone() {
+        PropsType::Activate => Self::try_activate_model(
+          proposal.clone().proposer, 
+          proposal.clone().proposer, 
+          proposal.clone().subnet_data, 
+          proposal.clone().subnet_nodes,
+          proposal.subnet_node_stake,
+        ),
+        PropsType::Deactivate => Self::try_deactivate_model(
+          proposal.clone().proposer, 
+          proposal.clone().proposer, 
+          proposal.clone().subnet_data
+        ),
+        PropsType::ParameterChange(parameter) => Self::try_change_parameter(parameter),
+        PropsType::None => Err(Error::<T>::PropsTypeInvalid.into()),
+      }
+    });
+
+    let proposal_status: PropsStatus = match result {
+      Ok(()) => PropsStatus::Succeeded,
+      Err(_) => PropsStatus::Failed,
+    };
+
     Proposals::<T>::mutate(
       proposal_index,
       |params: &mut PropsParams<T::AccountId>| {
+        params.proposal_status = proposal_status.clone();
+        // --- Proposal stake unreserved before enactment, update to reflect no reserves 
         params.proposer_stake = 0;
       },
     );
-    Ok(())
+
+    Self::set_props_path_status(proposal.subnet_data.path, proposal_status);
+
+    result
   }
 
   fn try_defeat(proposal_index: PropIndex, path: Vec<u8>) -> DispatchResult {
@@ -1024,7 +1582,7 @@ impl<T: Config> Pallet<T> {
       },
     );
   
-    PropsPathStatus::<T>::insert(path.clone(), PropsStatus::Defeated);
+    Self::set_props_path_status(path, PropsStatus::Defeated);
 
     ActiveProposals::<T>::mutate(|n: &mut u32| n.saturating_dec());
 
@@ -1039,7 +1597,7 @@ impl<T: Config> Pallet<T> {
       },
     );
 
-    PropsPathStatus::<T>::insert(path.clone(), PropsStatus::Cancelled);
+    Self::set_props_path_status(path, PropsStatus::Cancelled);
 
     ActiveProposals::<T>::mutate(|n: &mut u32| n.saturating_dec());
 
@@ -1054,13 +1612,44 @@ impl<T: Config> Pallet<T> {
       },
     );
   
-    PropsPathStatus::<T>::insert(path.clone(), PropsStatus::Expired);
+    Self::set_props_path_status(path, PropsStatus::Expired);
 
     ActiveProposals::<T>::mutate(|n: &mut u32| n.saturating_dec());
 
     Ok(())
   }
 
+  /// Parameter change proposals have no subnet path to track
+  fn set_props_path_status(path: Vec<u8>, status: PropsStatus) {
+    if !path.is_empty() {
+      PropsPathStatus::<T>::insert(path, status);
+    }
+  }
+
+  /// Reserve the refundable stake of a deactivate or parameter change proposal
+  fn try_reserve_proposer_stake(account_id: &T::AccountId, proposer_balance: BalanceOf<T>) -> Result<u128, DispatchError> {
+    let proposer_stake: u128 = T::MinProposalStake::get();
+    let proposer_stake_as_balance = Self::u128_to_balance(proposer_stake);
+
+    ensure!(
+      proposer_stake_as_balance.is_some(),
+      Error::<T>::CouldNotConvertToBalance
+    );
+
+    ensure!(
+      proposer_balance >= proposer_stake_as_balance.unwrap(),
+      Error::<T>::NotEnoughSubnetInitializationBalance
+    );
+
+    // --- Reserve balance to be given back once concluded
+    T::Currency::reserve(
+      account_id,
+      proposer_stake_as_balance.unwrap(),
+    )?;
+
+    Ok(proposer_stake)
+  }
+
   /// Is voting active and within voting period
   fn is_voting_open(proposal: PropsParams<T::AccountId>) -> bool {
     let block = Self::get_current_block_as_u64();
@@ -1079,21 +1668,55 @@ impl<T: Config> Pallet<T> {
     total_quorum_votes >= quorum
   }
 
-  /// Activate subnet - Someone must add_subnet once activated
-  fn try_activate_model(activator: T::AccountId, proposer: T::AccountId, subnet_data: PreSubnetData) -> DispatchResult {
+  /// Activate subnet and register its subnet nodes with their previously reserved stake
+  fn try_activate_model(
+    activator: T::AccountId, 
+    proposer: T::AccountId, 
+    subnet_data: PreSubnetData,
+    subnet_nodes: Vec<SubnetNode<T::AccountId>>,
+    subnet_node_stake: u128,
+  ) -> DispatchResult {
     let vote_subnet_data = VoteSubnetData {
       data: subnet_data.clone(),
       active: true,
     };
 
+    let bootstrap_subnet_nodes: Vec<BootstrapSubnetNode<T::AccountId>> = subnet_nodes
+      .into_iter()
+      .map(|node| BootstrapSubnetNode {
+        account_id: node.account_id,
+        peer_id: node.peer_id,
+        stake: subnet_node_stake,
+      })
+      .collect();
+
     T::SubnetVote::vote_activated(
       activator.clone(),
       subnet_data.clone().path, 
       proposer.clone(),
-      vote_subnet_data.clone()
+      vote_subnet_data.clone(),
+      bootstrap_subnet_nodes,
     )
   }
 
+  /// Set a network parameter through the network pallets admin interface
+  fn try_change_parameter(parameter: NetworkParameter) -> DispatchResult {
+    match parameter {
+      NetworkParameter::MaxSubnets(value) => T::NetworkAdminInterface::set_max_models(value),
+      NetworkParameter::MinSubnetNodes(value) => T::NetworkAdminInterface::set_min_subnet_nodes(value),
+      NetworkParameter::MaxSubnetNodes(value) => T::NetworkAdminInterface::set_max_subnet_nodes(value),
+      NetworkParameter::MinStakeBalance(value) => T::NetworkAdminInterface::set_min_stake_balance(value),
+      NetworkParameter::BaseSubnetReward(value) => T::NetworkAdminInterface::set_base_subnet_reward(value),
+      NetworkParameter::SlashPercentage(value) => T::NetworkAdminInterface::set_slash_percentage(value),
+      NetworkParameter::SubnetRemovalBounty(value) => T::NetworkAdminInterface::set_subnet_removal_bounty(value),
+      NetworkParameter::StakeUnbondingEpochs(value) => T::NetworkAdminInterface::set_stake_unbonding_epochs(value),
+      NetworkParameter::ValidatorsPerEpoch(value) => T::NetworkAdminInterface::set_validators_per_epoch(value),
+      NetworkParameter::SubnetNodeClassEpochs(class_id, value) => T::NetworkAdminInterface::set_subnet_node_class_epochs(class_id, value),
+      NetworkParameter::NodeClassDemotionAbsentThreshold(value) => T::NetworkAdminInterface::set_node_class_demotion_absent_threshold(value),
+      NetworkParameter::NodeClassDemotionPenaltyThreshold(value) => T::NetworkAdminInterface::set_node_class_demotion_penalty_threshold(value),
+    }
+  }
+
   fn try_deactivate_model(activator: T::AccountId, proposer: T::AccountId, subnet_data: PreSubnetData) -> DispatchResult {
     let vote_subnet_data = VoteSubnetData {
       data: subnet_data.clone(),
@@ -1154,10 +1777,14 @@ impl<T: Config> AdminInterface for Pallet<T> {
   fn set_majority(value: u128) -> DispatchResult {
 		Self::set_majority(value)
 	}
+	fn cancel_enactment(proposal_index: PropIndex) -> DispatchResult {
+		Self::cancel_enactment(proposal_index)
+	}
 }
 
 pub trait AdminInterface {
 	fn set_peer_vote_premium(value: u128) -> DispatchResult;
   fn set_quorum(value: u128) -> DispatchResult;
   fn set_majority(value: u128) -> DispatchResult;
+  fn cancel_enactment(proposal_index: PropIndex) -> DispatchResult;
 }
\ No newline at end of file
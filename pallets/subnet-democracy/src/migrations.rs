@@ -0,0 +1,134 @@
+// Copyright (C) Hypertensor.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+// Storage migrations of the subnet democracy pallet
+
+use super::*;
+use codec::{Decode, Encode};
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use frame_support::weights::Weight;
+use sp_runtime::RuntimeDebug;
+use pallet_network::migrations::v1::OldPreSubnetData;
+
+pub mod v1 {
+  use super::*;
+
+  /// `PropsParams` before subnet node stake was escrowed, the subnet data carried node class
+  /// epochs and approved proposals were queued for enactment
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+  pub struct OldPropsParams<AccountId> {
+    pub proposer: AccountId,
+    pub proposer_stake: u128,
+    pub proposal_status: PropsStatus,
+    pub proposal_type: PropsType,
+    pub path: Vec<u8>,
+    pub subnet_data: OldPreSubnetData,
+    pub subnet_nodes: Vec<SubnetNode<AccountId>>,
+    pub max_block: u64,
+  }
+
+  /// Translate the stored proposals to the current `PropsParams` layout and queue the active
+  /// proposals to be tallied
+  // Proposed subnets keep the networks subnet node class epochs
+  // No proposal was approved or failed before the enactment queue, so statuses decode unchanged
+  // and `enactment_block` starts unset
+  // Votes were reserved before vote locks, they are unreserved and the votes of active proposals
+  // are locked until voting ends
+  pub struct MigrateToV1<T>(frame_support::sp_std::marker::PhantomData<T>);
+
+  impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+    fn on_runtime_upgrade() -> Weight {
+      let db_weight = T::DbWeight::get();
+
+      if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return db_weight.reads(1)
+      }
+
+      let mut reads: u64 = 1;
+      let mut writes: u64 = 0;
+
+      // --- Subnet nodes of in flight activate proposals join with the current minimum stake
+      let min_stake_balance: u128 = T::SubnetVote::get_min_stake_balance();
+
+      // --- Proposals whose voting already ended are tallied on the next block
+      let next_block: u64 = Pallet::<T>::get_current_block_as_u64().saturating_add(1);
+
+      Proposals::<T>::translate::<OldPropsParams<T::AccountId>, _>(|proposal_index, old| {
+        reads += 1;
+        writes += 1;
+
+        if old.proposal_status == PropsStatus::Active {
+          TallyQueue::<T>::append(old.max_block.saturating_add(1).max(next_block), proposal_index);
+          writes += 1;
+        }
+
+        let subnet_node_stake: u128 = match old.proposal_type {
+          PropsType::Activate => min_stake_balance,
+          _ => 0,
+        };
+
+        Some(PropsParams {
+          proposer: old.proposer,
+          proposer_stake: old.proposer_stake,
+          subnet_node_stake: subnet_node_stake,
+          proposal_status: old.proposal_status,
+          proposal_type: old.proposal_type,
+          path: old.path,
+          subnet_data: old.subnet_data.into(),
+          subnet_nodes: old.subnet_nodes,
+          max_block: old.max_block,
+          enactment_block: 0,
+        })
+      });
+
+      // --- Move the reserved vote balances to vote locks
+      let votes: Vec<(PropIndex, T::AccountId, BalanceOf<T>)> = VotesBalance::<T>::iter().collect();
+      let mut vote_locks: BTreeMap<T::AccountId, BTreeMap<PropIndex, u64>> = BTreeMap::new();
+
+      for (proposal_index, account_id, balance) in votes {
+        reads += 2;
+        writes += 1;
+
+        T::Currency::unreserve(&account_id, balance);
+
+        let proposal = Proposals::<T>::get(proposal_index);
+
+        if proposal.proposal_status == PropsStatus::Active {
+          vote_locks
+            .entry(account_id)
+            .or_insert(BTreeMap::new())
+            .insert(proposal_index, proposal.max_block);
+        } else {
+          // --- Concluded proposals could already be unreserved
+          VotesBalance::<T>::remove(proposal_index, &account_id);
+          writes += 1;
+        }
+      }
+
+      for (account_id, account_vote_locks) in vote_locks.iter() {
+        reads += 1;
+        writes += 2;
+
+        Pallet::<T>::set_vote_lock(account_id, account_vote_locks);
+        VoteLocks::<T>::insert(account_id, account_vote_locks);
+      }
+
+      StorageVersion::new(1).put::<Pallet<T>>();
+      writes += 1;
+
+      db_weight.reads_writes(reads, writes)
+    }
+  }
+}
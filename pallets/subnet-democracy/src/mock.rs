@@ -147,6 +147,8 @@ impl pallet_network::Config for Test {
   type OffchainSignature = Signature;
 	type OffchainPublic = AccountPublic;
   type Randomness = InsecureRandomnessCollectiveFlip;
+  type ElectionRandomness = pallet_network::BlockRandomness<Test>;
+  type StakeVaultSubsidy = ();
 	type PalletId = NetworkPalletId;
   type SubnetInitializationCost = SubnetInitializationCost;
 }
@@ -154,20 +156,27 @@ impl pallet_network::Config for Test {
 parameter_types! {
 	pub const VotingPeriod: BlockNumber = DAYS * 21;
 	pub const EnactmentPeriod: BlockNumber = DAYS * 7;
+	pub const VoteLockingPeriod: BlockNumber = DAYS * 7;
   pub const MinProposalStake: u128 = 100_000_000_000_000_000_000; // 100 * 1e18
+  pub const MaxBaseSubnetReward: u128 = 100_000_000_000_000_000_000; // 100 * 1e18
+  pub const MaxMinStakeBalance: u128 = 100_000_000_000_000_000_000_000; // 100,000 * 1e18
 }
 
 impl Config for Test {
 	type WeightInfo = ();
 	type RuntimeEvent = RuntimeEvent;
 	type SubnetVote = Network;
+	type NetworkAdminInterface = Network;
 	type Currency = Balances;
 	type MaxActivateProposals = ConstU32<32>;
 	type MaxDeactivateProposals = ConstU32<32>;
 	type MaxProposals = ConstU32<32>;
 	type VotingPeriod = VotingPeriod;
 	type EnactmentPeriod = EnactmentPeriod;
+	type VoteLockingPeriod = VoteLockingPeriod;
   type MinProposalStake = MinProposalStake; // 100 * 1e18
+  type MaxBaseSubnetReward = MaxBaseSubnetReward;
+  type MaxMinStakeBalance = MaxMinStakeBalance;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
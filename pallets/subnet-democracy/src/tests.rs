@@ -21,12 +21,13 @@ use frame_support::{
 };
 use log::info;
 use sp_core::{H256, U256};
-use frame_support::traits::Currency;
+use frame_support::traits::{Currency, Hooks};
 use sp_core::OpaquePeerId as PeerId;
 use crate::{
   Error, SubnetNode, PropsType, SubnetVote, VotesBalance, ReservableCurrency, PropCount, VoteType,
   Votes, ActiveProposals, Proposals, PropsStatus, Quorum, PropsPathStatus, BalanceOf, PreSubnetData,
-  ActivateProposals,
+  ActivateProposals, Conviction, VoteLocks, MODEL_VOTING_ID, VoterVotes, EnactmentQueue,
+  EnactmentResults, NetworkParameter, JoinedSubnetNodes,
 };
 type AccountIdOf<Test> = <Test as frame_system::Config>::AccountId;
 
@@ -54,6 +55,7 @@ fn default_add_subnet_data() -> PreSubnetData {
   let subnet_data = PreSubnetData {
     path: DEFAULT_MODEL_PATH.into(),
 		memory_mb: 50000,
+		class_epochs: Vec::new(),
   };
   subnet_data
 }
@@ -62,6 +64,7 @@ fn default_existing_add_subnet_data() -> PreSubnetData {
   let subnet_data = PreSubnetData {
     path: DEFAULT_EXISTING_MODEL_PATH.into(),
 		memory_mb: 50000,
+		class_epochs: Vec::new(),
   };
   subnet_data
 }
@@ -94,6 +97,7 @@ fn build_existing_model(start: u32, end: u32) {
   let add_subnet_data = PreSubnetData {
     path: model_path.clone(),
     memory_mb: 50000,
+    class_epochs: Vec::new(),
   };
   assert_ok!(
     Network::activate_subnet(
@@ -190,7 +194,7 @@ fn post_success_proposal_activate_ensures(path: Vec<u8>, proposal_index: u32, pr
 
 fn post_cast_vote_ensures(proposal_index: u32, voter: u32) {
   assert_err!(
-    SubnetVoting::unreserve(
+    SubnetVoting::unlock(
       RuntimeOrigin::signed(account(voter)),
       proposal_index, 
     ),
@@ -198,19 +202,34 @@ fn post_cast_vote_ensures(proposal_index: u32, voter: u32) {
   );
 }
 
-fn post_yay_ensures(proposal_index: u32, prev_votes: u128, voter: u32, vote_amount: u128) {
-  let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
+fn vote_lock_balance(account_id: AccountId) -> u128 {
+  Balances::locks(&account_id)
+    .iter()
+    .find(|lock| lock.id == MODEL_VOTING_ID)
+    .map_or(0, |lock| lock.amount)
+}
 
+fn post_vote_lock_ensures(proposal_index: u32, voter: u32, vote_amount: u128) {
   let reserved_balance = Balances::reserved_balance(&account(voter));
-  let voting_power = SubnetVoting::get_voting_power(account(voter), vote_amount);
 
+  // Votes are locked rather than reserved
   if voter == 0 {
-    assert_eq!(reserved_balance, vote_amount + model_initialization_cost);
+    let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
+    let min_stake = pallet_network::MinStakeBalance::<Test>::get();
+    assert_eq!(reserved_balance, model_initialization_cost + min_stake);
   } else {
-    assert_eq!(reserved_balance, vote_amount);
+    assert_eq!(reserved_balance, 0);
   }
 
-  assert_eq!(VotesBalance::<Test>::get(proposal_index, account(voter)), voting_power);  
+  assert_eq!(vote_lock_balance(account(voter)), vote_amount);
+  assert_eq!(VotesBalance::<Test>::get(proposal_index, account(voter)), vote_amount);
+  assert!(VoteLocks::<Test>::get(account(voter)).contains_key(&proposal_index));
+}
+
+fn post_yay_ensures(proposal_index: u32, prev_votes: u128, voter: u32, vote_amount: u128) {
+  post_vote_lock_ensures(proposal_index, voter, vote_amount);
+
+  let voting_power = SubnetVoting::get_voting_power(account(voter), vote_amount, Conviction::None);
 
   let votes = Votes::<Test>::get(proposal_index);
 
@@ -218,18 +237,9 @@ fn post_yay_ensures(proposal_index: u32, prev_votes: u128, voter: u32, vote_amou
 }
 
 fn post_nay_ensures(proposal_index: u32, prev_votes: u128, voter: u32, vote_amount: u128) {
-  let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
-
-  let reserved_balance = Balances::reserved_balance(&account(voter));
-  let voting_power = SubnetVoting::get_voting_power(account(voter), vote_amount);
-
-  if voter == 0 {
-    assert_eq!(reserved_balance, vote_amount + model_initialization_cost);
-  } else {
-    assert_eq!(reserved_balance, vote_amount);
-  }
+  post_vote_lock_ensures(proposal_index, voter, vote_amount);
 
-  assert_eq!(VotesBalance::<Test>::get(proposal_index, account(voter)), voting_power);
+  let voting_power = SubnetVoting::get_voting_power(account(voter), vote_amount, Conviction::None);
 
   let votes = Votes::<Test>::get(proposal_index);
 
@@ -237,16 +247,9 @@ fn post_nay_ensures(proposal_index: u32, prev_votes: u128, voter: u32, vote_amou
 }
 
 fn post_abstain_ensures(proposal_index: u32, prev_votes: u128, voter: u32, vote_amount: u128) {
-  let reserved_balance = Balances::reserved_balance(&account(voter));
-  if voter == 0 {
-    let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
-    assert_eq!(reserved_balance, vote_amount + model_initialization_cost);  
-  } else {
-    assert_eq!(reserved_balance, vote_amount);  
-  }
+  post_vote_lock_ensures(proposal_index, voter, vote_amount);
 
-  let voting_power = SubnetVoting::get_voting_power(account(voter), vote_amount);
-  assert_eq!(VotesBalance::<Test>::get(proposal_index, account(voter)), voting_power);
+  let voting_power = SubnetVoting::get_voting_power(account(voter), vote_amount, Conviction::None);
 
   let votes = Votes::<Test>::get(proposal_index);
 
@@ -254,7 +257,34 @@ fn post_abstain_ensures(proposal_index: u32, prev_votes: u128, voter: u32, vote_
 }
 
 
+fn post_execute_approved_ensures(proposal_index: u32, path: Vec<u8>) {
+  let proposal = Proposals::<Test>::get(proposal_index);
+  assert_eq!(proposal.proposal_status, PropsStatus::Approved);
+  assert_eq!(proposal.enactment_block, System::block_number() + EnactmentPeriod::get());
+
+  let proposal_path_status = PropsPathStatus::<Test>::get(path.clone());
+  assert_eq!(proposal_path_status, PropsStatus::Approved);
+
+  let enactment_queue = EnactmentQueue::<Test>::get(proposal.enactment_block);
+  assert!(enactment_queue.contains(&proposal_index));
+
+  // --- Nothing is enacted until the enactment block
+  assert_eq!(EnactmentResults::<Test>::get(proposal_index), None);
+
+  post_proposal_concluded(proposal_index, path.clone());
+}
+
+fn enact_proposal(proposal_index: u32) {
+  let proposal = Proposals::<Test>::get(proposal_index);
+  System::set_block_number(proposal.enactment_block);
+  SubnetVoting::on_initialize(proposal.enactment_block);
+
+  assert_eq!(EnactmentQueue::<Test>::contains_key(proposal.enactment_block), false);
+}
+
 fn post_activate_execute_succeeded_ensures(proposal_index: u32, path: Vec<u8>) {
+  assert_eq!(EnactmentResults::<Test>::get(proposal_index), Some(Ok(())));
+
   let vote_subnet_data = pallet_network::SubnetActivated::<Test>::get(path.clone());
   assert_eq!(vote_subnet_data.active, true);
 
@@ -277,9 +307,23 @@ fn post_activate_execute_succeeded_ensures(proposal_index: u32, path: Vec<u8>) {
   let model_data = pallet_network::SubnetsData::<Test>::get(subnet_id);
   let model_path: Vec<u8> = model_data.unwrap().path;
   assert_eq!(model_path, path);
+
+  // Check that the proposals subnet nodes have been staked into the subnet
+  let total_subnet_nodes = pallet_network::TotalSubnetNodes::<Test>::get(subnet_id);
+  assert_eq!(total_subnet_nodes, proposal.subnet_nodes.len() as u32);
+
+  for subnet_node in proposal.subnet_nodes.iter() {
+    let subnet_node_data = pallet_network::SubnetNodesData::<Test>::get(subnet_id, subnet_node.account_id.clone());
+    assert_eq!(subnet_node_data.peer_id, subnet_node.peer_id);
+
+    let account_subnet_stake = pallet_network::AccountSubnetStake::<Test>::get(subnet_node.account_id.clone(), subnet_id);
+    assert_eq!(account_subnet_stake, proposal.subnet_node_stake);
+  }
 }
 
 fn post_deactivate_succeeded_execute_ensures(proposal_index: u32, path: Vec<u8>) {
+  assert_eq!(EnactmentResults::<Test>::get(proposal_index), Some(Ok(())));
+
   let vote_subnet_data = pallet_network::SubnetActivated::<Test>::get(path.clone());
   assert_eq!(vote_subnet_data.active, false);
 
@@ -348,6 +392,7 @@ fn post_proposal_concluded(proposal_index: u32, path: Vec<u8>) {
       proposal_index,
       1000,
       VoteType::Yay,
+      Conviction::None,
     ),
     Error::<Test>::VotingNotOpen
   );
@@ -366,24 +411,29 @@ fn post_proposal_concluded(proposal_index: u32, path: Vec<u8>) {
   assert_ne!(proposal_path_status, PropsStatus::None);
 }
 
-fn post_proposal_conclusion_unreserves(proposal_index: u32, start: u32, end: u32, vote_amount: u128) {
+fn post_proposal_conclusion_unlocks(proposal_index: u32, start: u32, end: u32, vote_amount: u128) {
   let proposal = Proposals::<Test>::get(proposal_index);
   for n in start..end {
     let beginning_balance = Balances::free_balance(&account(n));
     let votes_balance = VotesBalance::<Test>::get(proposal_index, account(n));
+    assert_eq!(vote_lock_balance(account(n)), votes_balance);
 
     assert_ok!(
-      SubnetVoting::unreserve(
+      SubnetVoting::unlock(
         RuntimeOrigin::signed(account(n)),
         proposal_index, 
       )
     );
 
+    // Locked balance was never moved out of the free balance
     let balance = Balances::free_balance(&account(n));
-    assert_eq!(balance, beginning_balance + votes_balance);
+    assert_eq!(balance, beginning_balance);
+    assert_eq!(vote_lock_balance(account(n)), 0);
 
     let votes_balance = VotesBalance::<Test>::get(proposal_index, account(n));
     assert_eq!(votes_balance, 0);
+
+    assert_eq!(VoteLocks::<Test>::contains_key(account(n)), false);
   }
   // check proposers reserve
   let proposers_reserve = Balances::reserved_balance(&account(0));
@@ -404,8 +454,39 @@ fn build_propose_activate(path: Vec<u8>, start: u32, end: u32, deposit_amount: u
       PropsType::Activate,
     )
   );
-  let proposal_index = PropCount::<Test>::get();
-  return proposal_index - 1
+  let proposal_index = PropCount::<Test>::get() - 1;
+
+  join_subnet_nodes(proposal_index, start, end);
+
+  proposal_index
+}
+
+fn join_subnet_nodes(proposal_index: u32, start: u32, end: u32) {
+  for n in start..end {
+    assert_ok!(
+      SubnetVoting::join_proposal(
+        RuntimeOrigin::signed(account(n)),
+        proposal_index,
+      )
+    );
+  }
+}
+
+fn propose_activate_without_joining(start: u32, end: u32, deposit_amount: u128) -> u32 {
+  let subnet_nodes = build_subnet_nodes(start, end, deposit_amount);
+
+  let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
+  let _ = Balances::deposit_creating(&account(0), model_initialization_cost+1000);
+
+  assert_ok!(
+    SubnetVoting::propose(
+      RuntimeOrigin::signed(account(0)),
+      default_add_subnet_data(), 
+      subnet_nodes,
+      PropsType::Activate,
+    )
+  );
+  PropCount::<Test>::get() - 1
 }
 
 
@@ -463,7 +544,7 @@ fn test_propose_activate() {
     let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
     let _ = Balances::deposit_creating(&account(0), model_initialization_cost);
 
-    let subnet_nodes = build_subnet_nodes(0, min_subnet_nodes, min_stake);
+    let subnet_nodes = build_subnet_nodes(0, min_subnet_nodes, min_stake + EXISTENTIAL_DEPOSIT);
 
     assert_ok!(
       SubnetVoting::propose(
@@ -481,6 +562,109 @@ fn test_propose_activate() {
   })
 }
 
+#[test]
+fn test_join_proposal_reserves_subnet_node_stake() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let min_stake = pallet_network::MinStakeBalance::<Test>::get();
+    let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
+
+    let proposal_index = propose_activate_without_joining(0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    let proposal = Proposals::<Test>::get(proposal_index);
+    assert_eq!(proposal.subnet_node_stake, min_stake);
+
+    // --- Proposing doesn't reserve anything from the subnet nodes
+    assert_eq!(Balances::reserved_balance(&account(0)), model_initialization_cost);
+    for n in 1..min_subnet_nodes {
+      assert_eq!(Balances::reserved_balance(&account(n)), 0);
+    }
+
+    join_subnet_nodes(proposal_index, 0, min_subnet_nodes);
+
+    for n in 0..min_subnet_nodes {
+      let reserved_balance = Balances::reserved_balance(&account(n));
+      if n == 0 {
+        assert_eq!(reserved_balance, model_initialization_cost + min_stake);
+      } else {
+        assert_eq!(reserved_balance, min_stake);
+      }
+      assert_eq!(JoinedSubnetNodes::<Test>::get(proposal_index, account(n)), Some(min_stake));
+    }
+  })
+}
+
+#[test]
+fn test_join_proposal_existential_deposit_err() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let min_stake = pallet_network::MinStakeBalance::<Test>::get();
+
+    // Reserving the entire balance would take the account below the existential deposit
+    let proposal_index = propose_activate_without_joining(1, min_subnet_nodes + 1, min_stake);
+
+    assert_err!(
+      SubnetVoting::join_proposal(
+        RuntimeOrigin::signed(account(1)),
+        proposal_index,
+      ),
+      Error::<Test>::NotEnoughMinStakeBalance
+    );
+  })
+}
+
+#[test]
+fn test_join_proposal_not_proposed_err() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = propose_activate_without_joining(1, min_subnet_nodes + 1, DEFAULT_DEPOSIT_AMOUNT);
+
+    let _ = Balances::deposit_creating(&account(255), DEFAULT_DEPOSIT_AMOUNT);
+
+    assert_err!(
+      SubnetVoting::join_proposal(
+        RuntimeOrigin::signed(account(255)),
+        proposal_index,
+      ),
+      Error::<Test>::SubnetNodeNotProposed
+    );
+  })
+}
+
+#[test]
+fn test_join_proposal_already_joined_err() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_propose_activate(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    assert_err!(
+      SubnetVoting::join_proposal(
+        RuntimeOrigin::signed(account(1)),
+        proposal_index,
+      ),
+      Error::<Test>::SubnetNodeAlreadyJoined
+    );
+  })
+}
+
+#[test]
+fn test_join_proposal_voting_not_open_err() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = propose_activate_without_joining(0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    System::set_block_number(System::block_number() + VotingPeriod::get() + 1);
+
+    assert_err!(
+      SubnetVoting::join_proposal(
+        RuntimeOrigin::signed(account(1)),
+        proposal_index,
+      ),
+      Error::<Test>::VotingNotOpen
+    );
+  })
+}
+
 #[test]
 fn test_propose_activate_duplicate_nodes() {
   new_test_ext().execute_with(|| {
@@ -509,7 +693,30 @@ fn test_propose_activate_duplicate_nodes() {
         subnet_nodes,
         PropsType::Activate,
       ),
-      Error::<Test>::SubnetNodesLengthInvalid,
+      Error::<Test>::DuplicateSubnetNode,
+    );
+  })
+}
+
+#[test]
+fn test_propose_activate_duplicate_peer_id() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
+    let _ = Balances::deposit_creating(&account(0), model_initialization_cost);
+
+    let mut subnet_nodes = build_subnet_nodes(0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+    // Unique account claiming another nodes peer ID
+    subnet_nodes[1].peer_id = peer(0);
+
+    assert_err!(
+      SubnetVoting::propose(
+        RuntimeOrigin::signed(account(0)),
+        default_add_subnet_data(), 
+        subnet_nodes,
+        PropsType::Activate,
+      ),
+      Error::<Test>::DuplicateSubnetNode,
     );
   })
 }
@@ -601,21 +808,17 @@ fn test_propose_activate_peers_min_length_err() {
 }
 
 #[test]
-fn test_propose_activate_peers_balance_err() {
+fn test_join_proposal_balance_err() {
   new_test_ext().execute_with(|| {
     let min_subnet_nodes = get_default_min_subnet_nodes();
     let min_stake = pallet_network::MinStakeBalance::<Test>::get();
-    let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
-    let _ = Balances::deposit_creating(&account(0), model_initialization_cost);
 
-    let subnet_nodes = build_subnet_nodes(0, min_subnet_nodes, min_stake-10000);
+    let proposal_index = propose_activate_without_joining(1, min_subnet_nodes + 1, min_stake-10000);
 
     assert_err!(
-      SubnetVoting::propose(
-        RuntimeOrigin::signed(account(0)),
-        default_add_subnet_data(),
-        subnet_nodes,
-        PropsType::Activate,
+      SubnetVoting::join_proposal(
+        RuntimeOrigin::signed(account(1)),
+        proposal_index,
       ),
       Error::<Test>::NotEnoughMinStakeBalance
     );
@@ -678,6 +881,7 @@ fn test_cast_vote_activate_yay() {
         prop_count,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Yay,
+        Conviction::None,
       )
     );
 
@@ -695,6 +899,7 @@ fn test_cast_vote_activate_yay_props_exists_err() {
         0,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Yay,
+        Conviction::None,
       ),
       Error::<Test>::ProposalInvalid
     );
@@ -721,6 +926,7 @@ fn test_cast_vote_activate_yay_voting_not_open_err() {
         proposal_index,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Yay,
+        Conviction::None,
       ),
       Error::<Test>::VotingNotOpen
     );
@@ -745,6 +951,7 @@ fn test_cast_vote_activate_yay_not_enough_balance_err() {
         proposal_index,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Yay,
+        Conviction::None,
       ),
       Error::<Test>::NotEnoughBalanceToVote
     );
@@ -769,6 +976,7 @@ fn test_cast_vote_activate_nay() {
         prop_count,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Nay,
+        Conviction::None,
       )
     );
 
@@ -786,6 +994,7 @@ fn test_cast_vote_activate_nay_props_exists_err() {
         0,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Nay,
+        Conviction::None,
       ),
       Error::<Test>::ProposalInvalid
     );
@@ -812,6 +1021,7 @@ fn test_cast_vote_activate_nay_voting_not_open_err() {
         proposal_index,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Nay,
+        Conviction::None,
       ),
       Error::<Test>::VotingNotOpen
     );
@@ -836,6 +1046,7 @@ fn test_cast_vote_activate_nay_not_enough_balance_err() {
         proposal_index,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Nay,
+        Conviction::None,
       ),
       Error::<Test>::NotEnoughBalanceToVote
     );
@@ -860,6 +1071,7 @@ fn test_cast_vote_activate_abstain() {
         prop_count,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Abstain,
+        Conviction::None,
       )
     );
 
@@ -877,6 +1089,7 @@ fn test_cast_vote_activate_abstain_props_exists_err() {
         0,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Abstain,
+        Conviction::None,
       ),
       Error::<Test>::ProposalInvalid
     );
@@ -903,6 +1116,7 @@ fn test_cast_vote_activate_abstain_voting_not_open_err() {
         proposal_index,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Abstain,
+        Conviction::None,
       ),
       Error::<Test>::VotingNotOpen
     );
@@ -926,6 +1140,7 @@ fn test_cast_vote_activate_abstain_not_enough_balance_err() {
         proposal_index,
         DEFAUT_VOTE_AMOUNT,
         VoteType::Abstain,
+        Conviction::None,
       ),
       Error::<Test>::NotEnoughBalanceToVote
     );
@@ -949,6 +1164,7 @@ fn test_execute_activate_succeeded() {
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
           VoteType::Yay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -964,9 +1180,13 @@ fn test_execute_activate_succeeded() {
       )
     );
 
+    post_execute_approved_ensures(proposal_index, DEFAULT_MODEL_PATH.into());
+
+    enact_proposal(proposal_index);
+
     post_activate_execute_succeeded_ensures(proposal_index, DEFAULT_MODEL_PATH.into());
 
-    post_proposal_conclusion_unreserves(proposal_index, 0, min_subnet_nodes, DEFAUT_VOTE_AMOUNT);
+    post_proposal_conclusion_unlocks(proposal_index, 0, min_subnet_nodes, DEFAUT_VOTE_AMOUNT);
   })
 }
 
@@ -992,6 +1212,7 @@ fn test_execute_activate_succeeded_reexecute() {
             proposal_index,
             DEFAUT_VOTE_AMOUNT,
             VoteType::Yay,
+            Conviction::None,
           )
         );
         post_cast_vote_ensures(proposal_index, n);
@@ -1006,10 +1227,14 @@ fn test_execute_activate_succeeded_reexecute() {
           proposal_index,
         )
       );
+
+      post_execute_approved_ensures(proposal_index, DEFAULT_MODEL_PATH.into());
+
+      enact_proposal(proposal_index);
   
       post_activate_execute_succeeded_ensures(proposal_index, DEFAULT_MODEL_PATH.into());
   
-      post_proposal_conclusion_unreserves(proposal_index, 0, min_subnet_nodes, DEFAUT_VOTE_AMOUNT);  
+      post_proposal_conclusion_unlocks(proposal_index, 0, min_subnet_nodes, DEFAUT_VOTE_AMOUNT);  
     }
   })
 }
@@ -1037,6 +1262,7 @@ fn test_execute_activate_succeeded_reexecute_expired_enactment() {
             proposal_index,
             DEFAUT_VOTE_AMOUNT,
             VoteType::Yay,
+            Conviction::None,
           )
         );
         post_cast_vote_ensures(proposal_index, n);
@@ -1091,6 +1317,7 @@ fn test_execute_voting_period_err() {
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
           VoteType::Yay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -1123,6 +1350,7 @@ fn test_execute_enactment_period_err() {
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
           VoteType::Yay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -1162,6 +1390,7 @@ fn test_execute_quorum_not_reached_err() {
           proposal_index,
           1, // too low to reach minimum quorum
           VoteType::Yay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -1195,7 +1424,7 @@ fn test_execute_quorum_not_reached_err() {
     assert_eq!(is_active.active, false);
 
 
-    post_proposal_conclusion_unreserves(proposal_index, 0, min_subnet_nodes, DEFAUT_VOTE_AMOUNT);
+    post_proposal_conclusion_unlocks(proposal_index, 0, min_subnet_nodes, DEFAUT_VOTE_AMOUNT);
   })
 }
 
@@ -1229,6 +1458,7 @@ fn test_execute_defeated() {
           proposal_index,
           vote_amount,
           VoteType::Yay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -1242,6 +1472,7 @@ fn test_execute_defeated() {
           proposal_index,
           vote_amount,
           VoteType::Nay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -1264,7 +1495,7 @@ fn test_execute_defeated() {
     let proposal = Proposals::<Test>::get(proposal_index);
     assert_eq!(proposal.proposal_status, PropsStatus::Defeated);
 
-    post_proposal_conclusion_unreserves(proposal_index, 0, min_subnet_nodes, vote_amount);
+    post_proposal_conclusion_unlocks(proposal_index, 0, min_subnet_nodes, vote_amount);
   })
 }
 
@@ -1285,6 +1516,7 @@ fn test_execute_cancelled() {
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
           VoteType::Yay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -1298,6 +1530,12 @@ fn test_execute_cancelled() {
     );
 
     post_activate_cancel_ensures(prop_count, DEFAULT_MODEL_PATH.into());
+
+    // Subnet node stake is freed on cancel, only the votes remain reserved
+    for n in 1..min_subnet_nodes {
+      let reserved_balance = Balances::reserved_balance(&account(n));
+      assert_eq!(reserved_balance, DEFAUT_VOTE_AMOUNT);
+    }
   })
 }
 
@@ -1318,6 +1556,7 @@ fn test_execute_cancelled_not_proposer_err() {
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
           VoteType::Yay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -1350,6 +1589,7 @@ fn test_execute_cancelled_proposal_index_err() {
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
           VoteType::Yay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -1382,6 +1622,7 @@ fn test_execute_cancelled_vote_completed_err() {
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
           VoteType::Yay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -1542,6 +1783,7 @@ fn test_execute_deactivate_succeeded() {
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
           VoteType::Yay,
+          Conviction::None,
         )
       );
       post_cast_vote_ensures(proposal_index, n);
@@ -1557,9 +1799,13 @@ fn test_execute_deactivate_succeeded() {
       )
     );
 
+    post_execute_approved_ensures(proposal_index, DEFAULT_EXISTING_MODEL_PATH.into());
+
+    enact_proposal(proposal_index);
+
     post_deactivate_succeeded_execute_ensures(proposal_index, DEFAULT_EXISTING_MODEL_PATH.into());
 
-    post_proposal_conclusion_unreserves(proposal_index, 0, min_subnet_nodes, DEFAUT_VOTE_AMOUNT);
+    post_proposal_conclusion_unlocks(proposal_index, 0, min_subnet_nodes, DEFAUT_VOTE_AMOUNT);
   })
 }
 
@@ -1619,21 +1865,25 @@ fn test_balance_on_multiple_votes() {
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
           VoteType::Yay,
+          Conviction::None,
         )
       );
     }
 
     let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
+    let min_stake = pallet_network::MinStakeBalance::<Test>::get();
 
     for n in 0..min_subnet_nodes {
       let votes_balance = VotesBalance::<Test>::get(proposal_index, account(n));
       let reserve_balance: BalanceOf<Test> = <pallet_balances::Pallet<Test> as ReservableCurrency<AccountId>>::reserved_balance(&account(n));
       if n == 0 {
         assert_eq!(votes_balance, DEFAUT_VOTE_AMOUNT);
-        assert_eq!(reserve_balance, DEFAUT_VOTE_AMOUNT + model_initialization_cost);
+        assert_eq!(reserve_balance, model_initialization_cost + min_stake);
+        assert_eq!(vote_lock_balance(account(n)), DEFAUT_VOTE_AMOUNT);
       } else {
         assert_eq!(votes_balance, DEFAUT_VOTE_AMOUNT);
-        assert_eq!(reserve_balance, DEFAUT_VOTE_AMOUNT);
+        assert_eq!(reserve_balance, min_stake);
+        assert_eq!(vote_lock_balance(account(n)), DEFAUT_VOTE_AMOUNT);
       }
     }
 
@@ -1646,6 +1896,7 @@ fn test_balance_on_multiple_votes() {
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
           VoteType::Nay,
+          Conviction::None,
         )
       );
     }
@@ -1655,10 +1906,12 @@ fn test_balance_on_multiple_votes() {
       let reserve_balance: BalanceOf<Test> = <pallet_balances::Pallet<Test> as ReservableCurrency<AccountId>>::reserved_balance(&account(n));
       if n == 0 {
         assert_eq!(votes_balance, DEFAUT_VOTE_AMOUNT*2);
-        assert_eq!(reserve_balance, DEFAUT_VOTE_AMOUNT*2+model_initialization_cost);
+        assert_eq!(reserve_balance, model_initialization_cost + min_stake);
+        assert_eq!(vote_lock_balance(account(n)), DEFAUT_VOTE_AMOUNT*2);
       } else {
         assert_eq!(votes_balance, DEFAUT_VOTE_AMOUNT*2);
-        assert_eq!(reserve_balance, DEFAUT_VOTE_AMOUNT*2);
+        assert_eq!(reserve_balance, min_stake);
+        assert_eq!(vote_lock_balance(account(n)), DEFAUT_VOTE_AMOUNT*2);
       }
     }
 
@@ -1670,7 +1923,8 @@ fn test_balance_on_multiple_votes() {
           RuntimeOrigin::signed(account(n)),
           proposal_index,
           DEFAUT_VOTE_AMOUNT,
-          VoteType::Abstain
+          VoteType::Abstain,
+          Conviction::None,
         )
       );
     }
@@ -1680,11 +1934,742 @@ fn test_balance_on_multiple_votes() {
       let reserve_balance: BalanceOf<Test> = <pallet_balances::Pallet<Test> as ReservableCurrency<AccountId>>::reserved_balance(&account(n));
       if n == 0 {
         assert_eq!(votes_balance, DEFAUT_VOTE_AMOUNT*3);
-        assert_eq!(reserve_balance, DEFAUT_VOTE_AMOUNT*3+model_initialization_cost);
+        assert_eq!(reserve_balance, model_initialization_cost + min_stake);
+        assert_eq!(vote_lock_balance(account(n)), DEFAUT_VOTE_AMOUNT*3);
       } else {
         assert_eq!(votes_balance, DEFAUT_VOTE_AMOUNT*3);
-        assert_eq!(reserve_balance, DEFAUT_VOTE_AMOUNT*3);
+        assert_eq!(reserve_balance, min_stake);
+        assert_eq!(vote_lock_balance(account(n)), DEFAUT_VOTE_AMOUNT*3);
       }
     }
   })
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_cast_vote_conviction_voting_power() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_propose_activate(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    let voter = 200;
+    let _ = Balances::deposit_creating(&account(voter), DEFAUT_VOTE_AMOUNT);
+
+    assert_ok!(
+      SubnetVoting::cast_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+        DEFAUT_VOTE_AMOUNT,
+        VoteType::Yay,
+        Conviction::Locked3x,
+      )
+    );
+
+    let votes = Votes::<Test>::get(proposal_index);
+    assert_eq!(votes.yay, DEFAUT_VOTE_AMOUNT * 3);
+
+    // --- Locked until 4 lock periods pass after voting ends
+    let proposal = Proposals::<Test>::get(proposal_index);
+    let vote_locks = VoteLocks::<Test>::get(account(voter));
+    assert_eq!(
+      *vote_locks.get(&proposal_index).unwrap(), 
+      proposal.max_block + VoteLockingPeriod::get() * 4
+    );
+    assert_eq!(vote_lock_balance(account(voter)), DEFAUT_VOTE_AMOUNT);
+  })
+}
+
+#[test]
+fn test_cast_vote_lock_shared_across_proposals() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_propose_activate(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    // --- Second concurrent proposal on another path
+    let subnet_nodes = build_subnet_nodes(100, 100 + min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+    let model_initialization_cost = <pallet_network::Pallet<Test> as SubnetVote<<Test as frame_system::Config>::AccountId>>::get_model_initialization_cost();
+    let _ = Balances::deposit_creating(&account(0), model_initialization_cost+1000);
+
+    let mut subnet_data = default_add_subnet_data();
+    subnet_data.path = "hf/mistral".into();
+
+    assert_ok!(
+      SubnetVoting::propose(
+        RuntimeOrigin::signed(account(0)),
+        subnet_data, 
+        subnet_nodes,
+        PropsType::Activate,
+      )
+    );
+    let second_proposal_index = PropCount::<Test>::get() - 1;
+
+    let voter = 200;
+    let _ = Balances::deposit_creating(&account(voter), DEFAUT_VOTE_AMOUNT);
+
+    // --- The same balance votes on both proposals
+    for index in [proposal_index, second_proposal_index] {
+      assert_ok!(
+        SubnetVoting::cast_vote(
+          RuntimeOrigin::signed(account(voter)),
+          index,
+          DEFAUT_VOTE_AMOUNT,
+          VoteType::Yay,
+          Conviction::None,
+        )
+      );
+    }
+
+    assert_eq!(vote_lock_balance(account(voter)), DEFAUT_VOTE_AMOUNT);
+    assert_eq!(Balances::free_balance(&account(voter)), DEFAUT_VOTE_AMOUNT);
+
+    // --- But can't vote more than its balance on a single proposal
+    assert_err!(
+      SubnetVoting::cast_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+        1,
+        VoteType::Yay,
+        Conviction::None,
+      ),
+      Error::<Test>::NotEnoughBalanceToVote
+    );
+
+    // --- Unlocking one proposal keeps the lock of the other
+    assert_ok!(
+      SubnetVoting::cancel_proposal(
+        RuntimeOrigin::signed(account(0)),
+        proposal_index,
+      )
+    );
+
+    assert_ok!(
+      SubnetVoting::unlock(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+      )
+    );
+
+    assert_eq!(vote_lock_balance(account(voter)), DEFAUT_VOTE_AMOUNT);
+    assert_eq!(VoteLocks::<Test>::get(account(voter)).len(), 1);
+  })
+}
+
+#[test]
+fn test_unlock_conviction_lock_period() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_propose_activate(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    let voter = 200;
+    let _ = Balances::deposit_creating(&account(voter), DEFAUT_VOTE_AMOUNT);
+
+    assert_ok!(
+      SubnetVoting::cast_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+        DEFAUT_VOTE_AMOUNT,
+        VoteType::Yay,
+        Conviction::Locked1x,
+      )
+    );
+
+    let proposal = Proposals::<Test>::get(proposal_index);
+    System::set_block_number(proposal.max_block + 1);
+
+    assert_ok!(
+      SubnetVoting::execute(
+        RuntimeOrigin::signed(account(0)),
+        proposal_index,
+      )
+    );
+
+    // --- Locked for one lock period after voting ends
+    assert_err!(
+      SubnetVoting::unlock(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+      ),
+      Error::<Test>::VoteLocked
+    );
+
+    System::set_block_number(proposal.max_block + VoteLockingPeriod::get());
+
+    assert_ok!(
+      SubnetVoting::unlock(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+      )
+    );
+
+    assert_eq!(vote_lock_balance(account(voter)), 0);
+    assert_eq!(VoteLocks::<Test>::contains_key(account(voter)), false);
+  })
+}
+
+#[test]
+fn test_remove_vote() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_propose_activate(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    let voter = 200;
+    let other_voter = 201;
+    let _ = Balances::deposit_creating(&account(voter), DEFAUT_VOTE_AMOUNT);
+    let _ = Balances::deposit_creating(&account(other_voter), DEFAUT_VOTE_AMOUNT);
+
+    for v in [voter, other_voter] {
+      assert_ok!(
+        SubnetVoting::cast_vote(
+          RuntimeOrigin::signed(account(v)),
+          proposal_index,
+          DEFAUT_VOTE_AMOUNT,
+          VoteType::Yay,
+          Conviction::Locked1x,
+        )
+      );
+    }
+
+    assert_eq!(Votes::<Test>::get(proposal_index).yay, DEFAUT_VOTE_AMOUNT * 2);
+
+    assert_ok!(
+      SubnetVoting::remove_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+      )
+    );
+
+    // --- Only the voters votes are removed
+    assert_eq!(Votes::<Test>::get(proposal_index).yay, DEFAUT_VOTE_AMOUNT);
+    assert_eq!(VoterVotes::<Test>::contains_key(proposal_index, account(voter)), false);
+    assert_eq!(VoterVotes::<Test>::get(proposal_index, account(other_voter)).yay, DEFAUT_VOTE_AMOUNT);
+
+    // --- The vote balance is unlocked without waiting for the conviction lock
+    assert_eq!(VotesBalance::<Test>::contains_key(proposal_index, account(voter)), false);
+    assert_eq!(VoteLocks::<Test>::contains_key(account(voter)), false);
+    assert_eq!(vote_lock_balance(account(voter)), 0);
+    assert_eq!(vote_lock_balance(account(other_voter)), DEFAUT_VOTE_AMOUNT);
+
+    // --- Can vote again while voting is open
+    assert_ok!(
+      SubnetVoting::cast_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+        DEFAUT_VOTE_AMOUNT,
+        VoteType::Nay,
+        Conviction::Locked1x,
+      )
+    );
+
+    let votes = Votes::<Test>::get(proposal_index);
+    assert_eq!(votes.yay, DEFAUT_VOTE_AMOUNT);
+    assert_eq!(votes.nay, DEFAUT_VOTE_AMOUNT);
+  })
+}
+
+#[test]
+fn test_remove_vote_not_exist_err() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_propose_activate(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    assert_err!(
+      SubnetVoting::remove_vote(
+        RuntimeOrigin::signed(account(200)),
+        proposal_index,
+      ),
+      Error::<Test>::VoteNotExist
+    );
+
+    assert_err!(
+      SubnetVoting::change_vote(
+        RuntimeOrigin::signed(account(200)),
+        proposal_index,
+        VoteType::Nay,
+      ),
+      Error::<Test>::VoteNotExist
+    );
+  })
+}
+
+#[test]
+fn test_remove_vote_voting_not_open_err() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_propose_activate(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    let voter = 200;
+    let _ = Balances::deposit_creating(&account(voter), DEFAUT_VOTE_AMOUNT);
+
+    assert_ok!(
+      SubnetVoting::cast_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+        DEFAUT_VOTE_AMOUNT,
+        VoteType::Yay,
+        Conviction::None,
+      )
+    );
+
+    System::set_block_number(System::block_number() + VotingPeriod::get() + 1);
+
+    assert_err!(
+      SubnetVoting::remove_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+      ),
+      Error::<Test>::VotingNotOpen
+    );
+
+    assert_err!(
+      SubnetVoting::change_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+        VoteType::Nay,
+      ),
+      Error::<Test>::VotingNotOpen
+    );
+  })
+}
+
+#[test]
+fn test_change_vote() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_propose_activate(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    let voter = 200;
+    let _ = Balances::deposit_creating(&account(voter), DEFAUT_VOTE_AMOUNT);
+
+    // --- Split votes between directions with different convictions
+    assert_ok!(
+      SubnetVoting::cast_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+        DEFAUT_VOTE_AMOUNT / 2,
+        VoteType::Yay,
+        Conviction::Locked2x,
+      )
+    );
+
+    assert_ok!(
+      SubnetVoting::cast_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+        DEFAUT_VOTE_AMOUNT / 2,
+        VoteType::Nay,
+        Conviction::Locked1x,
+      )
+    );
+
+    let vote_power = DEFAUT_VOTE_AMOUNT / 2 * 2 + DEFAUT_VOTE_AMOUNT / 2;
+    let vote_locks = VoteLocks::<Test>::get(account(voter));
+
+    assert_ok!(
+      SubnetVoting::change_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+        VoteType::Abstain,
+      )
+    );
+
+    let votes = Votes::<Test>::get(proposal_index);
+    assert_eq!(votes.yay, 0);
+    assert_eq!(votes.nay, 0);
+    assert_eq!(votes.abstain, vote_power);
+
+    let voter_votes = VoterVotes::<Test>::get(proposal_index, account(voter));
+    assert_eq!(voter_votes.abstain, vote_power);
+
+    // --- Vote balance and lock are unchanged
+    assert_eq!(VotesBalance::<Test>::get(proposal_index, account(voter)), DEFAUT_VOTE_AMOUNT);
+    assert_eq!(VoteLocks::<Test>::get(account(voter)), vote_locks);
+    assert_eq!(vote_lock_balance(account(voter)), DEFAUT_VOTE_AMOUNT);
+
+    assert_err!(
+      SubnetVoting::change_vote(
+        RuntimeOrigin::signed(account(voter)),
+        proposal_index,
+        VoteType::Abstain,
+      ),
+      Error::<Test>::VoteUnchanged
+    );
+  })
+}
+
+fn build_approved_proposal(min_subnet_nodes: u32) -> u32 {
+  let proposal_index = build_propose_activate(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+  for n in 0..min_subnet_nodes {
+    let _ = Balances::deposit_creating(&account(n), DEFAUT_VOTE_AMOUNT);
+
+    assert_ok!(
+      SubnetVoting::cast_vote(
+        RuntimeOrigin::signed(account(n)),
+        proposal_index,
+        DEFAUT_VOTE_AMOUNT,
+        VoteType::Yay,
+        Conviction::None,
+      )
+    );
+  }
+
+  System::set_block_number(System::block_number() + VotingPeriod::get() + 1);
+
+  assert_ok!(
+    SubnetVoting::execute(
+      RuntimeOrigin::signed(account(0)),
+      proposal_index,
+    )
+  );
+
+  proposal_index
+}
+
+#[test]
+fn test_execute_approved_keeps_reserves_until_enactment() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_approved_proposal(min_subnet_nodes);
+
+    let path: Vec<u8> = DEFAULT_MODEL_PATH.into();
+    post_execute_approved_ensures(proposal_index, path.clone());
+
+    // --- Subnet isn't activated before the enactment block
+    assert_eq!(pallet_network::SubnetPaths::<Test>::get(path.clone()), None);
+
+    let proposal = Proposals::<Test>::get(proposal_index);
+    let min_stake = pallet_network::MinStakeBalance::<Test>::get();
+    assert_eq!(Balances::reserved_balance(&account(0)), proposal.proposer_stake + min_stake);
+    for n in 1..min_subnet_nodes {
+      assert_eq!(Balances::reserved_balance(&account(n)), min_stake);
+    }
+
+    // --- Can't propose the path again while waiting for enactment
+    let _ = Balances::deposit_creating(&account(0), proposal.proposer_stake + 1000);
+    assert_err!(
+      SubnetVoting::propose(
+        RuntimeOrigin::signed(account(0)),
+        default_add_subnet_data(), 
+        build_subnet_nodes(100, 100 + min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT),
+        PropsType::Activate,
+      ),
+      Error::<Test>::ProposalInvalid
+    );
+
+    // --- Not enacted before the enactment block
+    SubnetVoting::on_initialize(proposal.enactment_block - 1);
+    assert_eq!(Proposals::<Test>::get(proposal_index).proposal_status, PropsStatus::Approved);
+
+    enact_proposal(proposal_index);
+
+    post_activate_execute_succeeded_ensures(proposal_index, path);
+  })
+}
+
+#[test]
+fn test_enactment_failure_recorded() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_approved_proposal(min_subnet_nodes);
+
+    // --- Activation fails once the network reaches its max subnets
+    pallet_network::MaxSubnets::<Test>::set(0);
+
+    enact_proposal(proposal_index);
+
+    assert_eq!(
+      EnactmentResults::<Test>::get(proposal_index), 
+      Some(Err(pallet_network::Error::<Test>::MaxSubnets.into()))
+    );
+
+    let proposal = Proposals::<Test>::get(proposal_index);
+    assert_eq!(proposal.proposal_status, PropsStatus::Failed);
+    assert_eq!(proposal.proposer_stake, 0);
+
+    // --- Subnet wasn't activated and the reserves were freed
+    let path: Vec<u8> = DEFAULT_MODEL_PATH.into();
+    assert_eq!(PropsPathStatus::<Test>::get(path.clone()), PropsStatus::Failed);
+    assert_eq!(pallet_network::SubnetPaths::<Test>::get(path), None);
+
+    for n in 0..min_subnet_nodes {
+      assert_eq!(Balances::reserved_balance(&account(n)), 0);
+    }
+  })
+}
+
+#[test]
+fn test_enact_activate_registers_joined_subnet_nodes() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = propose_activate_without_joining(0, min_subnet_nodes + 1, DEFAULT_DEPOSIT_AMOUNT);
+
+    // --- The last subnet node doesn't join
+    join_subnet_nodes(proposal_index, 0, min_subnet_nodes);
+
+    for n in 0..min_subnet_nodes {
+      let _ = Balances::deposit_creating(&account(n), DEFAUT_VOTE_AMOUNT);
+
+      assert_ok!(
+        SubnetVoting::cast_vote(
+          RuntimeOrigin::signed(account(n)),
+          proposal_index,
+          DEFAUT_VOTE_AMOUNT,
+          VoteType::Yay,
+          Conviction::None,
+        )
+      );
+    }
+
+    System::set_block_number(System::block_number() + VotingPeriod::get() + 1);
+
+    assert_ok!(
+      SubnetVoting::execute(
+        RuntimeOrigin::signed(account(0)),
+        proposal_index,
+      )
+    );
+
+    enact_proposal(proposal_index);
+
+    assert_eq!(EnactmentResults::<Test>::get(proposal_index), Some(Ok(())));
+    assert_eq!(JoinedSubnetNodes::<Test>::iter_prefix(proposal_index).count(), 0);
+
+    let path: Vec<u8> = DEFAULT_MODEL_PATH.into();
+    let subnet_id = pallet_network::SubnetPaths::<Test>::get(path).unwrap();
+    assert_eq!(pallet_network::TotalSubnetNodes::<Test>::get(subnet_id), min_subnet_nodes);
+
+    for n in 0..min_subnet_nodes {
+      assert_eq!(pallet_network::SubnetNodesData::<Test>::contains_key(subnet_id, account(n)), true);
+    }
+
+    assert_eq!(pallet_network::SubnetNodesData::<Test>::contains_key(subnet_id, account(min_subnet_nodes)), false);
+    assert_eq!(pallet_network::AccountSubnetStake::<Test>::get(account(min_subnet_nodes), subnet_id), 0);
+  })
+}
+
+#[test]
+fn test_cancel_enactment() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_approved_proposal(min_subnet_nodes);
+
+    let proposal = Proposals::<Test>::get(proposal_index);
+
+    assert_ok!(SubnetVoting::cancel_enactment(proposal_index));
+
+    let path: Vec<u8> = DEFAULT_MODEL_PATH.into();
+    let cancelled_proposal = Proposals::<Test>::get(proposal_index);
+    assert_eq!(cancelled_proposal.proposal_status, PropsStatus::Cancelled);
+    assert_eq!(cancelled_proposal.proposer_stake, 0);
+    assert_eq!(PropsPathStatus::<Test>::get(path.clone()), PropsStatus::Cancelled);
+    assert!(!EnactmentQueue::<Test>::get(proposal.enactment_block).contains(&proposal_index));
+
+    // --- Reserves are given back
+    for n in 0..min_subnet_nodes {
+      assert_eq!(Balances::reserved_balance(&account(n)), 0);
+    }
+
+    // --- Nothing is enacted on the enactment block
+    enact_proposal(proposal_index);
+    assert_eq!(EnactmentResults::<Test>::get(proposal_index), None);
+    assert_eq!(pallet_network::SubnetPaths::<Test>::get(path), None);
+
+    assert_err!(
+      SubnetVoting::cancel_enactment(proposal_index),
+      Error::<Test>::ProposalNotApproved
+    );
+
+    // --- Cancelled votes unlock immediately
+    post_proposal_conclusion_unlocks(proposal_index, 0, min_subnet_nodes, DEFAUT_VOTE_AMOUNT);
+  })
+}
+
+#[test]
+fn test_cancel_enactment_not_approved_err() {
+  new_test_ext().execute_with(|| {
+    let min_subnet_nodes = get_default_min_subnet_nodes();
+    let proposal_index = build_propose_activate(DEFAULT_MODEL_PATH.into(), 0, min_subnet_nodes, DEFAULT_DEPOSIT_AMOUNT);
+
+    assert_err!(
+      SubnetVoting::cancel_enactment(proposal_index),
+      Error::<Test>::ProposalNotApproved
+    );
+
+    assert_err!(
+      SubnetVoting::cancel_enactment(proposal_index + 1),
+      Error::<Test>::ProposalInvalid
+    );
+  })
+}
+
+fn build_propose_parameter_change(parameter: NetworkParameter) -> u32 {
+  let _ = Balances::deposit_creating(&account(0), MinProposalStake::get() + 1000);
+
+  assert_ok!(
+    SubnetVoting::propose(
+      RuntimeOrigin::signed(account(0)),
+      PreSubnetData::default(), 
+      Vec::new(),
+      PropsType::ParameterChange(parameter),
+    )
+  );
+
+  PropCount::<Test>::get() - 1
+}
+
+fn build_approved_parameter_change(parameter: NetworkParameter) -> u32 {
+  let proposal_index = build_propose_parameter_change(parameter);
+
+  for n in 0..get_default_min_subnet_nodes() {
+    let _ = Balances::deposit_creating(&account(n), DEFAUT_VOTE_AMOUNT);
+
+    assert_ok!(
+      SubnetVoting::cast_vote(
+        RuntimeOrigin::signed(account(n)),
+        proposal_index,
+        DEFAUT_VOTE_AMOUNT,
+        VoteType::Yay,
+        Conviction::None,
+      )
+    );
+  }
+
+  System::set_block_number(System::block_number() + VotingPeriod::get() + 1);
+
+  assert_ok!(
+    SubnetVoting::execute(
+      RuntimeOrigin::signed(account(0)),
+      proposal_index,
+    )
+  );
+
+  proposal_index
+}
+
+#[test]
+fn test_parameter_change_enacted() {
+  new_test_ext().execute_with(|| {
+    let proposal_index = build_approved_parameter_change(NetworkParameter::MaxSubnets(50));
+
+    let proposal = Proposals::<Test>::get(proposal_index);
+    assert_eq!(proposal.proposal_status, PropsStatus::Approved);
+    assert_eq!(Balances::reserved_balance(&account(0)), MinProposalStake::get());
+
+    // --- Parameter changes don't track a subnet path
+    assert_eq!(PropsPathStatus::<Test>::contains_key(Vec::<u8>::new()), false);
+
+    enact_proposal(proposal_index);
+
+    assert_eq!(EnactmentResults::<Test>::get(proposal_index), Some(Ok(())));
+    assert_eq!(pallet_network::MaxSubnets::<Test>::get(), 50);
+
+    let proposal = Proposals::<Test>::get(proposal_index);
+    assert_eq!(proposal.proposal_status, PropsStatus::Succeeded);
+
+    // --- Proposer stake is given back
+    assert_eq!(Balances::reserved_balance(&account(0)), 0);
+  })
+}
+
+#[test]
+fn test_parameter_change_concurrent_proposals() {
+  new_test_ext().execute_with(|| {
+    let proposal_index = build_propose_parameter_change(NetworkParameter::MaxSubnets(50));
+    let second_proposal_index = build_propose_parameter_change(NetworkParameter::StakeUnbondingEpochs(10));
+
+    assert_eq!(second_proposal_index, proposal_index + 1);
+    assert_eq!(ActiveProposals::<Test>::get(), 2);
+  })
+}
+
+#[test]
+fn test_parameter_change_out_of_bounds_err() {
+  new_test_ext().execute_with(|| {
+    let _ = Balances::deposit_creating(&account(0), MinProposalStake::get() + 1000);
+
+    assert_err!(
+      SubnetVoting::propose(
+        RuntimeOrigin::signed(account(0)),
+        PreSubnetData::default(), 
+        Vec::new(),
+        PropsType::ParameterChange(NetworkParameter::MaxSubnets(101)),
+      ),
+      Error::<Test>::NetworkParameterOutOfBounds
+    );
+
+    assert_err!(
+      SubnetVoting::propose(
+        RuntimeOrigin::signed(account(0)),
+        PreSubnetData::default(), 
+        Vec::new(),
+        PropsType::ParameterChange(NetworkParameter::SlashPercentage(10001)),
+      ),
+      Error::<Test>::NetworkParameterOutOfBounds
+    );
+
+    assert_err!(
+      SubnetVoting::propose(
+        RuntimeOrigin::signed(account(0)),
+        PreSubnetData::default(), 
+        Vec::new(),
+        PropsType::ParameterChange(NetworkParameter::BaseSubnetReward(MaxBaseSubnetReward::get() + 1)),
+      ),
+      Error::<Test>::NetworkParameterOutOfBounds
+    );
+
+    assert_err!(
+      SubnetVoting::propose(
+        RuntimeOrigin::signed(account(0)),
+        PreSubnetData::default(), 
+        Vec::new(),
+        PropsType::ParameterChange(NetworkParameter::MinStakeBalance(MaxMinStakeBalance::get() + 1)),
+      ),
+      Error::<Test>::NetworkParameterOutOfBounds
+    );
+  })
+}
+
+#[test]
+fn test_parameter_change_with_subnet_err() {
+  new_test_ext().execute_with(|| {
+    let _ = Balances::deposit_creating(&account(0), MinProposalStake::get() + 1000);
+
+    assert_err!(
+      SubnetVoting::propose(
+        RuntimeOrigin::signed(account(0)),
+        default_add_subnet_data(), 
+        Vec::new(),
+        PropsType::ParameterChange(NetworkParameter::MaxSubnets(50)),
+      ),
+      Error::<Test>::InvalidParameterChange
+    );
+
+    assert_err!(
+      SubnetVoting::propose(
+        RuntimeOrigin::signed(account(0)),
+        PreSubnetData::default(), 
+        build_subnet_nodes(0, 1, DEFAULT_DEPOSIT_AMOUNT),
+        PropsType::ParameterChange(NetworkParameter::MaxSubnets(50)),
+      ),
+      Error::<Test>::InvalidParameterChange
+    );
+  })
+}
+
+#[test]
+fn test_parameter_change_enactment_failure_recorded() {
+  new_test_ext().execute_with(|| {
+    // --- Within proposal bounds but the network pallet limits the bounty to the initialization cost
+    let subnet_removal_bounty = pallet_network::SubnetRemovalBounty::<Test>::get();
+    let proposal_index = build_approved_parameter_change(NetworkParameter::SubnetRemovalBounty(u128::MAX));
+
+    enact_proposal(proposal_index);
+
+    assert_eq!(
+      EnactmentResults::<Test>::get(proposal_index), 
+      Some(Err(pallet_network::Error::<Test>::InvalidSubnetRemovalBounty.into()))
+    );
+    assert_eq!(Proposals::<Test>::get(proposal_index).proposal_status, PropsStatus::Failed);
+    assert_eq!(pallet_network::SubnetRemovalBounty::<Test>::get(), subnet_removal_bounty);
+    assert_eq!(Balances::reserved_balance(&account(0)), 0);
+  })
+}
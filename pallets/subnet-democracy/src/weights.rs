@@ -35,7 +35,11 @@ pub trait WeightInfo {
 	fn cast_vote() -> Weight;
 	fn execute() -> Weight;
 	fn cancel_proposal() -> Weight;
-	fn unreserve() -> Weight;
+	fn unlock() -> Weight;
+	fn remove_vote() -> Weight;
+	fn change_vote() -> Weight;
+	fn enact_proposal() -> Weight;
+	fn join_proposal() -> Weight;
 }
 
 /// Weights for `pallet_subnet_democracy` using the Substrate node and recommended hardware.
@@ -45,7 +49,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `SubnetVoting::ActiveProposals` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `SubnetVoting::PropCount` (r:1 w:1)
 	/// Proof: `SubnetVoting::PropCount` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// Storage: `System::Account` (r:6 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `Network::SubnetPaths` (r:1 w:0)
 	/// Proof: `Network::SubnetPaths` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -63,14 +67,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Network::MinStakeBalance` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `SubnetVoting::Proposals` (r:0 w:1)
 	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::TallyQueue` (r:1 w:1)
+	/// Proof: `SubnetVoting::TallyQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn propose() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1556`
 		//  Estimated: `16608`
 		// Minimum execution time: 69_222_000 picoseconds.
 		Weight::from_parts(85_847_000, 16608)
-			.saturating_add(T::DbWeight::get().reads(15_u64))
-			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
 	}
 	/// Storage: `SubnetVoting::Proposals` (r:1 w:0)
 	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -82,14 +88,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `SubnetVoting::VotesBalance` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `SubnetVoting::Votes` (r:1 w:1)
 	/// Proof: `SubnetVoting::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoterVotes` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoterVotes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoteLocks` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoteLocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	fn cast_vote() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `718`
-		//  Estimated: `4183`
-		// Minimum execution time: 90_837_000 picoseconds.
-		Weight::from_parts(93_248_000, 4183)
-			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+		Weight::from_parts(93_248_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
 	/// Storage: `SubnetVoting::Proposals` (r:1 w:1)
 	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -103,14 +111,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `SubnetVoting::ActiveProposals` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `SubnetVoting::PropsPathStatus` (r:0 w:1)
 	/// Proof: `SubnetVoting::PropsPathStatus` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::EnactmentQueue` (r:1 w:1)
+	/// Proof: `SubnetVoting::EnactmentQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn execute() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1188`
 		//  Estimated: `4653`
 		// Minimum execution time: 90_708_000 picoseconds.
 		Weight::from_parts(140_448_000, 4653)
-			.saturating_add(T::DbWeight::get().reads(5_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `SubnetVoting::Proposals` (r:1 w:1)
 	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -131,14 +141,78 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `SubnetVoting::VotesBalance` (r:1 w:1)
 	/// Proof: `SubnetVoting::VotesBalance` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoterVotes` (r:0 w:1)
+	/// Proof: `SubnetVoting::VoterVotes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoteLocks` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoteLocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	fn unreserve() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `841`
-		//  Estimated: `4306`
-		// Minimum execution time: 43_200_000 picoseconds.
-		Weight::from_parts(44_990_000, 4306)
+	fn unlock() -> Weight {
+		Weight::from_parts(44_990_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `SubnetVoting::Proposals` (r:1 w:0)
+	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoterVotes` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoterVotes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::Votes` (r:1 w:1)
+	/// Proof: `SubnetVoting::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VotesBalance` (r:0 w:1)
+	/// Proof: `SubnetVoting::VotesBalance` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoteLocks` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoteLocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn remove_vote() -> Weight {
+		Weight::from_parts(50_124_000, 4764)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `SubnetVoting::Proposals` (r:1 w:0)
+	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoterVotes` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoterVotes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::Votes` (r:1 w:1)
+	/// Proof: `SubnetVoting::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn change_vote() -> Weight {
+		Weight::from_parts(26_018_000, 4277)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `SubnetVoting::Proposals` (r:1 w:1)
+	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:13 w:13)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `SubnetVoting::PropsPathStatus` (r:0 w:1)
+	/// Proof: `SubnetVoting::PropsPathStatus` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetPaths` (r:1 w:1)
+	/// Proof: `Network::SubnetPaths` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetsData` (r:1 w:1)
+	/// Proof: `Network::SubnetsData` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::MaxSubnets` (r:1 w:0)
+	/// Proof: `Network::MaxSubnets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::TotalSubnetNodes` (r:1 w:1)
+	/// Proof: `Network::TotalSubnetNodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::EnactmentResults` (r:0 w:1)
+	/// Proof: `SubnetVoting::EnactmentResults` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn enact_proposal() -> Weight {
+		Weight::from_parts(431_870_000, 34107)
+			.saturating_add(T::DbWeight::get().reads(18_u64))
+			.saturating_add(T::DbWeight::get().writes(19_u64))
+	}
+	/// Storage: `SubnetVoting::Proposals` (r:1 w:0)
+	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::JoinedSubnetNodes` (r:1 w:1)
+	/// Proof: `SubnetVoting::JoinedSubnetNodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn join_proposal() -> Weight {
+		Weight::from_parts(45_000_000, 4653)
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
@@ -150,7 +224,7 @@ impl WeightInfo for () {
 	/// Proof: `SubnetVoting::ActiveProposals` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `SubnetVoting::PropCount` (r:1 w:1)
 	/// Proof: `SubnetVoting::PropCount` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
-	/// Storage: `System::Account` (r:6 w:1)
+	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
 	/// Storage: `Network::SubnetPaths` (r:1 w:0)
 	/// Proof: `Network::SubnetPaths` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -168,14 +242,16 @@ impl WeightInfo for () {
 	/// Proof: `Network::MinStakeBalance` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `SubnetVoting::Proposals` (r:0 w:1)
 	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::TallyQueue` (r:1 w:1)
+	/// Proof: `SubnetVoting::TallyQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn propose() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1556`
 		//  Estimated: `16608`
 		// Minimum execution time: 69_222_000 picoseconds.
 		Weight::from_parts(85_847_000, 16608)
-			.saturating_add(RocksDbWeight::get().reads(15_u64))
-			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
 	}
 	/// Storage: `SubnetVoting::Proposals` (r:1 w:0)
 	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -187,14 +263,16 @@ impl WeightInfo for () {
 	/// Proof: `SubnetVoting::VotesBalance` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `SubnetVoting::Votes` (r:1 w:1)
 	/// Proof: `SubnetVoting::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoterVotes` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoterVotes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoteLocks` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoteLocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	fn cast_vote() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `718`
-		//  Estimated: `4183`
-		// Minimum execution time: 90_837_000 picoseconds.
-		Weight::from_parts(93_248_000, 4183)
-			.saturating_add(RocksDbWeight::get().reads(5_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+		Weight::from_parts(93_248_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
 	/// Storage: `SubnetVoting::Proposals` (r:1 w:1)
 	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -208,14 +286,16 @@ impl WeightInfo for () {
 	/// Proof: `SubnetVoting::ActiveProposals` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `SubnetVoting::PropsPathStatus` (r:0 w:1)
 	/// Proof: `SubnetVoting::PropsPathStatus` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::EnactmentQueue` (r:1 w:1)
+	/// Proof: `SubnetVoting::EnactmentQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn execute() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1188`
 		//  Estimated: `4653`
 		// Minimum execution time: 90_708_000 picoseconds.
 		Weight::from_parts(140_448_000, 4653)
-			.saturating_add(RocksDbWeight::get().reads(5_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `SubnetVoting::Proposals` (r:1 w:1)
 	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -236,14 +316,78 @@ impl WeightInfo for () {
 	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `SubnetVoting::VotesBalance` (r:1 w:1)
 	/// Proof: `SubnetVoting::VotesBalance` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoterVotes` (r:0 w:1)
+	/// Proof: `SubnetVoting::VoterVotes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoteLocks` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoteLocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
 	/// Storage: `System::Account` (r:1 w:1)
 	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
-	fn unreserve() -> Weight {
-		// Proof Size summary in bytes:
-		//  Measured:  `841`
-		//  Estimated: `4306`
-		// Minimum execution time: 43_200_000 picoseconds.
-		Weight::from_parts(44_990_000, 4306)
+	fn unlock() -> Weight {
+		Weight::from_parts(44_990_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `SubnetVoting::Proposals` (r:1 w:0)
+	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoterVotes` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoterVotes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::Votes` (r:1 w:1)
+	/// Proof: `SubnetVoting::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VotesBalance` (r:0 w:1)
+	/// Proof: `SubnetVoting::VotesBalance` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoteLocks` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoteLocks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn remove_vote() -> Weight {
+		Weight::from_parts(50_124_000, 4764)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `SubnetVoting::Proposals` (r:1 w:0)
+	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::VoterVotes` (r:1 w:1)
+	/// Proof: `SubnetVoting::VoterVotes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::Votes` (r:1 w:1)
+	/// Proof: `SubnetVoting::Votes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn change_vote() -> Weight {
+		Weight::from_parts(26_018_000, 4277)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `SubnetVoting::Proposals` (r:1 w:1)
+	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:13 w:13)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `SubnetVoting::PropsPathStatus` (r:0 w:1)
+	/// Proof: `SubnetVoting::PropsPathStatus` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetPaths` (r:1 w:1)
+	/// Proof: `Network::SubnetPaths` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::SubnetsData` (r:1 w:1)
+	/// Proof: `Network::SubnetsData` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::MaxSubnets` (r:1 w:0)
+	/// Proof: `Network::MaxSubnets` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Network::TotalSubnetNodes` (r:1 w:1)
+	/// Proof: `Network::TotalSubnetNodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::EnactmentResults` (r:0 w:1)
+	/// Proof: `SubnetVoting::EnactmentResults` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn enact_proposal() -> Weight {
+		Weight::from_parts(431_870_000, 34107)
+			.saturating_add(RocksDbWeight::get().reads(18_u64))
+			.saturating_add(RocksDbWeight::get().writes(19_u64))
+	}
+	/// Storage: `SubnetVoting::Proposals` (r:1 w:0)
+	/// Proof: `SubnetVoting::Proposals` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `SubnetVoting::JoinedSubnetNodes` (r:1 w:1)
+	/// Proof: `SubnetVoting::JoinedSubnetNodes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn join_proposal() -> Weight {
+		Weight::from_parts(45_000_000, 4653)
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
@@ -52,7 +52,6 @@ use frame_system::{EnsureRoot, EnsureSigned};
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
-use codec::Encode;
 
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
@@ -591,6 +590,8 @@ impl pallet_network::Config for Runtime {
 	type OffchainSignature = Signature;
 	type OffchainPublic = AccountPublic;
 	type Randomness = InsecureRandomnessCollectiveFlip;
+	type ElectionRandomness = pallet_network::CommitRevealRandomness<Runtime>;
+	type StakeVaultSubsidy = Rewards;
 	type PalletId = NetworkPalletId;
 	type SubnetInitializationCost = SubnetInitializationCost;
 }
@@ -599,29 +600,38 @@ parameter_types! {
 	// Mainnet
 	// pub const VotingPeriod: BlockNumber = DAYS * 21;
 	// pub const EnactmentPeriod: BlockNumber = DAYS * 7;
+	// pub const VoteLockingPeriod: BlockNumber = DAYS * 7;
 
 	// Testnet
 	pub const VotingPeriod: BlockNumber = DAYS * 6;
 	pub const EnactmentPeriod: BlockNumber = DAYS * 14;
+	pub const VoteLockingPeriod: BlockNumber = DAYS * 1;
 
 	// Testing
 	// pub const VotingPeriod: BlockNumber = 50; // ~5 minutes
 	// pub const EnactmentPeriod: BlockNumber = 600; // ~60 minutes
+	// pub const VoteLockingPeriod: BlockNumber = 100; // ~10 minutes
 
 	pub const MinProposalStake: u128 = 100_000_000_000_000_000_000; // 100 * 1e18
+	pub const MaxBaseSubnetReward: u128 = 100_000_000_000_000_000_000; // 100 * 1e18
+	pub const MaxMinStakeBalance: u128 = 100_000_000_000_000_000_000_000; // 100,000 * 1e18
 }
 
 impl pallet_subnet_democracy::Config for Runtime {
 	type WeightInfo = ();
 	type RuntimeEvent = RuntimeEvent;
 	type SubnetVote = Network;
+	type NetworkAdminInterface = Network;
 	type Currency = Balances;
 	type MaxActivateProposals = ConstU32<1>;
 	type MaxDeactivateProposals = ConstU32<32>;
 	type MaxProposals = ConstU32<32>;
 	type VotingPeriod = VotingPeriod;
 	type EnactmentPeriod = EnactmentPeriod;
+	type VoteLockingPeriod = VoteLockingPeriod;
 	type MinProposalStake = MinProposalStake;
+	type MaxBaseSubnetReward = MaxBaseSubnetReward;
+	type MaxMinStakeBalance = MaxMinStakeBalance;
 }
 
 // cargo check -p node-template-runtime --release
@@ -669,6 +679,13 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Storage migrations run on the next runtime upgrade.
+pub type Migrations = (
+	pallet_network::migrations::v1::MigrateToV1<Runtime>,
+	pallet_network::migrations::v2::MigrateToV2<Runtime>,
+	pallet_subnet_democracy::migrations::v1::MigrateToV1<Runtime>,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -676,6 +693,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -863,35 +881,39 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl network_custom_rpc_runtime_api::NetworkRuntimeApi<Block> for Runtime {
-		fn get_subnet_nodes(model_id: u32) -> Vec<u8> {
-			let result = Network::get_subnet_nodes(model_id);
-			result.encode()
+	impl network_custom_rpc_runtime_api::NetworkRuntimeApi<Block, AccountId> for Runtime {
+		fn get_subnet_nodes(subnet_id: u32) -> Vec<pallet_network::SubnetNode<AccountId>> {
+			Network::get_subnet_nodes(subnet_id)
 		}
-		fn get_subnet_nodes_included(model_id: u32) -> Vec<u8> {
-			let result = Network::get_subnet_nodes_included(model_id);
-			result.encode()
+		fn get_subnet_nodes_included(subnet_id: u32) -> Vec<pallet_network::SubnetNode<AccountId>> {
+			Network::get_subnet_nodes_included(subnet_id)
 		}
-		fn get_subnet_nodes_submittable(model_id: u32) -> Vec<u8> {
-			let result = Network::get_subnet_nodes_submittable(model_id);
-			result.encode()
+		fn get_subnet_nodes_submittable(subnet_id: u32) -> Vec<pallet_network::SubnetNode<AccountId>> {
+			Network::get_subnet_nodes_submittable(subnet_id)
 		}
 		fn get_subnet_nodes_model_unconfirmed_count(model_id: u32) -> u32 {
-			let result = Network::get_subnet_nodes_model_unconfirmed_count(model_id);
-			result
-			// result.encode()
+			Network::get_subnet_nodes_model_unconfirmed_count(model_id)
 		}
-		fn get_consensus_data(model_id: u32, epoch: u32) -> Vec<u8> {
-			let result = Network::get_consensus_data(model_id, epoch);
-			result.encode()
+		fn get_consensus_data(subnet_id: u32, epoch: u32) -> Option<pallet_network::RewardsData<AccountId>> {
+			Network::get_consensus_data(subnet_id, epoch)
 		}
-		fn get_accountant_data(model_id: u32, id: u32) -> Vec<u8> {
-			let result = Network::get_accountant_data(model_id, id);
-			result.encode()
+		fn get_accountant_data(subnet_id: u32, id: u32) -> Option<pallet_network::AccountantDataParams<AccountId>> {
+			Network::get_accountant_data(subnet_id, id)
 		}
 		fn get_minimum_subnet_nodes(subnet_id: u32, memory_mb: u128) -> u32 {
-			let result = Network::get_minimum_subnet_nodes(subnet_id, memory_mb);
-			result
+			Network::get_minimum_subnet_nodes(subnet_id, memory_mb)
+		}
+		fn get_account_info(account_id: AccountId) -> pallet_network::AccountNetworkInfo<AccountId> {
+			Network::get_account_info(account_id)
+		}
+		fn get_subnet_epoch_info(subnet_id: u32, account_id: AccountId) -> pallet_network::SubnetEpochInfo {
+			Network::get_subnet_epoch_info(subnet_id, account_id)
+		}
+		fn get_reward_projection(subnet_id: u32, account_id: AccountId, stake: u128, delegate_stake: u128) -> pallet_network::RewardProjection {
+			Network::get_reward_projection(subnet_id, account_id, stake, delegate_stake)
+		}
+		fn get_epoch_rewards(subnet_id: u32, epoch: u32) -> Option<pallet_network::EpochRewardsData<AccountId>> {
+			Network::get_epoch_rewards(subnet_id, epoch)
 		}
 	}
 